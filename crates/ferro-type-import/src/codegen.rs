@@ -4,7 +4,7 @@
 //! with serde derives for JSON serialization compatibility.
 
 use convert_case::{Case, Casing};
-use ferro_type::{Field, Literal, Primitive, TypeDef, TypeParam};
+use ferro_type::{Field, IntWidth, Literal, Primitive, TypeDef, TypeParam};
 
 use crate::TsTypeInfo;
 
@@ -33,6 +33,7 @@ fn generate_type(type_info: &TsTypeInfo) -> String {
             name,
             type_params,
             def,
+            ..
         } => generate_generic_type(name, type_params, def, type_info.is_interface),
         _ => format!("// Unsupported type: {}", type_info.name),
     }
@@ -43,7 +44,7 @@ fn generate_named_type(name: &str, def: &TypeDef, is_interface: bool) -> String
     match def {
         TypeDef::Object(fields) if is_interface => generate_struct(name, fields, &[]),
         TypeDef::Union(variants) => generate_union_type(name, variants),
-        TypeDef::Primitive(p) => generate_type_alias(name, &primitive_to_rust(p)),
+        TypeDef::Primitive(p) => generate_type_alias(name, primitive_to_rust(p)),
         TypeDef::Array(inner) => {
             let inner_type = typedef_to_rust_type(inner);
             generate_type_alias(name, &format!("Vec<{}>", inner_type))
@@ -421,7 +422,7 @@ fn typedef_to_rust_type(def: &TypeDef) -> String {
             // For data modeling, we'll use a marker type
             "()".to_string()
         }
-        TypeDef::Generic { base, args } => {
+        TypeDef::Generic { base, args, .. } => {
             let args_str: Vec<String> = args.iter().map(typedef_to_rust_type).collect();
             format!("{}<{}>", base, args_str.join(", "))
         }
@@ -432,6 +433,12 @@ fn typedef_to_rust_type(def: &TypeDef) -> String {
         TypeDef::TemplateLiteral { .. } => "String".to_string(),
         TypeDef::GenericDef { name, .. } => name.clone(),
         TypeDef::TypeParamRef(name) => name.clone(),
+        TypeDef::Enum { .. } => {
+            // Anonymous enums have no Rust type to name
+            "serde_json::Value".to_string()
+        }
+        TypeDef::LargeInt { width: IntWidth::Bits64, .. } => "i64".to_string(),
+        TypeDef::LargeInt { width: IntWidth::Bits128, .. } => "i128".to_string(),
     }
 }
 
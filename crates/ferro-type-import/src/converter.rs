@@ -55,6 +55,9 @@ fn convert_interface(iface: &TsInterfaceDecl) -> Option<TsTypeInfo> {
             name: name.clone(),
             type_params: params,
             def: Box::new(TypeDef::Object(fields)),
+            module: None,
+            docs: None,
+            declaration_style: None,
         }
     } else {
         TypeDef::Named {
@@ -63,6 +66,8 @@ fn convert_interface(iface: &TsInterfaceDecl) -> Option<TsTypeInfo> {
             def: Box::new(TypeDef::Object(fields)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     };
 
@@ -90,6 +95,9 @@ fn convert_type_alias(alias: &TsTypeAliasDecl) -> Option<TsTypeInfo> {
             name: name.clone(),
             type_params: params,
             def: Box::new(inner_type),
+            module: None,
+            docs: None,
+            declaration_style: None,
         }
     } else {
         TypeDef::Named {
@@ -98,6 +106,8 @@ fn convert_type_alias(alias: &TsTypeAliasDecl) -> Option<TsTypeInfo> {
             def: Box::new(inner_type),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     };
 
@@ -119,7 +129,7 @@ fn convert_ts_enum(ts_enum: &TsEnumDecl) -> Option<TsTypeInfo> {
     let variants: Vec<TypeDef> = ts_enum
         .members
         .iter()
-        .filter_map(|member| {
+        .map(|member| {
             // Get the member name
             let member_name = match &member.id {
                 TsEnumMemberId::Ident(ident) => ident.sym.to_string(),
@@ -130,17 +140,17 @@ fn convert_ts_enum(ts_enum: &TsEnumDecl) -> Option<TsTypeInfo> {
             if let Some(init) = &member.init {
                 match init.as_ref() {
                     Expr::Lit(Lit::Str(s)) => {
-                        Some(TypeDef::Literal(Literal::String(s.value.as_str().unwrap_or("").to_string())))
+                        TypeDef::Literal(Literal::String(s.value.as_str().unwrap_or("").to_string()))
                     }
-                    Expr::Lit(Lit::Num(n)) => Some(TypeDef::Literal(Literal::Number(n.value))),
+                    Expr::Lit(Lit::Num(n)) => TypeDef::Literal(Literal::Number(n.value)),
                     _ => {
                         // Use member name as the literal value for computed initializers
-                        Some(TypeDef::Literal(Literal::String(member_name)))
+                        TypeDef::Literal(Literal::String(member_name))
                     }
                 }
             } else {
                 // No initializer - use member name as string literal
-                Some(TypeDef::Literal(Literal::String(member_name)))
+                TypeDef::Literal(Literal::String(member_name))
             }
         })
         .collect();
@@ -151,6 +161,8 @@ fn convert_ts_enum(ts_enum: &TsEnumDecl) -> Option<TsTypeInfo> {
         def: Box::new(TypeDef::Union(variants)),
         module: None,
         wrapper: None,
+        docs: None,
+        declaration_style: None,
     };
 
     Some(TsTypeInfo {
@@ -332,11 +344,12 @@ fn convert_type_ref(type_ref: &TsTypeRef) -> TypeDef {
                     .iter()
                     .map(|t| convert_ts_type(t))
                     .collect();
-                TypeDef::Generic { base: name, args }
+                TypeDef::Generic { base: name, args, def: None }
             } else {
                 TypeDef::Generic {
                     base: name,
                     args: vec![],
+                    def: None,
                 }
             }
         }
@@ -351,7 +364,7 @@ fn convert_type_ref(type_ref: &TsTypeRef) -> TypeDef {
                 if args.is_empty() {
                     TypeDef::Ref(name)
                 } else {
-                    TypeDef::Generic { base: name, args }
+                    TypeDef::Generic { base: name, args, def: None }
                 }
             } else {
                 TypeDef::Ref(name)
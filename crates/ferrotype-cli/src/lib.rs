@@ -0,0 +1,552 @@
+//! ferro-type-cli: command-line interface for ferro-type
+//!
+//! Drives [`Generator`] from a `ferrotype.toml` and wraps ferro-type-import.
+//!
+//! ```text
+//! ferrotype generate            write TypeScript for the registered types
+//! ferrotype check               fail if the TypeScript on disk is out of date
+//! ferrotype import api.ts       convert TypeScript declarations to Rust
+//! ```
+//!
+//! # Registering Types
+//!
+//! Rust types are only known to the binary they are compiled into, so
+//! `generate` and `check` see the types that derive `TS` in crates linked
+//! into the running binary. Add a small binary to the crate that owns them:
+//!
+//! ```ignore
+//! // src/bin/ferrotype.rs
+//! use my_api as _;
+//!
+//! fn main() -> std::process::ExitCode {
+//!     ferro_type_cli::main()
+//! }
+//! ```
+//!
+//! and run it with `cargo run --bin ferrotype -- generate`. Use [`run`] to
+//! register types explicitly instead.
+//!
+//! # Configuration
+//!
+//! The config is read from `--config`, or else from `ferrotype.toml` or the
+//! `[package.metadata.ferrotype]` section of `Cargo.toml` in the current
+//! directory (see [`Config::from_toml`] for every key):
+//!
+//! ```toml
+//! # ferrotype.toml
+//! output = "frontend/src/types.ts"   # or `output_dir` for one file per module
+//! export_style = "named"
+//! include_utilities = true
+//! ```
+//!
+//! Relative paths are resolved against the directory of the config file.
+//!
+//! # Exit Codes
+//!
+//! See [`exit_code`]. The codes are stable, so CI can tell an out-of-date
+//! check apart from a broken setup.
+
+use clap::{Parser, Subcommand};
+use ferro_type_gen::{Config, Generator};
+use std::ffi::OsString;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Exit codes returned by [`run`]
+pub mod exit_code {
+    /// The command succeeded, and `check` found everything up to date
+    pub const SUCCESS: u8 = 0;
+    /// `check` found generated files that differ, are missing or are stale
+    pub const OUT_OF_DATE: u8 = 1;
+    /// Invalid arguments or configuration
+    pub const USAGE: u8 = 2;
+    /// Generation failed, or files could not be read or written
+    pub const FAILURE: u8 = 3;
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "ferrotype",
+    version,
+    about = "Rust-to-TypeScript type generation"
+)]
+struct Cli {
+    /// Path to `ferrotype.toml` or a `Cargo.toml` with
+    /// `[package.metadata.ferrotype]` [default: found in the current directory]
+    #[arg(short, long, global = true)]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Write TypeScript for the registered types
+    Generate,
+    /// Check that the TypeScript on disk is up to date, without writing
+    Check,
+    /// Convert TypeScript declarations to Rust
+    Import {
+        /// TypeScript file to read, or `-` for stdin
+        input: PathBuf,
+        /// Rust file to write instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Errors that end a command, each with its exit code
+#[derive(Debug)]
+enum Error {
+    Config(String),
+    Io(std::io::Error),
+    Import(String),
+}
+
+impl Error {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Error::Config(_) => exit_code::USAGE,
+            Error::Io(_) | Error::Import(_) => exit_code::FAILURE,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Config(message) | Error::Import(message) => f.write_str(message),
+            Error::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Runs the CLI with the process arguments over every type that derives `TS`
+/// in the running binary
+pub fn main() -> ExitCode {
+    let code = run(std::env::args_os(), |generator| {
+        generator.registry_mut().collect_all();
+    });
+    ExitCode::from(code)
+}
+
+/// Runs the CLI with the given arguments, returning its exit code
+///
+/// `register` adds the types to generate once the config has been loaded.
+/// The first argument is the program name, as with [`std::env::args_os`].
+pub fn run<I, T>(args: I, register: impl FnOnce(&mut Generator)) -> u8
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(err) => {
+            // Help and version requests are not errors
+            let _ = err.print();
+            return if err.use_stderr() {
+                exit_code::USAGE
+            } else {
+                exit_code::SUCCESS
+            };
+        }
+    };
+
+    match execute(cli, register) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            err.exit_code()
+        }
+    }
+}
+
+fn execute(cli: Cli, register: impl FnOnce(&mut Generator)) -> Result<u8, Error> {
+    match cli.command {
+        Command::Generate => {
+            let (generator, layout) = load(cli.config.as_deref(), register)?;
+            generate(&generator, &layout)?;
+            Ok(exit_code::SUCCESS)
+        }
+        Command::Check => {
+            let (generator, layout) = load(cli.config.as_deref(), register)?;
+            check(&generator, &layout)
+        }
+        Command::Import { input, output } => {
+            import(&input, output.as_deref())?;
+            Ok(exit_code::SUCCESS)
+        }
+    }
+}
+
+/// Where generated TypeScript goes
+enum Layout {
+    /// Every type in one file
+    SingleFile(PathBuf),
+    /// One file per Rust module below a directory
+    MultiFile(PathBuf),
+}
+
+/// Loads the config and registers the types to generate
+fn load(
+    path: Option<&Path>,
+    register: impl FnOnce(&mut Generator),
+) -> Result<(Generator, Layout), Error> {
+    let config = match path {
+        Some(path) if path.file_name().is_some_and(|name| name == "Cargo.toml") => {
+            Config::from_cargo_manifest(path)
+                .map_err(|e| Error::Config(e.to_string()))?
+                .ok_or_else(|| {
+                    Error::Config(format!(
+                        "{}: no [package.metadata.ferrotype] section",
+                        path.display()
+                    ))
+                })?
+        }
+        Some(path) => Config::from_file(path).map_err(|e| Error::Config(e.to_string()))?,
+        None => Config::discover(".").map_err(|e| Error::Config(e.to_string()))?,
+    };
+
+    let layout = match (&config.output, &config.output_dir) {
+        (_, Some(dir)) => Layout::MultiFile(dir.clone()),
+        (Some(output), None) => Layout::SingleFile(output.clone()),
+        (None, None) => {
+            return Err(Error::Config(
+                "the config must set `output` or `output_dir`".to_string(),
+            ))
+        }
+    };
+
+    let mut generator = Generator::new(config);
+    register(&mut generator);
+    for location in generator.lossy_integers() {
+        eprintln!(
+            "warning: `{}` is a 64-bit integer generated as `number`, which loses precision \
+             above Number.MAX_SAFE_INTEGER; set `int64` in the config or \
+             `#[ts(int64 = \"...\")]` on the field",
+            location
+        );
+    }
+    for location in generator.invalid_map_keys() {
+        eprintln!(
+            "warning: `{}` is a map whose keys serde_json can't write as JSON object keys, \
+             so serializing it will fail",
+            location
+        );
+    }
+    Ok((generator, layout))
+}
+
+fn generate(generator: &Generator, layout: &Layout) -> Result<(), Error> {
+    match layout {
+        Layout::SingleFile(output) => {
+            if generator.write_if_changed()? {
+                println!("wrote {}", output.display());
+            } else {
+                println!("{} is up to date", output.display());
+            }
+        }
+        Layout::MultiFile(dir) => {
+            let summary = generator.write_multi_file_if_changed(dir)?;
+            for (action, paths) in [
+                ("created", &summary.created),
+                ("updated", &summary.updated),
+                ("removed", &summary.removed),
+            ] {
+                for path in paths {
+                    println!("{} {}", action, dir.join(path).display());
+                }
+            }
+            println!(
+                "{} written, {} removed, {} unchanged",
+                summary.written(),
+                summary.removed.len(),
+                summary.unchanged.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check(generator: &Generator, layout: &Layout) -> Result<u8, Error> {
+    let report = match layout {
+        Layout::SingleFile(_) => generator.check()?,
+        Layout::MultiFile(dir) => generator.check_multi_file(dir)?,
+    };
+    if report.is_ok() {
+        println!("TypeScript is up to date");
+        return Ok(exit_code::SUCCESS);
+    }
+
+    print!("{}", report);
+    let count = report.differ.len() + report.missing.len() + report.extra.len();
+    eprintln!(
+        "{} file{} out of date; run `ferrotype generate`",
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    Ok(exit_code::OUT_OF_DATE)
+}
+
+fn import(input: &Path, output: Option<&Path>) -> Result<(), Error> {
+    let source = if input.as_os_str() == "-" {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source)?;
+        source
+    } else {
+        std::fs::read_to_string(input)
+            .map_err(|e| Error::Import(format!("cannot read {}: {}", input.display(), e)))?
+    };
+
+    let rust = ferro_type_import::generate_rust(&source)
+        .map_err(|e| Error::Import(format!("{}: {}", input.display(), e)))?;
+
+    match output {
+        Some(path) => std::fs::write(path, rust)?,
+        None => std::io::stdout().write_all(rust.as_bytes())?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferro_type::{Primitive, TypeDef};
+
+    fn named(module: &str, name: &str) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: Some(module.to_string()),
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    /// Runs the CLI with a config file containing `config` in `dir`
+    fn run_in(dir: &Path, config: &str, args: &[&str], types: &[TypeDef]) -> u8 {
+        let config_path = dir.join("ferrotype.toml");
+        std::fs::write(&config_path, config).unwrap();
+
+        let mut argv = vec![
+            "ferrotype".into(),
+            "--config".into(),
+            config_path.into_os_string(),
+        ];
+        argv.extend(args.iter().map(OsString::from));
+        run(argv, |generator| {
+            for typedef in types {
+                generator.add(typedef.clone());
+            }
+        })
+    }
+
+    #[test]
+    fn test_generate_and_check_single_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = "output = \"types.ts\"";
+        let user = [named("my_crate", "User")];
+
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["check"], &user),
+            exit_code::OUT_OF_DATE
+        );
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["generate"], &user),
+            exit_code::SUCCESS
+        );
+        let content = std::fs::read_to_string(temp_dir.path().join("types.ts")).unwrap();
+        assert!(content.contains("export type User = string;"));
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["check"], &user),
+            exit_code::SUCCESS
+        );
+
+        let changed = [named("my_crate", "User"), named("my_crate", "Post")];
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["check"], &changed),
+            exit_code::OUT_OF_DATE
+        );
+    }
+
+    #[test]
+    fn test_generate_and_check_multi_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = "output_dir = \"types\"\nbarrel = \"error\"";
+        let types = [
+            named("my_crate::models", "User"),
+            named("my_crate::api", "Request"),
+        ];
+
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["generate"], &types),
+            exit_code::SUCCESS
+        );
+        assert!(temp_dir.path().join("types/models.ts").exists());
+        assert!(temp_dir.path().join("types/index.ts").exists());
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["check"], &types),
+            exit_code::SUCCESS
+        );
+
+        // A removed module leaves a stale file until the next generate
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["check"], &types[..1]),
+            exit_code::OUT_OF_DATE
+        );
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["generate"], &types[..1]),
+            exit_code::SUCCESS
+        );
+        assert!(!temp_dir.path().join("types/api.ts").exists());
+    }
+
+    #[test]
+    fn test_exit_codes_for_errors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let types = [named("my_crate", "User")];
+
+        // Invalid config and arguments
+        assert_eq!(
+            run_in(
+                temp_dir.path(),
+                "export_style = \"named\"",
+                &["generate"],
+                &types
+            ),
+            exit_code::USAGE
+        );
+        assert_eq!(
+            run_in(temp_dir.path(), "output = 1", &["generate"], &types),
+            exit_code::USAGE
+        );
+        assert_eq!(
+            run_in(
+                temp_dir.path(),
+                "output = \"a.ts\"",
+                &["frobnicate"],
+                &types
+            ),
+            exit_code::USAGE
+        );
+        let missing = temp_dir.path().join("missing.toml");
+        assert_eq!(
+            run(
+                [
+                    "ferrotype".as_ref(),
+                    "check".as_ref(),
+                    "-c".as_ref(),
+                    missing.as_os_str()
+                ],
+                |_| {}
+            ),
+            exit_code::USAGE
+        );
+
+        // Generation failures
+        let cyclic = [
+            TypeDef::Named {
+                namespace: vec![],
+                name: "A".into(),
+                def: Box::new(TypeDef::Ref("B".into())),
+                module: Some("my_crate::a".into()),
+                wrapper: None,
+                docs: None,
+                declaration_style: None,
+            },
+            TypeDef::Named {
+                namespace: vec![],
+                name: "B".into(),
+                def: Box::new(TypeDef::Ref("A".into())),
+                module: Some("my_crate::b".into()),
+                wrapper: None,
+                docs: None,
+                declaration_style: None,
+            },
+        ];
+        let config = "output_dir = \"types\"\ndeny_import_cycles = true";
+        assert_eq!(
+            run_in(temp_dir.path(), config, &["generate"], &cyclic),
+            exit_code::FAILURE
+        );
+
+        assert_eq!(run(["ferrotype", "--help"], |_| {}), exit_code::SUCCESS);
+    }
+
+    #[test]
+    fn test_import() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let input = temp_dir.path().join("api.ts");
+        let output = temp_dir.path().join("api.rs");
+        std::fs::write(&input, "interface User { id: string; age?: number }").unwrap();
+
+        let args = [
+            OsString::from("ferrotype"),
+            "import".into(),
+            input.clone().into(),
+            "-o".into(),
+            output.clone().into(),
+        ];
+        assert_eq!(run(args, |_| {}), exit_code::SUCCESS);
+        let rust = std::fs::read_to_string(&output).unwrap();
+        assert!(rust.contains("pub struct User"));
+        assert!(rust.contains("pub id: String"));
+
+        // Unreadable or invalid input
+        std::fs::write(&input, "interface {").unwrap();
+        assert_eq!(
+            run(
+                ["ferrotype".as_ref(), "import".as_ref(), input.as_os_str()],
+                |_| {}
+            ),
+            exit_code::FAILURE
+        );
+        let missing = temp_dir.path().join("missing.ts");
+        assert_eq!(
+            run(
+                ["ferrotype".as_ref(), "import".as_ref(), missing.as_os_str()],
+                |_| {}
+            ),
+            exit_code::FAILURE
+        );
+    }
+
+    #[test]
+    fn test_cargo_manifest_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest = temp_dir.path().join("Cargo.toml");
+        let args = |command: &str| {
+            [
+                OsString::from("ferrotype"),
+                command.into(),
+                "--config".into(),
+                manifest.clone().into(),
+            ]
+        };
+
+        std::fs::write(&manifest, "[package]\nname = \"api\"\n").unwrap();
+        assert_eq!(run(args("generate"), |_| {}), exit_code::USAGE);
+
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"api\"\n\n[package.metadata.ferrotype]\noutput = \"types.ts\"\n",
+        )
+        .unwrap();
+        let register = |generator: &mut Generator| {
+            generator.add(named("api", "User"));
+        };
+        assert_eq!(run(args("generate"), register), exit_code::SUCCESS);
+        assert!(temp_dir.path().join("types.ts").exists());
+        assert_eq!(run(args("check"), register), exit_code::SUCCESS);
+    }
+}
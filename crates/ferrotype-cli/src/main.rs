@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    ferro_type_cli::main()
+}
@@ -6,9 +6,10 @@
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Attribute, Data, DeriveInput, Fields, GenericParam, Generics, Ident, Type,
+    ext::IdentExt, meta::ParseNestedMeta, parse_macro_input, visit::{self, Visit}, visit_mut::{self, VisitMut}, Attribute, Data,
+    DeriveInput, Fields, GenericParam, Generics, Ident, Type,
 };
 
 // ============================================================================
@@ -16,6 +17,7 @@ use syn::{
 // ============================================================================
 
 /// Case conversion strategies for rename_all
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum RenameAll {
     /// camelCase
@@ -30,6 +32,10 @@ enum RenameAll {
     KebabCase,
     /// SCREAMING-KEBAB-CASE
     ScreamingKebabCase,
+    /// lowercase
+    LowerCase,
+    /// UPPERCASE
+    UpperCase,
 }
 
 impl RenameAll {
@@ -41,6 +47,8 @@ impl RenameAll {
             "SCREAMING_SNAKE_CASE" => Some(RenameAll::ScreamingSnakeCase),
             "kebab-case" => Some(RenameAll::KebabCase),
             "SCREAMING-KEBAB-CASE" => Some(RenameAll::ScreamingKebabCase),
+            "lowercase" => Some(RenameAll::LowerCase),
+            "UPPERCASE" => Some(RenameAll::UpperCase),
             _ => None,
         }
     }
@@ -53,6 +61,8 @@ impl RenameAll {
             RenameAll::ScreamingSnakeCase => to_snake_case(name).to_uppercase(),
             RenameAll::KebabCase => to_snake_case(name).replace('_', "-"),
             RenameAll::ScreamingKebabCase => to_snake_case(name).replace('_', "-").to_uppercase(),
+            RenameAll::LowerCase => name.to_lowercase(),
+            RenameAll::UpperCase => name.to_uppercase(),
         }
     }
 }
@@ -118,14 +128,19 @@ struct ContainerAttrs {
     rename: Option<String>,
     /// Rename all fields/variants
     rename_all: Option<RenameAll>,
+    /// Rename all fields of every struct variant in an enum
+    rename_all_fields: Option<RenameAll>,
     /// Make newtype structs transparent (use inner type directly)
     transparent: bool,
-    /// Custom tag field name for enums (default: "type")
+    /// Tag field name for internally or adjacently tagged enums
     tag: Option<String>,
     /// Content field name for adjacently tagged enums
     content: Option<String>,
     /// Generate untagged union (no discriminant)
     untagged: bool,
+    /// Fill in every missing field from `Default` when deserializing
+    /// (`#[serde(default)]` on a struct)
+    default: bool,
     /// Template literal pattern for branded ID types (e.g., "vm-${string}")
     pattern: Option<String>,
     /// Namespace path for the type (e.g., "VM::Git" or "VM.Git")
@@ -134,11 +149,41 @@ struct ContainerAttrs {
     extends: Option<String>,
     /// Utility type wrapper (e.g., "Prettify" or "Prettify<Required<")
     wrapper: Option<String>,
+    /// Declare object types as interfaces (`interface X { ... }`)
+    interface: bool,
+    /// TypeScript constraints for Rust trait bounds on type parameters
+    /// (e.g., `bounds(Identified = "{ id: string }")`)
+    bounds: Vec<(String, String)>,
+    /// Represent unit variants by their discriminants (`repr = "number"`)
+    numeric: bool,
+    /// Declare a unit-only enum as a TypeScript enum instead of a union
+    enum_style: Option<EnumStyleAttr>,
+    /// Doc comments and `#[deprecated]`, emitted as JSDoc
+    docs: DocAttrs,
+}
+
+/// How a unit-only enum is declared, from `#[ts(enum_style = "...")]`
+#[derive(Clone, Copy)]
+enum EnumStyleAttr {
+    /// `enum Name { ... }`
+    Enum,
+    /// `const enum Name { ... }`
+    ConstEnum,
+    /// `const Name = { ... } as const;` plus a union type
+    AsConst,
 }
 
 impl ContainerAttrs {
     fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
-        let mut result = ContainerAttrs::default();
+        let mut result = ContainerAttrs {
+            docs: DocAttrs::from_attrs(attrs)?,
+            ..ContainerAttrs::default()
+        };
+
+        // Parse #[serde(...)] first so that #[ts(...)] takes precedence
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| result.parse_serde_meta(&meta))?;
+        }
 
         for attr in attrs {
             if !attr.path().is_ident("ts") {
@@ -151,19 +196,10 @@ impl ContainerAttrs {
                     result.rename = Some(value.value());
                 } else if meta.path.is_ident("rename_all") {
                     let value: syn::LitStr = meta.value()?.parse()?;
-                    let s = value.value();
-                    result.rename_all = RenameAll::from_str(&s);
-                    if result.rename_all.is_none() {
-                        return Err(syn::Error::new_spanned(
-                            value,
-                            format!(
-                                "unknown rename_all value: '{}'. Expected one of: \
-                                camelCase, PascalCase, snake_case, \
-                                SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE",
-                                s
-                            ),
-                        ));
-                    }
+                    result.rename_all = Some(parse_rename_all(&value)?);
+                } else if meta.path.is_ident("rename_all_fields") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.rename_all_fields = Some(parse_rename_all(&value)?);
                 } else if meta.path.is_ident("transparent") {
                     result.transparent = true;
                 } else if meta.path.is_ident("tag") {
@@ -182,7 +218,7 @@ impl ContainerAttrs {
                     // Parse namespace path - supports both "::" and "." as separators
                     let ns_str = value.value();
                     result.namespace = ns_str
-                        .split(|c| c == ':' || c == '.')
+                        .split([':', '.'])
                         .filter(|s| !s.is_empty())
                         .map(|s| s.to_string())
                         .collect();
@@ -192,6 +228,44 @@ impl ContainerAttrs {
                 } else if meta.path.is_ident("wrapper") {
                     let value: syn::LitStr = meta.value()?.parse()?;
                     result.wrapper = Some(value.value());
+                } else if meta.path.is_ident("interface") {
+                    result.interface = true;
+                } else if meta.path.is_ident("repr") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.numeric = match value.value().as_str() {
+                        "string" => false,
+                        "number" => true,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                format!("unknown repr value: '{}'. Expected one of: string, number", other),
+                            ))
+                        }
+                    };
+                } else if meta.path.is_ident("enum_style") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.enum_style = match value.value().as_str() {
+                        "union" => None,
+                        "enum" => Some(EnumStyleAttr::Enum),
+                        "const_enum" => Some(EnumStyleAttr::ConstEnum),
+                        "as_const" => Some(EnumStyleAttr::AsConst),
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                format!(
+                                    "unknown enum_style value: '{}'. Expected one of: union, enum, const_enum, as_const",
+                                    other
+                                ),
+                            ))
+                        }
+                    };
+                } else if meta.path.is_ident("bounds") {
+                    meta.parse_nested_meta(|bound| {
+                        let trait_name = bound.path.require_ident()?.to_string();
+                        let value: syn::LitStr = bound.value()?.parse()?;
+                        result.bounds.push((trait_name, value.value()));
+                        Ok(())
+                    })?;
                 }
                 Ok(())
             })?;
@@ -199,6 +273,122 @@ impl ContainerAttrs {
 
         Ok(result)
     }
+
+    /// The `declaration_style` of the generated declaration.
+    fn declaration_style_expr(&self) -> TokenStream2 {
+        if self.interface {
+            quote! { Some(ferro_type::DeclarationStyle::Interface) }
+        } else {
+            quote! { None }
+        }
+    }
+
+    /// Parses a single item of a container-level `#[serde(...)]` attribute.
+    ///
+    /// Only options that affect the serialized shape are recorded; everything
+    /// else serde accepts is skipped.
+    fn parse_serde_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("rename") {
+            if let Some(value) = parse_serde_serialize_value(meta)? {
+                self.rename = Some(value.value());
+            }
+        } else if meta.path.is_ident("rename_all") {
+            if let Some(value) = parse_serde_serialize_value(meta)? {
+                self.rename_all = Some(parse_rename_all(&value)?);
+            }
+        } else if meta.path.is_ident("rename_all_fields") {
+            if let Some(value) = parse_serde_serialize_value(meta)? {
+                self.rename_all_fields = Some(parse_rename_all(&value)?);
+            }
+        } else if meta.path.is_ident("transparent") {
+            self.transparent = true;
+        } else if meta.path.is_ident("tag") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            self.tag = Some(value.value());
+        } else if meta.path.is_ident("content") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            self.content = Some(value.value());
+        } else if meta.path.is_ident("untagged") {
+            self.untagged = true;
+        } else if meta.path.is_ident("default") {
+            // Both `default` and `default = "path"` make every field optional
+            skip_meta(meta)?;
+            self.default = true;
+        } else {
+            skip_meta(meta)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a `rename_all` value, rejecting unknown case conventions.
+fn parse_rename_all(value: &syn::LitStr) -> syn::Result<RenameAll> {
+    let s = value.value();
+    RenameAll::from_str(&s).ok_or_else(|| {
+        syn::Error::new_spanned(
+            value,
+            format!(
+                "unknown rename_all value: '{}'. Expected one of: \
+                camelCase, PascalCase, snake_case, \
+                SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE, \
+                lowercase, UPPERCASE",
+                s
+            ),
+        )
+    })
+}
+
+/// Parses the value of `#[ts(int64 = "...")]` or `#[ts(int128 = "...")]`
+/// into an `IntPolicy` path.
+fn parse_int_policy(value: &syn::LitStr) -> syn::Result<TokenStream2> {
+    match value.value().as_str() {
+        "number" => Ok(quote! { ferro_type::IntPolicy::Number }),
+        "bigint" => Ok(quote! { ferro_type::IntPolicy::BigInt }),
+        "string" => Ok(quote! { ferro_type::IntPolicy::String }),
+        "number_or_string" => Ok(quote! { ferro_type::IntPolicy::NumberOrString }),
+        other => Err(syn::Error::new_spanned(
+            value,
+            format!(
+                "unknown int policy: '{}'. Expected one of: number, bigint, string, number_or_string",
+                other
+            ),
+        )),
+    }
+}
+
+/// Parses a serde option that can be split by direction and returns the
+/// serialize side.
+///
+/// Accepts both `rename = "name"` and
+/// `rename(serialize = "ser_name", deserialize = "de_name")`. Returns `None`
+/// when only a deserialize value is given, since it doesn't affect the
+/// serialized shape.
+fn parse_serde_serialize_value(meta: &ParseNestedMeta) -> syn::Result<Option<syn::LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut serialize = None;
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("serialize") {
+            serialize = Some(nested.value()?.parse()?);
+        } else {
+            skip_meta(&nested)?;
+        }
+        Ok(())
+    })?;
+    Ok(serialize)
+}
+
+/// Consumes the value of an attribute option we don't interpret, such as
+/// `bound = "..."`, `with = "module"` or `deny_unknown_fields`.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta(&nested))?;
+    }
+    Ok(())
 }
 
 /// Indexed access base type specification
@@ -234,6 +424,8 @@ struct IndexedAccessValidation {
 struct FieldAttrs {
     /// Rename this specific field
     rename: Option<String>,
+    /// Rename all fields of a struct variant (variant-level only)
+    rename_all: Option<RenameAll>,
     /// Skip this field in the generated TypeScript
     skip: bool,
     /// Flatten this field's type into the parent object
@@ -245,6 +437,8 @@ struct FieldAttrs {
     /// Mark this field as optional (with ?), unwrapping Option<T> to T
     /// Unlike `default`, this extracts the inner type from Option<T>
     optional: bool,
+    /// serde's `skip_serializing_if` predicate, which makes the field optional
+    skip_serializing_if: Option<String>,
     /// Inline the type definition instead of using a reference
     inline: bool,
     /// Base type for indexed access (e.g., Profile in Profile["login"])
@@ -253,11 +447,25 @@ struct FieldAttrs {
     key: Option<KeySpec>,
     /// Template literal pattern for this field (e.g., "${TOPIC}::${ULID}")
     pattern: Option<String>,
+    /// Policy for the 64-bit integers in this field's type
+    int64: Option<TokenStream2>,
+    /// Policy for the 128-bit integers in this field's type
+    int128: Option<TokenStream2>,
+    /// Doc comments and `#[deprecated]`, emitted as JSDoc
+    docs: DocAttrs,
 }
 
 impl FieldAttrs {
     fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
-        let mut result = FieldAttrs::default();
+        let mut result = FieldAttrs {
+            docs: DocAttrs::from_attrs(attrs)?,
+            ..FieldAttrs::default()
+        };
+
+        // Parse #[serde(...)] first so that #[ts(...)] takes precedence
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| result.parse_serde_meta(&meta))?;
+        }
 
         for attr in attrs {
             if !attr.path().is_ident("ts") {
@@ -268,6 +476,9 @@ impl FieldAttrs {
                 if meta.path.is_ident("rename") {
                     let value: syn::LitStr = meta.value()?.parse()?;
                     result.rename = Some(value.value());
+                } else if meta.path.is_ident("rename_all") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.rename_all = Some(parse_rename_all(&value)?);
                 } else if meta.path.is_ident("skip") {
                     result.skip = true;
                 } else if meta.path.is_ident("flatten") {
@@ -304,6 +515,12 @@ impl FieldAttrs {
                 } else if meta.path.is_ident("pattern") {
                     let value: syn::LitStr = meta.value()?.parse()?;
                     result.pattern = Some(value.value());
+                } else if meta.path.is_ident("int64") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.int64 = Some(parse_int_policy(&value)?);
+                } else if meta.path.is_ident("int128") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.int128 = Some(parse_int_policy(&value)?);
                 }
                 Ok(())
             })?;
@@ -312,6 +529,37 @@ impl FieldAttrs {
         Ok(result)
     }
 
+    /// Parses a single item of a field- or variant-level `#[serde(...)]` attribute.
+    ///
+    /// `skip_serializing` omits the field since it never appears in the output.
+    /// `skip_deserializing` and `alias` only affect deserialization, so they
+    /// are accepted without changing the generated type.
+    fn parse_serde_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("rename") {
+            if let Some(value) = parse_serde_serialize_value(meta)? {
+                self.rename = Some(value.value());
+            }
+        } else if meta.path.is_ident("rename_all") {
+            if let Some(value) = parse_serde_serialize_value(meta)? {
+                self.rename_all = Some(parse_rename_all(&value)?);
+            }
+        } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+            self.skip = true;
+        } else if meta.path.is_ident("flatten") {
+            self.flatten = true;
+        } else if meta.path.is_ident("default") {
+            // Both `default` and `default = "path"` make the field optional
+            skip_meta(meta)?;
+            self.default = true;
+        } else if meta.path.is_ident("skip_serializing_if") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            self.skip_serializing_if = Some(value.value());
+        } else {
+            skip_meta(meta)?;
+        }
+        Ok(())
+    }
+
     /// Returns true if this field uses indexed access type
     fn has_indexed_access(&self) -> bool {
         self.index.is_some() && self.key.is_some()
@@ -321,21 +569,155 @@ impl FieldAttrs {
     fn has_pattern(&self) -> bool {
         self.pattern.is_some()
     }
+
+    /// Returns true if this field is left out of the output, rather than
+    /// written as `null`, whenever it's `None`
+    fn skips_none(&self, ty: &Type) -> bool {
+        let Some(predicate) = &self.skip_serializing_if else {
+            return false;
+        };
+        let predicate: String = predicate.split_whitespace().collect();
+        is_option_type(ty)
+            && ["Option::is_none", "std::option::Option::is_none", "core::option::Option::is_none"]
+                .contains(&predicate.as_str())
+    }
+
+    /// Returns true if this field may be left out of either the output or
+    /// the input, so renders as `field?: T`
+    fn is_optional(&self) -> bool {
+        self.optional || self.default || self.skip_serializing_if.is_some()
+    }
+
+    /// Generate the `.with_shape(...)` suffix for how this field differs
+    /// between serialized output and deserialized input
+    ///
+    /// `#[ts(optional)]` asks for `field?: T` in both, so has no shape.
+    fn shape_suffix(&self, ty: &Type) -> TokenStream2 {
+        let shape = match (self.skip_serializing_if.is_some(), self.default) {
+            _ if self.optional => return quote! {},
+            (true, _) if self.skips_none(ty) => quote! { SkippedNone },
+            (true, false) => quote! { Skipped },
+            (false, true) => quote! { Defaulted },
+            _ => return quote! {},
+        };
+        quote! { .with_shape(ferro_type::FieldShape::#shape) }
+    }
+
+    /// `.with_int_policy(...)` calls to chain onto the field's `TypeDef`, if any
+    fn int_policy_suffix(&self) -> TokenStream2 {
+        let widths = [("Bits64", &self.int64), ("Bits128", &self.int128)];
+        let calls = widths.into_iter().filter_map(|(width, policy)| {
+            let width = syn::Ident::new(width, proc_macro2::Span::call_site());
+            policy.as_ref().map(|policy| quote! { .with_int_policy(ferro_type::IntWidth::#width, #policy) })
+        });
+        quote! { #(#calls)* }
+    }
+}
+
+/// Documentation gathered from `///` comments and `#[deprecated]`
+#[derive(Default)]
+struct DocAttrs {
+    /// Doc comment lines joined with newlines
+    description: Option<String>,
+    /// Deprecation note; empty for a bare `#[deprecated]`
+    deprecated: Option<String>,
+}
+
+impl DocAttrs {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut lines: Vec<String> = Vec::new();
+        let mut deprecated = None;
+
+        for attr in attrs {
+            if attr.path().is_ident("doc") {
+                // `/// text` arrives as `#[doc = " text"]`; drop the leading space
+                if let syn::Meta::NameValue(nv) = &attr.meta {
+                    if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) = &nv.value {
+                        let value = s.value();
+                        for line in value.split('\n') {
+                            lines.push(line.strip_prefix(' ').unwrap_or(line).trim_end().to_string());
+                        }
+                    }
+                }
+            } else if attr.path().is_ident("deprecated") {
+                deprecated = Some(match &attr.meta {
+                    syn::Meta::Path(_) => String::new(),
+                    syn::Meta::NameValue(nv) => match &nv.value {
+                        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => s.value(),
+                        _ => String::new(),
+                    },
+                    syn::Meta::List(_) => {
+                        let mut note = String::new();
+                        attr.parse_nested_meta(|meta| {
+                            if meta.path.is_ident("note") {
+                                let value: syn::LitStr = meta.value()?.parse()?;
+                                note = value.value();
+                            } else {
+                                skip_meta(&meta)?;
+                            }
+                            Ok(())
+                        })?;
+                        note
+                    }
+                });
+            }
+        }
+
+        let description = lines.join("\n").trim().to_string();
+        Ok(DocAttrs {
+            description: (!description.is_empty()).then_some(description),
+            deprecated,
+        })
+    }
+
+    /// A `ferro_type::Docs` expression, or `None` if there is nothing to document
+    fn docs_expr(&self) -> Option<TokenStream2> {
+        if self.description.is_none() && self.deprecated.is_none() {
+            return None;
+        }
+        let description = match &self.description {
+            Some(d) => quote! { Some(#d.to_string()) },
+            None => quote! { None },
+        };
+        let deprecated = match &self.deprecated {
+            Some(d) => quote! { Some(#d.to_string()) },
+            None => quote! { None },
+        };
+        Some(quote! {
+            ferro_type::Docs { description: #description, deprecated: #deprecated }
+        })
+    }
+
+    /// `Some(Docs { .. })` or `None`, for `TypeDef::Named` and `GenericDef`
+    fn option_expr(&self) -> TokenStream2 {
+        match self.docs_expr() {
+            Some(docs) => quote! { Some(#docs) },
+            None => quote! { None },
+        }
+    }
+
+    /// A `.with_docs(...)` call to chain onto a `Field` constructor, if any
+    fn field_suffix(&self) -> TokenStream2 {
+        match self.docs_expr() {
+            Some(docs) => quote! { .with_docs(#docs) },
+            None => quote! {},
+        }
+    }
 }
 
 /// Get the effective name for a field, applying rename attributes
 fn get_field_name(
     original: &str,
     field_attrs: &FieldAttrs,
-    container_attrs: &ContainerAttrs,
+    rename_all: Option<RenameAll>,
 ) -> String {
     // Field-level rename takes precedence
     if let Some(ref renamed) = field_attrs.rename {
         return renamed.clone();
     }
 
-    // Then apply container-level rename_all
-    if let Some(rename_all) = container_attrs.rename_all {
+    // Then apply the enclosing rename_all
+    if let Some(rename_all) = rename_all {
         return rename_all.apply(original);
     }
 
@@ -366,7 +748,7 @@ fn parse_template_pattern(pattern: &str) -> syn::Result<(Vec<String>, Vec<String
 
             let mut type_name = String::new();
             let mut depth = 1;
-            while let Some(tc) = chars.next() {
+            for tc in chars.by_ref() {
                 if tc == '{' {
                     depth += 1;
                     type_name.push(tc);
@@ -481,7 +863,7 @@ fn generate_template_literal_expr(strings: &[String], types: &[String]) -> Token
 ///     D2(f64, f64),
 ///     D3(f64, f64, f64),
 /// }
-/// // Generates: { type: "D2"; value: [number, number] } | { type: "D3"; value: [number, number, number] }
+/// // Generates: { D2: [number, number] } | { D3: [number, number, number] }
 /// ```
 ///
 /// ## Struct variants
@@ -491,7 +873,18 @@ fn generate_template_literal_expr(strings: &[String], types: &[String]) -> Token
 ///     Circle { center: Point, radius: f64 },
 ///     Rectangle { x: f64, y: f64, width: f64, height: f64 },
 /// }
-/// // Generates: { type: "Circle"; center: Point; radius: number } | { type: "Rectangle"; x: number; y: number; width: number; height: number }
+/// // Generates: { Circle: { center: Point; radius: number } } | { Rectangle: { x: number; y: number; width: number; height: number } }
+/// ```
+///
+/// ## Internally tagged variants
+/// ```ignore
+/// #[derive(TS)]
+/// #[ts(tag = "type")]
+/// enum Shape {
+///     Circle { center: Point, radius: f64 },
+///     Custom(Polygon),
+/// }
+/// // Generates: { type: "Circle"; center: Point; radius: number } | { type: "Custom" } & Polygon
 /// ```
 ///
 /// ## Structs
@@ -541,23 +934,42 @@ fn expand_derive_typescript(input: &DeriveInput) -> syn::Result<TokenStream2> {
         .clone()
         .unwrap_or_else(|| name.to_string());
 
-    match &input.data {
-        Data::Enum(data) => {
-            let typedef = generate_enum_typedef(&data.variants, &container_attrs)?;
-            generate_impl(name, &type_name, &container_attrs.namespace, &container_attrs.wrapper, generics, typedef)
+    // The declaration of a generic type is built with its type parameters
+    // replaced by markers; see `generate_generic_typescript`. Where the
+    // markers can't stand in for them, the type is declared as a whole.
+    let generic_def = generics.type_params().next().is_some() && markers_fit(generics, &input.data);
+    let mut decl_data = input.data.clone();
+    if generic_def {
+        ParamSubstituter::new(generics.type_params()).visit_data_mut(&mut decl_data);
+    }
+
+    match (&input.data, &decl_data) {
+        (Data::Enum(_), Data::Enum(data)) => {
+            let (typedef, validations) = generate_enum_typedef(&data.variants, &container_attrs)?;
+            let impl_code = generate_impl(name, &type_name, &container_attrs.namespace, &container_attrs, generics, generic_def, typedef)?;
+            let validation_code = generate_indexed_access_validations(name, &validations);
+
+            Ok(quote! {
+                #impl_code
+                #validation_code
+            })
         }
-        Data::Struct(data) => {
-            // Handle transparent newtypes - they become the inner type directly
+        (Data::Struct(data), Data::Struct(decl_data)) => {
+            // Handle transparent wrappers - they become the inner type directly.
+            // Like serde, this allows exactly one field that isn't skipped.
             if container_attrs.transparent {
-                if let syn::Fields::Unnamed(fields) = &data.fields {
-                    if fields.unnamed.len() == 1 {
-                        let inner_type = &fields.unnamed.first().unwrap().ty;
-                        return generate_transparent_impl(name, inner_type, generics);
+                let mut included = Vec::new();
+                for field in data.fields.iter() {
+                    if !FieldAttrs::from_attrs(&field.attrs)?.skip {
+                        included.push(field);
                     }
                 }
+                if let [field] = included.as_slice() {
+                    return generate_transparent_impl(name, field, generics);
+                }
                 return Err(syn::Error::new_spanned(
                     input,
-                    "#[ts(transparent)] can only be used on newtype structs (single unnamed field)",
+                    "#[ts(transparent)] can only be used on structs with exactly one non-skipped field",
                 ));
             }
 
@@ -565,10 +977,24 @@ fn expand_derive_typescript(input: &DeriveInput) -> syn::Result<TokenStream2> {
             if let Some(ref pattern) = container_attrs.pattern {
                 let (strings, types) = parse_template_pattern(pattern)?;
                 let typedef = generate_template_literal_expr(&strings, &types);
-                return generate_impl(name, &type_name, &[], &container_attrs.wrapper, generics, typedef);
+                return generate_impl(name, &type_name, &[], &container_attrs, generics, generic_def, typedef);
             }
 
-            let (typedef, validations) = generate_struct_typedef(&data.fields, &container_attrs)?;
+            let (typedef, validations) = generate_struct_typedef(&decl_data.fields, &container_attrs)?;
+
+            // Internally tagged structs (#[serde(tag = "...")]) carry their name as a tag field
+            let typedef = match (&container_attrs.tag, &decl_data.fields) {
+                (Some(tag), syn::Fields::Named(_)) => quote! {
+                    {
+                        let tag = ferro_type::Field::new(
+                            #tag,
+                            ferro_type::TypeDef::Literal(ferro_type::Literal::String(#type_name.to_string())),
+                        );
+                        ferro_type::flatten_object(vec![tag], vec![#typedef])
+                    }
+                },
+                _ => typedef,
+            };
 
             // Handle intersection types via extends attribute
             let typedef = if let Some(ref extends_type) = container_attrs.extends {
@@ -582,7 +1008,7 @@ fn expand_derive_typescript(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 typedef
             };
 
-            let impl_code = generate_impl(name, &type_name, &container_attrs.namespace, &container_attrs.wrapper, generics, typedef)?;
+            let impl_code = generate_impl(name, &type_name, &container_attrs.namespace, &container_attrs, generics, generic_def, typedef)?;
 
             // Generate validation code for indexed access with Type/Ident
             let validation_code = generate_indexed_access_validations(name, &validations);
@@ -592,7 +1018,7 @@ fn expand_derive_typescript(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 #validation_code
             })
         }
-        Data::Union(_) => {
+        _ => {
             Err(syn::Error::new_spanned(
                 input,
                 "TypeScript derive is not supported for unions",
@@ -601,10 +1027,37 @@ fn expand_derive_typescript(input: &DeriveInput) -> syn::Result<TokenStream2> {
     }
 }
 
+/// JSON representation of an enum, mirroring serde's enum representations.
+enum EnumRepr<'a> {
+    /// `{ "Variant": data }`, or `"Variant"` for unit variants (serde's default)
+    External,
+    /// `{ [tag]: "Variant", ...fields }`
+    Internal { tag: &'a str },
+    /// `{ [tag]: "Variant", [content]: data }`
+    Adjacent { tag: &'a str, content: &'a str },
+    /// Variant data only, with no discriminant
+    Untagged,
+}
+
+impl ContainerAttrs {
+    fn enum_repr(&self) -> syn::Result<EnumRepr<'_>> {
+        match (self.untagged, self.tag.as_deref(), self.content.as_deref()) {
+            (true, _, _) => Ok(EnumRepr::Untagged),
+            (false, None, None) => Ok(EnumRepr::External),
+            (false, Some(tag), None) => Ok(EnumRepr::Internal { tag }),
+            (false, Some(tag), Some(content)) => Ok(EnumRepr::Adjacent { tag, content }),
+            (false, None, Some(_)) => Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "`content` requires `tag` to also be set",
+            )),
+        }
+    }
+}
+
 fn generate_enum_typedef(
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
     container_attrs: &ContainerAttrs,
-) -> syn::Result<TokenStream2> {
+) -> syn::Result<(TokenStream2, Vec<IndexedAccessValidation>)> {
     if variants.is_empty() {
         return Err(syn::Error::new(
             proc_macro2::Span::call_site(),
@@ -612,230 +1065,269 @@ fn generate_enum_typedef(
         ));
     }
 
-    // Check if all variants are unit variants (for string literal union type)
-    let all_unit = variants.iter().all(|v| matches!(v.fields, Fields::Unit));
-
-    // Handle untagged enums: generate plain union without discriminant
-    if container_attrs.untagged {
-        return generate_untagged_enum(variants, container_attrs);
+    if container_attrs.numeric || container_attrs.enum_style.is_some() {
+        return Ok((generate_unit_enum(variants, container_attrs)?, vec![]));
     }
 
-    // Get tag field name (default: "type")
-    let tag_name = container_attrs.tag.as_deref().unwrap_or("type");
-
-    // Check if using adjacent tagging (content field specified)
-    let content_name = container_attrs.content.as_deref();
-
-    if all_unit {
-        // Generate string literal union: "Pending" | "Active" | "Completed"
-        let mut variant_exprs: Vec<TokenStream2> = Vec::new();
-        for v in variants.iter() {
-            let variant_attrs = FieldAttrs::from_attrs(&v.attrs)?;
-            let name = get_field_name(&v.ident.to_string(), &variant_attrs, container_attrs);
-            variant_exprs.push(
-                quote! { ferro_type::TypeDef::Literal(ferro_type::Literal::String(#name.to_string())) }
-            );
+    // Variants marked #[ts(skip)] or #[serde(skip)] never appear in the output
+    let mut included: Vec<&syn::Variant> = Vec::new();
+    for variant in variants.iter() {
+        if !FieldAttrs::from_attrs(&variant.attrs)?.skip {
+            included.push(variant);
         }
+    }
+    if included.is_empty() {
+        return Ok((quote! { ferro_type::TypeDef::Primitive(ferro_type::Primitive::Never) }, vec![]));
+    }
+    let variants = included;
 
-        Ok(quote! {
-            ferro_type::TypeDef::Union(vec![#(#variant_exprs),*])
-        })
-    } else {
-        // Generate discriminated union with tag field
-        let mut variant_exprs: Vec<TokenStream2> = Vec::new();
-
-        for variant in variants.iter() {
-            let variant_attrs = FieldAttrs::from_attrs(&variant.attrs)?;
-            let variant_name_str = get_field_name(
-                &variant.ident.to_string(),
-                &variant_attrs,
-                container_attrs,
-            );
+    let mut validations: Vec<IndexedAccessValidation> = Vec::new();
+    let repr = container_attrs.enum_repr()?;
+    if let EnumRepr::Untagged = repr {
+        let typedef = generate_untagged_enum(&variants, container_attrs, &mut validations)?;
+        return Ok((typedef, validations));
+    }
 
-            let expr = match &variant.fields {
-                Fields::Unit => {
-                    // { [tag]: "VariantName" }
+    let mut variant_exprs: Vec<TokenStream2> = Vec::new();
+    for variant in variants.iter() {
+        let variant_attrs = FieldAttrs::from_attrs(&variant.attrs)?;
+        let variant_name_str = get_field_name(
+            &variant.ident.to_string(),
+            &variant_attrs,
+            container_attrs.rename_all,
+        );
+        let name_literal = quote! {
+            ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
+        };
+        // Variant docs go on the field that identifies the variant; a bare
+        // string literal for an externally tagged unit variant has none
+        let docs = variant_attrs.docs.field_suffix();
+
+        let expr = match repr {
+            EnumRepr::External => match &variant.fields {
+                // "Ping"
+                Fields::Unit => name_literal,
+                // { Text: string }, { D2: [number, number] }, { Circle: { ... } }
+                _ => {
+                    let data = generate_variant_data(variant, container_attrs, &mut validations)?;
                     quote! {
                         ferro_type::TypeDef::Object(vec![
-                            ferro_type::Field::new(
-                                #tag_name,
-                                ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                            )
+                            ferro_type::Field::new(#variant_name_str, #data) #docs
                         ])
                     }
                 }
-                Fields::Unnamed(fields) => {
-                    if let Some(content) = content_name {
-                        // Adjacent tagging: { [tag]: "Variant", [content]: data }
-                        let content_type = if fields.unnamed.len() == 1 {
-                            let field_type = &fields.unnamed.first().unwrap().ty;
-                            type_to_typedef(field_type)
-                        } else {
-                            let field_exprs: Vec<TokenStream2> = fields
-                                .unnamed
-                                .iter()
-                                .map(|f| type_to_typedef(&f.ty))
-                                .collect();
-                            quote! { ferro_type::TypeDef::Tuple(vec![#(#field_exprs),*]) }
-                        };
-                        quote! {
-                            ferro_type::TypeDef::Object(vec![
-                                ferro_type::Field::new(
-                                    #tag_name,
-                                    ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                                ),
-                                ferro_type::Field::new(#content, #content_type)
-                            ])
-                        }
-                    } else if fields.unnamed.len() == 1 {
-                        // Newtype variant (internal tagging): { [tag]: "Text"; value: T }
-                        let field_type = &fields.unnamed.first().unwrap().ty;
-                        let type_expr = type_to_typedef(field_type);
-                        quote! {
-                            ferro_type::TypeDef::Object(vec![
-                                ferro_type::Field::new(
-                                    #tag_name,
-                                    ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                                ),
-                                ferro_type::Field::new("value", #type_expr)
-                            ])
-                        }
-                    } else {
-                        // Tuple variant (internal tagging): { [tag]: "D2"; value: [T1, T2] }
-                        let field_exprs: Vec<TokenStream2> = fields
-                            .unnamed
-                            .iter()
-                            .map(|f| type_to_typedef(&f.ty))
-                            .collect();
-                        quote! {
-                            ferro_type::TypeDef::Object(vec![
-                                ferro_type::Field::new(
-                                    #tag_name,
-                                    ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                                ),
-                                ferro_type::Field::new(
-                                    "value",
-                                    ferro_type::TypeDef::Tuple(vec![#(#field_exprs),*])
-                                )
-                            ])
-                        }
+            },
+            EnumRepr::Internal { tag } => match &variant.fields {
+                // { [tag]: "Ping" }
+                Fields::Unit => quote! {
+                    ferro_type::TypeDef::Object(vec![ferro_type::Field::new(#tag, #name_literal) #docs])
+                },
+                // serde merges the newtype's own fields next to the tag:
+                // { [tag]: "Text" } & Inner
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    let field = fields.unnamed.first().unwrap();
+                    if !can_merge_tag(&field.ty) {
+                        return Err(syn::Error::new_spanned(
+                            &field.ty,
+                            "internally tagged newtype variants must contain a struct or map, which the tag is \
+                             merged into; serde fails to serialize them around primitives and sequences",
+                        ));
                     }
+                    let inner = field_to_typedef(field)?;
+                    quote! {
+                        ferro_type::TypeDef::Intersection(vec![
+                            ferro_type::TypeDef::Object(vec![ferro_type::Field::new(#tag, #name_literal) #docs]),
+                            #inner
+                        ])
+                    }
+                }
+                Fields::Unnamed(_) => {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "internally tagged enums cannot contain tuple variants; use #[ts(content = \"...\")] for adjacent tagging",
+                    ));
                 }
+                // { [tag]: "Circle"; center: Point; radius: number }
                 Fields::Named(fields) => {
-                    let mut field_exprs: Vec<TokenStream2> = Vec::new();
-                    for f in fields.named.iter() {
-                        let field_attrs = FieldAttrs::from_attrs(&f.attrs)?;
-                        if field_attrs.skip {
-                            continue;
-                        }
-                        let original_name = f.ident.as_ref().unwrap().to_string();
-                        let field_name = field_attrs.rename.clone().unwrap_or(original_name);
-                        let field_type = &f.ty;
-                        let type_expr = type_to_typedef(field_type);
-                        field_exprs.push(quote! {
-                            ferro_type::Field::new(#field_name, #type_expr)
-                        });
+                    let rename_all = variant_attrs.rename_all.or(container_attrs.rename_all_fields);
+                    let object = generate_object(fields, rename_all, false, &mut validations)?;
+                    quote! {
+                        ferro_type::flatten_object(
+                            vec![ferro_type::Field::new(#tag, #name_literal) #docs],
+                            vec![#object],
+                        )
                     }
-
-                    if let Some(content) = content_name {
-                        // Adjacent tagging: { [tag]: "Variant", [content]: { fields... } }
-                        quote! {
-                            ferro_type::TypeDef::Object(vec![
-                                ferro_type::Field::new(
-                                    #tag_name,
-                                    ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                                ),
-                                ferro_type::Field::new(
-                                    #content,
-                                    ferro_type::TypeDef::Object(vec![#(#field_exprs),*])
-                                )
-                            ])
-                        }
-                    } else {
-                        // Internal tagging: { [tag]: "Circle"; center: Point; radius: number }
-                        quote! {
-                            ferro_type::TypeDef::Object({
-                                let mut fields = vec![
-                                    ferro_type::Field::new(
-                                        #tag_name,
-                                        ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                                    )
-                                ];
-                                fields.extend(vec![#(#field_exprs),*]);
-                                fields
-                            })
-                        }
+                }
+            },
+            EnumRepr::Adjacent { tag, content } => match &variant.fields {
+                // { [tag]: "Ping" }
+                Fields::Unit => quote! {
+                    ferro_type::TypeDef::Object(vec![ferro_type::Field::new(#tag, #name_literal) #docs])
+                },
+                // { [tag]: "Text"; [content]: string }
+                _ => {
+                    let data = generate_variant_data(variant, container_attrs, &mut validations)?;
+                    quote! {
+                        ferro_type::TypeDef::Object(vec![
+                            ferro_type::Field::new(#tag, #name_literal) #docs,
+                            ferro_type::Field::new(#content, #data)
+                        ])
                     }
                 }
-            };
-            variant_exprs.push(expr);
+            },
+            EnumRepr::Untagged => unreachable!("untagged enums are handled above"),
+        };
+        variant_exprs.push(expr);
+    }
+
+    Ok((quote! {
+        ferro_type::TypeDef::Union(vec![#(#variant_exprs),*])
+    }, validations))
+}
+
+/// Generate a unit-only enum whose variants are string or number literals,
+/// either as a union of those literals or as a TypeScript enum declaration.
+///
+/// With `repr = "number"` each variant is its discriminant, following Rust's
+/// numbering: explicit values where given, otherwise one more than the
+/// previous variant. This matches what `serde_repr` serializes.
+fn generate_unit_enum(
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    container_attrs: &ContainerAttrs,
+) -> syn::Result<TokenStream2> {
+    let attr = if container_attrs.numeric { "repr = \"number\"" } else { "enum_style" };
+    if !matches!(container_attrs.enum_repr()?, EnumRepr::External) {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("#[ts({})] cannot be combined with tagged or untagged enums", attr),
+        ));
+    }
+    if container_attrs.enum_style.is_some() && container_attrs.wrapper.is_some() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "#[ts(wrapper)] cannot be used on enum declarations",
+        ));
+    }
+
+    let mut members: Vec<TokenStream2> = Vec::new();
+    let mut discriminant: i128 = 0;
+    for variant in variants.iter() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("#[ts({})] requires an enum with only unit variants", attr),
+            ));
+        }
+        if let (true, Some((_, expr))) = (container_attrs.numeric, &variant.discriminant) {
+            discriminant = parse_discriminant(expr)?;
         }
+        let value = discriminant;
+        discriminant += 1;
 
-        Ok(quote! {
-            ferro_type::TypeDef::Union(vec![#(#variant_exprs),*])
-        })
+        let variant_attrs = FieldAttrs::from_attrs(&variant.attrs)?;
+        if variant_attrs.skip {
+            continue;
+        }
+
+        let value = if container_attrs.numeric {
+            let lit = proc_macro2::Literal::i128_unsuffixed(value);
+            quote! { ferro_type::Literal::Number((#lit) as f64) }
+        } else {
+            let name = get_field_name(&variant.ident.to_string(), &variant_attrs, container_attrs.rename_all);
+            quote! { ferro_type::Literal::String(#name.to_string()) }
+        };
+        let docs = variant_attrs.docs.field_suffix();
+        members.push(match container_attrs.enum_style {
+            Some(_) => {
+                let member_name = variant.ident.unraw().to_string();
+                quote! { ferro_type::EnumMember::new(#member_name, #value) #docs }
+            }
+            None => quote! { ferro_type::TypeDef::Literal(#value) },
+        });
+    }
+
+    let style = match container_attrs.enum_style {
+        None if members.is_empty() => {
+            return Ok(quote! { ferro_type::TypeDef::Primitive(ferro_type::Primitive::Never) });
+        }
+        None => return Ok(quote! { ferro_type::TypeDef::Union(vec![#(#members),*]) }),
+        Some(EnumStyleAttr::Enum) => quote! { ferro_type::EnumStyle::Enum },
+        Some(EnumStyleAttr::ConstEnum) => quote! { ferro_type::EnumStyle::ConstEnum },
+        Some(EnumStyleAttr::AsConst) => quote! { ferro_type::EnumStyle::AsConst },
+    };
+    Ok(quote! {
+        ferro_type::TypeDef::Enum {
+            members: vec![#(#members),*],
+            style: #style,
+        }
+    })
+}
+
+/// Parses an enum discriminant, which must be an integer literal such as
+/// `3` or `-1`.
+fn parse_discriminant(expr: &syn::Expr) -> syn::Result<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(int), .. }) => int.base10_parse(),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => {
+            parse_discriminant(expr).map(|value| -value)
+        }
+        syn::Expr::Group(group) => parse_discriminant(&group.expr),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "#[ts(repr = \"number\")] requires integer literal discriminants",
+        )),
+    }
+}
+
+/// Generate the data carried by a non-unit variant: the inner type for
+/// newtypes, a tuple for tuple variants, or an object for struct variants.
+fn generate_variant_data(
+    variant: &syn::Variant,
+    container_attrs: &ContainerAttrs,
+    validations: &mut Vec<IndexedAccessValidation>,
+) -> syn::Result<TokenStream2> {
+    match &variant.fields {
+        Fields::Unit => Ok(quote! { ferro_type::TypeDef::Primitive(ferro_type::Primitive::Null) }),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            field_to_typedef(fields.unnamed.first().unwrap())
+        }
+        Fields::Unnamed(fields) => {
+            let field_exprs = fields
+                .unnamed
+                .iter()
+                .map(field_to_typedef)
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! { ferro_type::TypeDef::Tuple(vec![#(#field_exprs),*]) })
+        }
+        Fields::Named(fields) => {
+            // A variant-level rename_all wins over the container's rename_all_fields
+            let rename_all = FieldAttrs::from_attrs(&variant.attrs)?
+                .rename_all
+                .or(container_attrs.rename_all_fields);
+            generate_object(fields, rename_all, false, validations)
+        }
     }
 }
 
 /// Generate untagged enum: plain union without discriminant fields
 fn generate_untagged_enum(
-    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    variants: &[&syn::Variant],
     container_attrs: &ContainerAttrs,
+    validations: &mut Vec<IndexedAccessValidation>,
 ) -> syn::Result<TokenStream2> {
     let mut variant_exprs: Vec<TokenStream2> = Vec::new();
+    let mut has_null = false;
 
     for variant in variants.iter() {
-        let variant_attrs = FieldAttrs::from_attrs(&variant.attrs)?;
-        let variant_name_str = get_field_name(
-            &variant.ident.to_string(),
-            &variant_attrs,
-            container_attrs,
-        );
-
         let expr = match &variant.fields {
+            // serde writes every untagged unit variant as `null`; emit it once
+            Fields::Unit if has_null => continue,
             Fields::Unit => {
-                // Unit variant in untagged enum becomes string literal
-                quote! {
-                    ferro_type::TypeDef::Literal(ferro_type::Literal::String(#variant_name_str.to_string()))
-                }
-            }
-            Fields::Unnamed(fields) => {
-                if fields.unnamed.len() == 1 {
-                    // Newtype: just the inner type
-                    let field_type = &fields.unnamed.first().unwrap().ty;
-                    type_to_typedef(field_type)
-                } else {
-                    // Tuple: [T1, T2, ...]
-                    let field_exprs: Vec<TokenStream2> = fields
-                        .unnamed
-                        .iter()
-                        .map(|f| type_to_typedef(&f.ty))
-                        .collect();
-                    quote! {
-                        ferro_type::TypeDef::Tuple(vec![#(#field_exprs),*])
-                    }
-                }
-            }
-            Fields::Named(fields) => {
-                // Struct variant: { field1: T1, field2: T2 }
-                let mut field_exprs: Vec<TokenStream2> = Vec::new();
-                for f in fields.named.iter() {
-                    let field_attrs = FieldAttrs::from_attrs(&f.attrs)?;
-                    if field_attrs.skip {
-                        continue;
-                    }
-                    let original_name = f.ident.as_ref().unwrap().to_string();
-                    let field_name = field_attrs.rename.clone().unwrap_or(original_name);
-                    let field_type = &f.ty;
-                    let type_expr = type_to_typedef(field_type);
-                    field_exprs.push(quote! {
-                        ferro_type::Field::new(#field_name, #type_expr)
-                    });
-                }
-                quote! {
-                    ferro_type::TypeDef::Object(vec![#(#field_exprs),*])
-                }
+                has_null = true;
+                quote! { ferro_type::TypeDef::Primitive(ferro_type::Primitive::Null) }
             }
+            // Newtype, tuple or struct variant: just the data
+            _ => generate_variant_data(variant, container_attrs, validations)?,
         };
         variant_exprs.push(expr);
     }
@@ -852,148 +1344,23 @@ fn generate_struct_typedef(
     match fields {
         syn::Fields::Named(fields) => {
             // Named struct: Object with fields
-            if fields.named.is_empty() {
-                // Empty struct becomes empty object
-                return Ok((quote! { ferro_type::TypeDef::Object(vec![]) }, vec![]));
-            }
-
-            // Separate regular fields from flattened fields
-            let mut regular_field_exprs: Vec<TokenStream2> = Vec::new();
             let mut validations: Vec<IndexedAccessValidation> = Vec::new();
-            let mut field_index: usize = 0;
-            let mut flatten_exprs: Vec<TokenStream2> = Vec::new();
-
-            for f in fields.named.iter() {
-                let field_attrs = FieldAttrs::from_attrs(&f.attrs)?;
-                // Skip fields marked with #[ts(skip)]
-                if field_attrs.skip {
-                    continue;
-                }
-
-                let field_type = &f.ty;
-
-                // Validate indexed access attributes - both must be present or neither
-                if field_attrs.index.is_some() != field_attrs.key.is_some() {
-                    return Err(syn::Error::new_spanned(
-                        f,
-                        "#[ts(index = ...)] and #[ts(key = ...)] must be used together",
-                    ));
-                }
-
-                if field_attrs.flatten {
-                    // For flattened fields, we extract the inner type's fields at runtime
-                    flatten_exprs.push(quote! {
-                        {
-                            let inner_td = <#field_type as ferro_type::TS>::typescript();
-                            ferro_type::extract_object_fields(&inner_td)
-                        }
-                    });
-                } else {
-                    let original_name = f.ident.as_ref().unwrap().to_string();
-                    let field_name = get_field_name(&original_name, &field_attrs, container_attrs);
-
-                    // Determine the type expression
-                    let type_expr = if let Some(ref type_override) = field_attrs.type_override {
-                        quote! { ferro_type::TypeDef::Ref(#type_override.to_string()) }
-                    } else if field_attrs.has_indexed_access() {
-                        // Use indexed access type: Profile["login"]
-                        let index_spec = field_attrs.index.as_ref().unwrap();
-                        let key_spec = field_attrs.key.as_ref().unwrap();
-
-                        // Get string representations for TypeDef
-                        let index_str = match index_spec {
-                            IndexSpec::Type(ty) => quote! { stringify!(#ty).to_string() },
-                            IndexSpec::String(s) => quote! { #s.to_string() },
-                        };
-                        let key_str = match key_spec {
-                            KeySpec::Ident(ident) => quote! { stringify!(#ident).to_string() },
-                            KeySpec::String(s) => quote! { #s.to_string() },
-                        };
-
-                        // Collect validation info if both are Type/Ident (not strings)
-                        if let (IndexSpec::Type(index_type), KeySpec::Ident(key_ident)) =
-                            (index_spec, key_spec)
-                        {
-                            validations.push(IndexedAccessValidation {
-                                index_type: index_type.clone(),
-                                key_ident: key_ident.clone(),
-                                field_index,
-                            });
-                            field_index += 1;
-                        }
-
-                        quote! {
-                            ferro_type::TypeDef::IndexedAccess {
-                                base: #index_str,
-                                key: #key_str,
-                            }
-                        }
-                    } else if field_attrs.has_pattern() {
-                        // Use template literal pattern
-                        let pattern = field_attrs.pattern.as_ref().unwrap();
-                        let (strings, types) = parse_template_pattern(pattern)?;
-                        generate_template_literal_expr(&strings, &types)
-                    } else if field_attrs.optional && is_option_type(field_type) {
-                        // For #[ts(optional)] on Option<T>, unwrap to just T
-                        // This generates `field?: T` instead of `field?: T | null`
-                        let inner_type = extract_option_inner(field_type).unwrap();
-                        let base_expr = type_to_typedef(inner_type);
-                        if field_attrs.inline {
-                            quote! { ferro_type::inline_typedef(#base_expr) }
-                        } else {
-                            base_expr
-                        }
-                    } else {
-                        let base_expr = type_to_typedef(field_type);
-                        if field_attrs.inline {
-                            quote! { ferro_type::inline_typedef(#base_expr) }
-                        } else {
-                            base_expr
-                        }
-                    };
-
-                    // Create field (optional if default or optional attribute is set)
-                    if field_attrs.default || field_attrs.optional {
-                        regular_field_exprs.push(quote! {
-                            ferro_type::Field::optional(#field_name, #type_expr)
-                        });
-                    } else {
-                        regular_field_exprs.push(quote! {
-                            ferro_type::Field::new(#field_name, #type_expr)
-                        });
-                    }
-                }
-            }
-
-            // If there are flattened fields, we need to build the vec dynamically
-            if flatten_exprs.is_empty() {
-                Ok((quote! {
-                    ferro_type::TypeDef::Object(vec![#(#regular_field_exprs),*])
-                }, validations))
-            } else {
-                Ok((quote! {
-                    {
-                        let mut fields = vec![#(#regular_field_exprs),*];
-                        #(fields.extend(#flatten_exprs);)*
-                        ferro_type::TypeDef::Object(fields)
-                    }
-                }, validations))
-            }
+            let object = generate_object(fields, container_attrs.rename_all, container_attrs.default, &mut validations)?;
+            Ok((object, validations))
         }
         syn::Fields::Unnamed(fields) => {
             // Tuple struct - no indexed access possible
             if fields.unnamed.len() == 1 {
                 // Newtype: unwrap to inner type
-                let field_type = &fields.unnamed.first().unwrap().ty;
-                let type_expr = type_to_typedef(field_type);
+                let type_expr = field_to_typedef(fields.unnamed.first().unwrap())?;
                 Ok((quote! { #type_expr }, vec![]))
             } else {
                 // Tuple: [type1, type2, ...]
-                let field_exprs: Vec<TokenStream2> = fields
+                let field_exprs = fields
                     .unnamed
                     .iter()
-                    .map(|f| type_to_typedef(&f.ty))
-                    .collect();
+                    .map(field_to_typedef)
+                    .collect::<syn::Result<Vec<_>>>()?;
 
                 Ok((quote! {
                     ferro_type::TypeDef::Tuple(vec![#(#field_exprs),*])
@@ -1007,6 +1374,169 @@ fn generate_struct_typedef(
     }
 }
 
+/// Generate an expression evaluating to the object type of a named struct or
+/// struct variant, applying every field-level attribute.
+///
+/// Flattened fields are merged in with `ferro_type::flatten_object`, which
+/// intersects the object with those that aren't objects themselves. With
+/// `default`, from a container-level `#[serde(default)]`, every field is
+/// treated as if it had `#[serde(default)]`.
+fn generate_object(
+    fields: &syn::FieldsNamed,
+    rename_all: Option<RenameAll>,
+    default: bool,
+    validations: &mut Vec<IndexedAccessValidation>,
+) -> syn::Result<TokenStream2> {
+    // Separate regular fields from flattened fields
+    let mut regular_field_exprs: Vec<TokenStream2> = Vec::new();
+    let mut flatten_exprs: Vec<TokenStream2> = Vec::new();
+
+    for f in fields.named.iter() {
+        let mut field_attrs = FieldAttrs::from_attrs(&f.attrs)?;
+        field_attrs.default |= default;
+        // Skip fields marked with #[ts(skip)], and type-level markers
+        if field_attrs.skip || is_phantom_data(&f.ty) {
+            continue;
+        }
+
+        let field_type = &f.ty;
+
+        // Validate indexed access attributes - both must be present or neither
+        if field_attrs.index.is_some() != field_attrs.key.is_some() {
+            return Err(syn::Error::new_spanned(
+                f,
+                "#[ts(index = ...)] and #[ts(key = ...)] must be used together",
+            ));
+        }
+
+        if field_attrs.flatten {
+            // Flattened types are merged in at runtime, once they are known
+            flatten_exprs.push(quote! { <#field_type as ferro_type::TS>::typescript() });
+            continue;
+        }
+
+        let original_name = f.ident.as_ref().unwrap().to_string();
+        let field_name = get_field_name(&original_name, &field_attrs, rename_all);
+
+        // Determine the type expression
+        let type_expr = if let Some(ref type_override) = field_attrs.type_override {
+            quote! { ferro_type::TypeDef::Ref(#type_override.to_string()) }
+        } else if field_attrs.has_indexed_access() {
+            // Use indexed access type: Profile["login"]
+            let index_spec = field_attrs.index.as_ref().unwrap();
+            let key_spec = field_attrs.key.as_ref().unwrap();
+
+            // Get string representations for TypeDef
+            let index_str = match index_spec {
+                IndexSpec::Type(ty) => quote! { stringify!(#ty).to_string() },
+                IndexSpec::String(s) => quote! { #s.to_string() },
+            };
+            let key_str = match key_spec {
+                KeySpec::Ident(ident) => quote! { stringify!(#ident).to_string() },
+                KeySpec::String(s) => quote! { #s.to_string() },
+            };
+
+            // Collect validation info if both are Type/Ident (not strings)
+            if let (IndexSpec::Type(index_type), KeySpec::Ident(key_ident)) =
+                (index_spec, key_spec)
+            {
+                validations.push(IndexedAccessValidation {
+                    index_type: index_type.clone(),
+                    key_ident: key_ident.clone(),
+                    field_index: validations.len(),
+                });
+            }
+
+            quote! {
+                ferro_type::TypeDef::IndexedAccess {
+                    base: #index_str,
+                    key: #key_str,
+                }
+            }
+        } else if field_attrs.has_pattern() {
+            // Use template literal pattern
+            let pattern = field_attrs.pattern.as_ref().unwrap();
+            let (strings, types) = parse_template_pattern(pattern)?;
+            generate_template_literal_expr(&strings, &types)
+        } else if (field_attrs.optional || field_attrs.skips_none(field_type)) && is_option_type(field_type) {
+            // For #[ts(optional)] on Option<T>, or an Option<T> never written
+            // as `null`, unwrap to just T
+            // This generates `field?: T` instead of `field?: T | null`
+            let inner_type = extract_option_inner(field_type).unwrap();
+            let base_expr = type_to_typedef(inner_type);
+            if field_attrs.inline {
+                quote! { ferro_type::inline_typedef(#base_expr) }
+            } else {
+                base_expr
+            }
+        } else {
+            let base_expr = type_to_typedef(field_type);
+            if field_attrs.inline {
+                quote! { ferro_type::inline_typedef(#base_expr) }
+            } else {
+                base_expr
+            }
+        };
+
+        let int_policy = field_attrs.int_policy_suffix();
+        let type_expr = quote! { #type_expr #int_policy };
+
+        // Create field (optional if default, optional or skip_serializing_if is set)
+        let docs = field_attrs.docs.field_suffix();
+        if field_attrs.is_optional() {
+            let shape = field_attrs.shape_suffix(field_type);
+            regular_field_exprs.push(quote! {
+                ferro_type::Field::optional(#field_name, #type_expr) #shape #docs
+            });
+        } else {
+            regular_field_exprs.push(quote! {
+                ferro_type::Field::new(#field_name, #type_expr) #docs
+            });
+        }
+    }
+
+    if flatten_exprs.is_empty() {
+        Ok(quote! { ferro_type::TypeDef::Object(vec![#(#regular_field_exprs),*]) })
+    } else {
+        Ok(quote! {
+            ferro_type::flatten_object(vec![#(#regular_field_exprs),*], vec![#(#flatten_exprs),*])
+        })
+    }
+}
+
+/// Returns false for types an internally tagged newtype variant can't hold,
+/// because serde has no object to merge the tag into: primitives, options
+/// and sequences.
+///
+/// Other types, such as structs and maps, are assumed to be objects.
+fn can_merge_tag(ty: &Type) -> bool {
+    const NOT_OBJECTS: &[&str] = &[
+        "bool", "char", "str", "String", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+        "u128", "usize", "f32", "f64", "Option", "Vec", "VecDeque", "LinkedList", "HashSet", "BTreeSet",
+        "BinaryHeap",
+    ];
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_none_or(|segment| !NOT_OBJECTS.iter().any(|name| segment.ident == name)),
+        Type::Reference(reference) => can_merge_tag(&reference.elem),
+        Type::Paren(paren) => can_merge_tag(&paren.elem),
+        Type::Group(group) => can_merge_tag(&group.elem),
+        // `()` is written as just the tag
+        Type::Tuple(tuple) => tuple.elems.is_empty(),
+        Type::Array(_) | Type::Slice(_) => false,
+        _ => true,
+    }
+}
+
+/// Whether a field's type is `PhantomData<...>`, which carries no data
+fn is_phantom_data(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none()
+        && path.path.segments.last().is_some_and(|segment| segment.ident == "PhantomData"))
+}
+
 /// Generate compile-time validation code for indexed access fields.
 ///
 /// For each validated indexed access, generates:
@@ -1066,13 +1596,21 @@ fn type_to_typedef(ty: &Type) -> TokenStream2 {
     quote! { <#ty as ferro_type::TS>::typescript() }
 }
 
+/// Convert a tuple field to its TypeDef, applying its integer policies.
+fn field_to_typedef(field: &syn::Field) -> syn::Result<TokenStream2> {
+    let type_expr = type_to_typedef(&field.ty);
+    let int_policy = FieldAttrs::from_attrs(&field.attrs)?.int_policy_suffix();
+    Ok(quote! { #type_expr #int_policy })
+}
+
 /// Generate implementation for a transparent newtype wrapper.
 /// The TypeScript representation is just the inner type, not wrapped in Named.
 fn generate_transparent_impl(
     name: &Ident,
-    inner_type: &Type,
+    field: &syn::Field,
     generics: &Generics,
 ) -> syn::Result<TokenStream2> {
+    let type_expr = field_to_typedef(field)?;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // Add TS bounds to generic parameters
@@ -1106,7 +1644,7 @@ fn generate_transparent_impl(
     Ok(quote! {
         impl #impl_generics ferro_type::TS for #name #ty_generics #where_clause {
             fn typescript() -> ferro_type::TypeDef {
-                <#inner_type as ferro_type::TS>::typescript()
+                #type_expr
             }
         }
     })
@@ -1116,8 +1654,9 @@ fn generate_impl(
     name: &Ident,
     name_str: &str,
     namespace: &[String],
-    wrapper: &Option<String>,
+    container_attrs: &ContainerAttrs,
     generics: &Generics,
+    generic_def: bool,
     typedef_expr: TokenStream2,
 ) -> syn::Result<TokenStream2> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
@@ -1160,6 +1699,7 @@ fn generate_impl(
         quote! {
             #[ferro_type::linkme::distributed_slice(ferro_type::TYPESCRIPT_TYPES)]
             #[linkme(crate = ferro_type::linkme)]
+            #[allow(deprecated)]
             static #register_name: fn() -> ferro_type::TypeDef = || <#name as ferro_type::TS>::typescript();
         }
     } else {
@@ -1175,24 +1715,329 @@ fn generate_impl(
     };
 
     // Generate wrapper option
-    let wrapper_expr = match wrapper {
+    let wrapper_expr = match &container_attrs.wrapper {
         Some(w) => quote! { Some(#w.to_string()) },
         None => quote! { None },
     };
+    let docs_expr = container_attrs.docs.option_expr();
+    let declaration_style_expr = container_attrs.declaration_style_expr();
+
+    if generic_def {
+        if !namespace.is_empty() {
+            return Err(syn::Error::new_spanned(
+                generics,
+                "#[ts(namespace = ...)] is not supported on generic types",
+            ));
+        }
+        let body = generate_generic_typescript(name_str, container_attrs, generics, typedef_expr)?;
+        return Ok(quote! {
+            #[allow(deprecated)]
+            impl #impl_generics ferro_type::TS for #name #ty_generics #where_clause {
+                fn typescript() -> ferro_type::TypeDef {
+                    #body
+                }
+            }
+        });
+    }
+
+    // Recursive references point back at the (namespace-qualified) declaration
+    let reference_name = if namespace.is_empty() {
+        name_str.to_string()
+    } else {
+        format!("{}.{}", namespace.join("."), name_str)
+    };
 
     Ok(quote! {
+        #[allow(deprecated)]
         impl #impl_generics ferro_type::TS for #name #ty_generics #where_clause {
             fn typescript() -> ferro_type::TypeDef {
-                ferro_type::TypeDef::Named {
-                    namespace: #namespace_expr,
+                ferro_type::recursion_guard::<Self>(
+                    || ferro_type::TypeDef::Ref(#reference_name.to_string()),
+                    || ferro_type::TypeDef::Named {
+                        namespace: #namespace_expr,
+                        name: #name_str.to_string(),
+                        def: Box::new(#typedef_expr),
+                        module: Some(module_path!().to_string()),
+                        wrapper: #wrapper_expr,
+                        docs: #docs_expr,
+                        declaration_style: #declaration_style_expr,
+                    },
+                )
+            }
+        }
+
+        #registration
+    })
+}
+
+/// Generate the body of `typescript()` for a generic type.
+///
+/// The result is a `Generic` application of the concrete arguments, e.g.
+/// `Page<User>`, carrying the `GenericDef` declaration `type Page<T> = ...`.
+/// The declaration body is built from field types whose type parameters were
+/// replaced by local marker types (see [`ParamSubstituter`]) that render as
+/// `TypeParamRef`s, so every instantiation shares the same declaration.
+fn generate_generic_typescript(
+    name_str: &str,
+    container_attrs: &ContainerAttrs,
+    generics: &Generics,
+    typedef_expr: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let bounds = &container_attrs.bounds;
+    let type_params: Vec<&syn::TypeParam> = generics.type_params().collect();
+    let mut substituter = ParamSubstituter::new(generics.type_params());
+    let mut markers: Vec<TokenStream2> = Vec::new();
+    let mut args: Vec<TokenStream2> = Vec::new();
+    let mut param_exprs: Vec<TokenStream2> = Vec::new();
+
+    for tp in &type_params {
+        let ident = &tp.ident;
+        let marker = param_marker(ident);
+        let param_name = ident.to_string();
+
+        // Markers derive the common std traits so that bounds such as
+        // `T: Clone` on the type itself are still satisfied
+        markers.push(quote! {
+            #[allow(dead_code, non_camel_case_types)]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            struct #marker;
+
+            impl ferro_type::TS for #marker {
+                fn typescript() -> ferro_type::TypeDef {
+                    ferro_type::TypeDef::TypeParamRef(#param_name.to_string())
+                }
+            }
+        });
+        args.push(quote! { <#ident as ferro_type::TS>::typescript() });
+
+        let constraints = type_param_constraints(tp, generics, bounds);
+        let constraint = match constraints.as_slice() {
+            [] => None,
+            [single] => Some(quote! { .with_constraint(ferro_type::TypeDef::Ref(#single.to_string())) }),
+            many => Some(quote! {
+                .with_constraint(ferro_type::TypeDef::Intersection(vec![
+                    #(ferro_type::TypeDef::Ref(#many.to_string())),*
+                ]))
+            }),
+        };
+        let default = tp.default.clone().map(|mut ty| {
+            substituter.visit_type_mut(&mut ty);
+            quote! { .with_default(<#ty as ferro_type::TS>::typescript()) }
+        });
+        param_exprs.push(quote! { ferro_type::TypeParam::new(#param_name) #constraint #default });
+    }
+
+    // A wrapper such as "Prettify<Required<" becomes nested generic applications
+    let mut def = typedef_expr;
+    if let Some(wrapper) = &container_attrs.wrapper {
+        for base in wrapper.split('<').map(str::trim).filter(|s| !s.is_empty()).rev() {
+            def = quote! {
+                ferro_type::TypeDef::Generic { base: #base.to_string(), args: vec![#def], def: None }
+            };
+        }
+    }
+
+    let docs_expr = container_attrs.docs.option_expr();
+    let declaration_style_expr = container_attrs.declaration_style_expr();
+
+    Ok(quote! {
+        #(#markers)*
+
+        ferro_type::recursion_guard::<Self>(
+            || ferro_type::TypeDef::Generic {
+                base: #name_str.to_string(),
+                args: vec![#(#args),*],
+                def: None,
+            },
+            || ferro_type::TypeDef::Generic {
+                base: #name_str.to_string(),
+                args: vec![#(#args),*],
+                def: Some(Box::new(ferro_type::TypeDef::GenericDef {
                     name: #name_str.to_string(),
-                    def: Box::new(#typedef_expr),
+                    type_params: vec![#(#param_exprs),*],
+                    def: Box::new(#def),
                     module: Some(module_path!().to_string()),
-                    wrapper: #wrapper_expr,
+                    docs: #docs_expr,
+                    declaration_style: #declaration_style_expr,
+                })),
+            },
+        )
+    })
+}
+
+/// Collects the TypeScript constraints for a type parameter by looking up
+/// each of its trait bounds, inline or in the where clause, in `#[ts(bounds(...))]`.
+fn type_param_constraints<'a>(
+    tp: &syn::TypeParam,
+    generics: &Generics,
+    bounds: &'a [(String, String)],
+) -> Vec<&'a str> {
+    let mut constraints = Vec::new();
+    for bound in param_bounds(tp, generics) {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            continue;
+        };
+        let Some(segment) = trait_bound.path.segments.last() else {
+            continue;
+        };
+        for (trait_name, ts_type) in bounds {
+            if segment.ident == trait_name && !constraints.contains(&ts_type.as_str()) {
+                constraints.push(ts_type.as_str());
+            }
+        }
+    }
+    constraints
+}
+
+/// The bounds on a type parameter, inline or in the where clause.
+fn param_bounds<'a>(tp: &'a syn::TypeParam, generics: &'a Generics) -> Vec<&'a syn::TypeParamBound> {
+    let mut trait_bounds: Vec<&syn::TypeParamBound> = tp.bounds.iter().collect();
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(pt) = predicate {
+                if matches!(&pt.bounded_ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident(&tp.ident)) {
+                    trait_bounds.extend(pt.bounds.iter());
                 }
             }
         }
+    }
+    trait_bounds
+}
 
-        #registration
-    })
+/// Traits the marker types implement, so bounds on them are still satisfied.
+const MARKER_TRAITS: &[&str] = &[
+    "TS", "Clone", "Copy", "Debug", "Default", "PartialEq", "Eq", "PartialOrd", "Ord", "Hash", "Sized", "Send",
+    "Sync", "Unpin",
+];
+
+/// Generic std types whose `TS` impls put no bounds on their arguments beyond `TS`.
+const MARKER_WRAPPERS: &[&str] = &[
+    "Vec", "VecDeque", "LinkedList", "HashSet", "BTreeSet", "BinaryHeap", "HashMap", "BTreeMap", "Option",
+    "Result", "Box", "Rc", "Arc", "Cell", "RefCell", "Mutex", "RwLock", "Cow", "Wrapping", "Reverse", "PhantomData",
+];
+
+/// Returns true if the marker types can stand in for the type parameters in
+/// every field type.
+///
+/// A parameter with a bound the markers don't implement, such as
+/// `T: Entity`, can't be passed to another type that requires that bound,
+/// such as `Wrapper<T>` with `struct Wrapper<T: Entity>`, or have its
+/// associated types named, as in `T::Id`. Std types such as `Vec<T>` only
+/// require `TS`, so they are fine.
+fn markers_fit(generics: &Generics, data: &Data) -> bool {
+    let bounded: Vec<&Ident> = generics
+        .type_params()
+        .filter(|tp| {
+            param_bounds(tp, generics).iter().any(|bound| match bound {
+                syn::TypeParamBound::Trait(trait_bound) => trait_bound
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| !MARKER_TRAITS.iter().any(|name| segment.ident == name)),
+                _ => false,
+            })
+        })
+        .map(|tp| &tp.ident)
+        .collect();
+    if bounded.is_empty() {
+        return true;
+    }
+
+    let fields: Vec<&syn::Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    };
+    let defaults = generics.type_params().filter_map(|tp| tp.default.as_ref());
+    fields.iter().map(|f| &f.ty).chain(defaults).all(|ty| markers_fit_type(ty, &bounded, false))
+}
+
+/// Returns true if `ty` doesn't use a bounded parameter where the markers
+/// can't stand in for it. `nested` is true inside the arguments of a type
+/// that isn't one of the [`MARKER_WRAPPERS`].
+fn markers_fit_type(ty: &Type, bounded: &[&Ident], nested: bool) -> bool {
+    match ty {
+        Type::Path(type_path) => {
+            if let Some(qself) = &type_path.qself {
+                if mentions_any(&qself.ty, bounded) {
+                    return false;
+                }
+            }
+            let path = &type_path.path;
+            if let Some(first) = path.segments.first() {
+                if type_path.qself.is_none() && bounded.contains(&&first.ident) {
+                    // `T` itself is fine where it isn't required to be an `Entity`,
+                    // but `T::Id` is not
+                    return path.segments.len() == 1 && !nested;
+                }
+            }
+            path.segments.iter().all(|segment| {
+                let nested = nested || !MARKER_WRAPPERS.iter().any(|name| segment.ident == name);
+                match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args.args.iter().all(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => markers_fit_type(ty, bounded, nested),
+                        _ => true,
+                    }),
+                    _ => true,
+                }
+            })
+        }
+        Type::Array(array) => markers_fit_type(&array.elem, bounded, nested),
+        Type::Slice(slice) => markers_fit_type(&slice.elem, bounded, nested),
+        Type::Reference(reference) => markers_fit_type(&reference.elem, bounded, nested),
+        Type::Paren(paren) => markers_fit_type(&paren.elem, bounded, nested),
+        Type::Group(group) => markers_fit_type(&group.elem, bounded, nested),
+        Type::Tuple(tuple) => tuple.elems.iter().all(|ty| markers_fit_type(ty, bounded, nested)),
+        _ => !mentions_any(ty, bounded),
+    }
+}
+
+/// Returns true if any of `params` appears anywhere in `ty`.
+fn mentions_any(ty: &Type, params: &[&Ident]) -> bool {
+    struct Finder<'a> {
+        params: &'a [&'a Ident],
+        found: bool,
+    }
+    impl<'ast> Visit<'ast> for Finder<'_> {
+        fn visit_path(&mut self, path: &'ast syn::Path) {
+            if path.segments.first().is_some_and(|first| self.params.contains(&&first.ident)) {
+                self.found = true;
+            }
+            visit::visit_path(self, path);
+        }
+    }
+    let mut finder = Finder { params, found: false };
+    finder.visit_type(ty);
+    finder.found
+}
+
+/// Name of the marker type standing in for a type parameter in a generic declaration.
+fn param_marker(param: &Ident) -> Ident {
+    format_ident!("__TsParam{}", param)
+}
+
+/// Replaces a generic type's own type parameters with their marker types.
+struct ParamSubstituter {
+    params: Vec<Ident>,
+}
+
+impl ParamSubstituter {
+    fn new<'a>(params: impl Iterator<Item = &'a syn::TypeParam>) -> Self {
+        Self {
+            params: params.map(|tp| tp.ident.clone()).collect(),
+        }
+    }
+}
+
+impl VisitMut for ParamSubstituter {
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        if path.leading_colon.is_none() {
+            if let Some(first) = path.segments.first_mut() {
+                if self.params.contains(&first.ident) {
+                    first.ident = param_marker(&first.ident);
+                }
+            }
+        }
+        visit_mut::visit_path_mut(self, path);
+    }
 }
@@ -31,6 +31,8 @@ impl TS for Point {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -57,6 +59,8 @@ impl TS for User {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -92,6 +96,8 @@ impl TS for Profile {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -112,6 +118,8 @@ impl TS for Rgb {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -128,6 +136,8 @@ impl TS for Ping {
             def: Box::new(TypeDef::Primitive(Primitive::Null)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -144,6 +154,8 @@ impl TS for UserId {
             def: Box::new(TypeDef::Primitive(Primitive::Number)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -166,6 +178,8 @@ impl TS for Rectangle {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -186,6 +200,8 @@ impl TS for Polygon {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -209,6 +225,8 @@ impl TS for Config {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -239,6 +257,8 @@ impl TS for Status {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -257,15 +277,13 @@ impl TS for Coordinate {
             name: "Coordinate".to_string(),
             def: Box::new(TypeDef::Union(vec![
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("D2".to_string()))),
-                    Field::new("value", TypeDef::Tuple(vec![
+                    Field::new("D2", TypeDef::Tuple(vec![
                         TypeDef::Primitive(Primitive::Number),
                         TypeDef::Primitive(Primitive::Number),
                     ])),
                 ]),
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("D3".to_string()))),
-                    Field::new("value", TypeDef::Tuple(vec![
+                    Field::new("D3", TypeDef::Tuple(vec![
                         TypeDef::Primitive(Primitive::Number),
                         TypeDef::Primitive(Primitive::Number),
                         TypeDef::Primitive(Primitive::Number),
@@ -274,6 +292,8 @@ impl TS for Coordinate {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -301,25 +321,24 @@ impl TS for Message {
             namespace: vec![],
             name: "Message".to_string(),
             def: Box::new(TypeDef::Union(vec![
+                TypeDef::Literal(ferro_type::Literal::String("Ping".to_string())),
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Ping".to_string()))),
+                    Field::new("Text", TypeDef::Primitive(Primitive::String)),
                 ]),
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Text".to_string()))),
-                    Field::new("value", TypeDef::Primitive(Primitive::String)),
+                    Field::new("Binary", TypeDef::Array(Box::new(TypeDef::Primitive(Primitive::Number)))),
                 ]),
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Binary".to_string()))),
-                    Field::new("value", TypeDef::Array(Box::new(TypeDef::Primitive(Primitive::Number)))),
-                ]),
-                TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Error".to_string()))),
-                    Field::new("code", TypeDef::Primitive(Primitive::Number)),
-                    Field::new("message", TypeDef::Primitive(Primitive::String)),
+                    Field::new("Error", TypeDef::Object(vec![
+                        Field::new("code", TypeDef::Primitive(Primitive::Number)),
+                        Field::new("message", TypeDef::Primitive(Primitive::String)),
+                    ])),
                 ]),
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -351,6 +370,8 @@ impl TS for GetUserRequest {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -374,6 +395,8 @@ impl TS for GetUserResponse {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -401,6 +424,8 @@ impl TS for ListUsersRequest {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -427,6 +452,8 @@ impl TS for ListUsersResponse {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -453,6 +480,8 @@ impl TS for ApiError {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -485,6 +514,8 @@ impl TS for DetailedError {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -506,27 +537,28 @@ impl TS for RpcError {
             name: "RpcError".to_string(),
             def: Box::new(TypeDef::Union(vec![
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("NotFound".to_string()))),
-                    Field::new("resource", TypeDef::Primitive(Primitive::String)),
-                ]),
-                TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Unauthorized".to_string()))),
-                ]),
-                TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Forbidden".to_string()))),
-                    Field::new("reason", TypeDef::Primitive(Primitive::String)),
+                    Field::new("NotFound", TypeDef::Object(vec![
+                        Field::new("resource", TypeDef::Primitive(Primitive::String)),
+                    ])),
                 ]),
+                TypeDef::Literal(ferro_type::Literal::String("Unauthorized".to_string())),
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("BadRequest".to_string()))),
-                    Field::new("field", TypeDef::Primitive(Primitive::String)),
-                    Field::new("message", TypeDef::Primitive(Primitive::String)),
+                    Field::new("Forbidden", TypeDef::Object(vec![
+                        Field::new("reason", TypeDef::Primitive(Primitive::String)),
+                    ])),
                 ]),
                 TypeDef::Object(vec![
-                    Field::new("type", TypeDef::Literal(ferro_type::Literal::String("Internal".to_string()))),
+                    Field::new("BadRequest", TypeDef::Object(vec![
+                        Field::new("field", TypeDef::Primitive(Primitive::String)),
+                        Field::new("message", TypeDef::Primitive(Primitive::String)),
+                    ])),
                 ]),
+                TypeDef::Literal(ferro_type::Literal::String("Internal".to_string())),
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         }
     }
 }
@@ -1142,6 +1174,7 @@ pub enum DerivedRpcError {
 #[cfg(test)]
 mod derive_tests {
     use super::*;
+    use ferro_type::TypeRegistry;
 
     /// Helper to get the inner definition from a Named TypeDef
     fn inner_def(td: TypeDef) -> TypeDef {
@@ -1167,7 +1200,7 @@ mod derive_tests {
         let td = DerivedCoordinate::typescript();
         assert_eq!(
             inner_def(td.clone()).render(),
-            r#"{ type: "D2"; value: [number, number] } | { type: "D3"; value: [number, number, number] }"#
+            r#"{ D2: [number, number] } | { D3: [number, number, number] }"#
         );
         assert_eq!(td.render(), "DerivedCoordinate");
     }
@@ -1178,7 +1211,7 @@ mod derive_tests {
         // Note: Point now renders as "Point" (named type reference)
         assert_eq!(
             inner_def(td.clone()).render(),
-            r#"{ type: "Circle"; center: Point; radius: number } | { type: "Rectangle"; top_left: Point; width: number; height: number } | { type: "Triangle"; a: Point; b: Point; c: Point }"#
+            r#"{ Circle: { center: Point; radius: number } } | { Rectangle: { top_left: Point; width: number; height: number } } | { Triangle: { a: Point; b: Point; c: Point } }"#
         );
         assert_eq!(td.render(), "DerivedShape");
     }
@@ -1188,7 +1221,7 @@ mod derive_tests {
         let td = DerivedMessage::typescript();
         assert_eq!(
             inner_def(td.clone()).render(),
-            r#"{ type: "Ping" } | { type: "Text"; value: string } | { type: "Binary"; value: number[] } | { type: "Error"; code: number; message: string }"#
+            r#""Ping" | { Text: string } | { Binary: number[] } | { Error: { code: number; message: string } }"#
         );
         assert_eq!(td.render(), "DerivedMessage");
     }
@@ -1196,10 +1229,13 @@ mod derive_tests {
     #[test]
     fn test_derived_generic_enum() {
         let td = <DerivedOptionalValue<String>>::typescript();
-        assert_eq!(
-            inner_def(td).render(),
-            r#"{ type: "None" } | { type: "Some"; value: string }"#
-        );
+        assert_eq!(td.render(), "DerivedOptionalValue<string>");
+
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(td);
+        assert!(registry
+            .render()
+            .contains(r#"type DerivedOptionalValue<T> = "None" | { Some: T };"#));
     }
 
     #[test]
@@ -1207,7 +1243,7 @@ mod derive_tests {
         let td = DerivedRpcError::typescript();
         assert_eq!(
             inner_def(td).render(),
-            r#"{ type: "NotFound"; resource: string } | { type: "Unauthorized" } | { type: "Forbidden"; reason: string } | { type: "BadRequest"; field: string; message: string } | { type: "Internal" }"#
+            r#"{ NotFound: { resource: string } } | "Unauthorized" | { Forbidden: { reason: string } } | { BadRequest: { field: string; message: string } } | "Internal""#
         );
     }
 
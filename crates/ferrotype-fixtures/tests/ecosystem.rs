@@ -0,0 +1,170 @@
+//! Fixture tests for the ecosystem crate features of ferro-type
+//!
+//! Each test checks the TypeScript rendering of a crate's types against
+//! the JSON that crate's serde impls actually produce.
+
+use ferro_type::TS;
+use serde::Serialize;
+
+/// Asserts that `T` renders as `ts` and that `value` serializes as `json`
+fn assert_ts<T: TS + Serialize>(value: T, ts: &str, json: &str) {
+    assert_eq!(T::typescript().render(), ts);
+    assert_eq!(serde_json::to_string(&value).expect("serialize"), json);
+}
+
+#[test]
+fn test_chrono() {
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let time = chrono::NaiveTime::from_hms_opt(12, 30, 0).unwrap();
+    assert_ts(
+        chrono::DateTime::from_timestamp(0, 0).unwrap(),
+        "string",
+        r#""1970-01-01T00:00:00Z""#,
+    );
+    assert_ts(
+        chrono::DateTime::parse_from_rfc3339("2024-03-01T12:30:00+02:00").unwrap(),
+        "string",
+        r#""2024-03-01T12:30:00+02:00""#,
+    );
+    assert_ts(date, "string", r#""2024-03-01""#);
+    assert_ts(time, "string", r#""12:30:00""#);
+    assert_ts(date.and_time(time), "string", r#""2024-03-01T12:30:00""#);
+    assert_ts(chrono::TimeDelta::new(5, 30).unwrap(), "[number, number]", "[5,30]");
+    assert_ts(
+        chrono::Weekday::Wed,
+        r#""Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun""#,
+        r#""Wed""#,
+    );
+    assert_eq!(serde_json::to_string(&chrono::Month::March).unwrap(), r#""March""#);
+    assert!(chrono::Month::typescript().render().contains(r#""March""#));
+}
+
+#[cfg(not(feature = "time-human-readable"))]
+#[test]
+fn test_time() {
+    let date = time::Date::from_calendar_date(2024, time::Month::March, 1).unwrap();
+    let time = time::Time::from_hms(12, 30, 0).unwrap();
+    let offset = time::UtcOffset::from_hms(2, 0, 0).unwrap();
+    assert_ts(date, "[number, number]", "[2024,61]");
+    assert_ts(time, "[number, number, number, number]", "[12,30,0,0]");
+    assert_ts(
+        time::PrimitiveDateTime::new(date, time),
+        "[number, number, number, number, number, number]",
+        "[2024,61,12,30,0,0]",
+    );
+    assert_ts(
+        time::PrimitiveDateTime::new(date, time).assume_offset(offset),
+        "[number, number, number, number, number, number, number, number, number]",
+        "[2024,61,12,30,0,0,2,0,0]",
+    );
+    assert_ts(offset, "[number, number, number]", "[2,0,0]");
+    assert_ts(time::Duration::new(5, 30), "[number, number]", "[5,30]");
+    assert_ts(time::Weekday::Wednesday, "number", "3");
+    assert_ts(time::Month::March, "number", "3");
+}
+
+#[cfg(feature = "time-human-readable")]
+#[test]
+fn test_time_human_readable() {
+    let date = time::Date::from_calendar_date(2024, time::Month::March, 1).unwrap();
+    let time = time::Time::from_hms(12, 30, 0).unwrap();
+    let offset = time::UtcOffset::from_hms(2, 0, 0).unwrap();
+    assert_ts(date, "string", r#""2024-03-01""#);
+    assert_ts(time, "string", r#""12:30:00.0""#);
+    assert_ts(time::PrimitiveDateTime::new(date, time), "string", r#""2024-03-01 12:30:00.0""#);
+    assert_ts(
+        time::PrimitiveDateTime::new(date, time).assume_offset(offset),
+        "string",
+        r#""2024-03-01 12:30:00.0 +02:00:00""#,
+    );
+    assert_ts(offset, "string", r#""+02:00:00""#);
+    assert_ts(time::Duration::new(5, 30), "string", r#""5.000000030""#);
+    assert_ts(
+        time::Weekday::Wednesday,
+        r#""Monday" | "Tuesday" | "Wednesday" | "Thursday" | "Friday" | "Saturday" | "Sunday""#,
+        r#""Wednesday""#,
+    );
+    assert_eq!(serde_json::to_string(&time::Month::March).unwrap(), r#""March""#);
+    assert!(time::Month::typescript().render().contains(r#""March""#));
+}
+
+#[test]
+fn test_uuid() {
+    assert_ts(uuid::Uuid::nil(), "string", r#""00000000-0000-0000-0000-000000000000""#);
+}
+
+#[test]
+fn test_url() {
+    assert_ts(
+        url::Url::parse("https://example.com/a?b=c").unwrap(),
+        "string",
+        r#""https://example.com/a?b=c""#,
+    );
+}
+
+#[test]
+fn test_semver() {
+    assert_ts(semver::Version::new(1, 2, 3), "string", r#""1.2.3""#);
+    assert_ts(semver::VersionReq::parse("^1.2").unwrap(), "string", r#""^1.2""#);
+}
+
+#[test]
+fn test_rust_decimal() {
+    assert_ts(rust_decimal::Decimal::new(12345, 2), "string", r#""123.45""#);
+}
+
+#[test]
+fn test_bigdecimal() {
+    let value: bigdecimal::BigDecimal = "123.45".parse().unwrap();
+    assert_ts(value, "string", r#""123.45""#);
+}
+
+#[test]
+fn test_serde_json() {
+    let value = serde_json::json!({ "a": [1, "two", null, true] });
+    assert_eq!(serde_json::Value::typescript().render(), "JsonValue");
+    assert_eq!(
+        serde_json::Value::typescript().render_declaration(),
+        "type JsonValue = string | number | boolean | null | JsonValue[] | Record<string, JsonValue>;"
+    );
+    assert_ts(value.clone(), "JsonValue", r#"{"a":[1,"two",null,true]}"#);
+    assert_ts(
+        value.as_object().unwrap().clone(),
+        "Record<string, JsonValue>",
+        r#"{"a":[1,"two",null,true]}"#,
+    );
+    assert_ts(serde_json::Number::from(7), "number", "7");
+}
+
+#[test]
+fn test_bytes() {
+    assert_ts(bytes::Bytes::from_static(b"hi"), "number[]", "[104,105]");
+    assert_ts(bytes::BytesMut::from(&b"hi"[..]), "number[]", "[104,105]");
+}
+
+#[test]
+fn test_indexmap() {
+    let map: indexmap::IndexMap<String, u32> = [("b".to_string(), 2), ("a".to_string(), 1)].into();
+    let set: indexmap::IndexSet<bool> = [true, false].into();
+    assert_ts(map, "Record<string, number>", r#"{"b":2,"a":1}"#);
+    assert_ts(set, "boolean[]", "[true,false]");
+}
+
+#[test]
+fn test_smallvec() {
+    let value: smallvec::SmallVec<[u8; 4]> = smallvec::smallvec![1, 2];
+    assert_ts(value, "number[]", "[1,2]");
+}
+
+#[test]
+fn test_ordered_float() {
+    assert_ts(ordered_float::OrderedFloat(1.5f64), "number", "1.5");
+    assert_ts(ordered_float::NotNan::new(2.5f32).unwrap(), "number", "2.5");
+}
+
+#[test]
+fn test_either() {
+    type Value = either::Either<u8, String>;
+    assert_ts::<Value>(either::Either::Left(1), "{ Left: number } | { Right: string }", r#"{"Left":1}"#);
+    assert_eq!(serde_json::to_string(&Value::Right("x".into())).unwrap(), r#"{"Right":"x"}"#);
+}
@@ -0,0 +1,194 @@
+//! Line-based unified diffs for check mode
+
+/// Lines of context around each change
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Renders the changes from `old` to `new` as a unified diff
+///
+/// Returns an empty string if the texts are equal.
+pub(crate) fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let changes: Vec<usize> = (0..ops.len()).filter(|&i| ops[i] != Op::Equal).collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut output = format!("--- {}\n+++ {}\n", old_name, new_name);
+
+    // Group changes whose context would overlap into one hunk
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for &i in &changes {
+        match groups.last_mut() {
+            Some((_, last)) if i - *last <= 2 * CONTEXT => *last = i,
+            _ => groups.push((i, i)),
+        }
+    }
+
+    for (first, last) in groups {
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + 1 + CONTEXT).min(ops.len());
+
+        // Line numbers at the start of the hunk
+        let (mut old_line, mut new_line) = (0, 0);
+        for op in &ops[..start] {
+            match op {
+                Op::Equal => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+                Op::Delete => old_line += 1,
+                Op::Insert => new_line += 1,
+            }
+        }
+
+        let mut body = String::new();
+        let (mut old_len, mut new_len) = (0, 0);
+        for op in &ops[start..end] {
+            let (prefix, line) = match op {
+                Op::Equal => {
+                    old_len += 1;
+                    new_len += 1;
+                    (' ', old_lines[old_line + old_len - 1])
+                }
+                Op::Delete => {
+                    old_len += 1;
+                    ('-', old_lines[old_line + old_len - 1])
+                }
+                Op::Insert => {
+                    new_len += 1;
+                    ('+', new_lines[new_line + new_len - 1])
+                }
+            };
+            body.push(prefix);
+            body.push_str(line);
+            if !line.ends_with('\n') {
+                body.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_line, old_len),
+            hunk_range(new_line, new_len)
+        ));
+        output.push_str(&body);
+    }
+
+    output
+}
+
+/// Formats a hunk range the way `diff -u` does
+fn hunk_range(before: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", before),
+        1 => format!("{}", before + 1),
+        _ => format!("{},{}", before + 1, len),
+    }
+}
+
+/// Computes a shortest edit script between two sequences of lines
+///
+/// Common leading and trailing lines are matched directly; the rest is
+/// aligned by longest common subsequence.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the LCS length of old_mid[i..] and new_mid[j..]
+    let width = new_mid.len() + 1;
+    let mut lcs = vec![0u32; (old_mid.len() + 1) * width];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = vec![Op::Equal; prefix];
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() && j < new_mid.len() {
+        if old_mid[i] == new_mid[j] {
+            ops.push(Op::Equal);
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            ops.push(Op::Delete);
+            i += 1;
+        } else {
+            ops.push(Op::Insert);
+            j += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(Op::Delete, old_mid.len() - i));
+    ops.extend(std::iter::repeat_n(Op::Insert, new_mid.len() - j));
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_texts() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "a", "b"), "");
+    }
+
+    #[test]
+    fn test_single_change() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n";
+        assert_eq!(
+            unified_diff(old, new, "a/types.ts", "b/types.ts"),
+            "--- a/types.ts\n+++ b/types.ts\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn test_separate_hunks() {
+        let old: String = (1..=20).map(|i| format!("{}\n", i)).collect();
+        let new: String = (1..=20)
+            .filter(|&i| i != 19)
+            .map(|i| if i == 2 { "two\n".to_string() } else { format!("{}\n", i) })
+            .collect();
+        assert_eq!(
+            unified_diff(&old, &new, "a", "b"),
+            "--- a\n+++ b\n\
+             @@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n\
+             @@ -16,5 +16,4 @@\n 16\n 17\n 18\n-19\n 20\n"
+        );
+    }
+
+    #[test]
+    fn test_added_to_empty_file() {
+        assert_eq!(unified_diff("", "a\n", "a", "b"), "--- a\n+++ b\n@@ -0,0 +1 @@\n+a\n");
+    }
+
+    #[test]
+    fn test_missing_trailing_newline() {
+        assert_eq!(
+            unified_diff("a\nb", "a\nb\n", "a", "b"),
+            "--- a\n+++ b\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n"
+        );
+    }
+}
@@ -0,0 +1,348 @@
+//! Runtime type guards for generated types
+
+use crate::zod::{discriminant, template_regex};
+use ferro_type::{Field, Literal, Primitive, TypeDef, TypeRegistry};
+
+/// The helper every guard of an object type narrows `unknown` with
+pub(crate) const IS_PLAIN_OBJECT: &str = "function isPlainObject(value: unknown): value is Record<string, unknown> {
+    return typeof value === \"object\" && value !== null && !Array.isArray(value);
+}";
+
+/// The name of the type guard for the type `name`
+///
+/// Namespaces are joined into the name, so `VM.Git.State` is checked by
+/// `isVMGitState`.
+pub fn guard_name(name: &str) -> String {
+    format!("is{}", name.replace('.', ""))
+}
+
+/// Renders type guards checking values against registered types
+pub(crate) struct Guards<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Guards<'a> {
+    pub(crate) fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Renders the guard of a registered type, such as
+    /// `function isUser(value: unknown): value is User { ... }`
+    ///
+    /// A generic type's guard takes a guard for each type parameter:
+    /// `isPage<T>(value: unknown, isT: (value: unknown) => value is T)`.
+    /// With `ambient`, only the function's signature is declared.
+    pub(crate) fn render(&self, name: &str, export: bool, ambient: bool) -> Option<String> {
+        let typedef = self.registry.get(name)?;
+        let (signature, body) = match typedef {
+            TypeDef::Named { def, .. } => (
+                format!("{}(value: unknown): value is {}", guard_name(name), name),
+                self.check(def, "value", 0),
+            ),
+            TypeDef::GenericDef { type_params, def, .. } => {
+                let params: Vec<_> = type_params.iter().map(|p| p.name.as_str()).collect();
+                let guards: Vec<_> = params
+                    .iter()
+                    .map(|p| format!(", is{0}: (value: unknown) => value is {0}", p))
+                    .collect();
+                (
+                    format!(
+                        "{}<{}>(value: unknown{}): value is {}<{}>",
+                        guard_name(name),
+                        params.join(", "),
+                        guards.concat(),
+                        name,
+                        params.join(", ")
+                    ),
+                    self.check(def, "value", 0),
+                )
+            }
+            _ => return None,
+        };
+
+        let export = if export { "export " } else { "" };
+        Some(if ambient {
+            format!("{}declare function {};", export, signature)
+        } else {
+            format!("{}function {} {{\n    return {};\n}}", export, signature, body)
+        })
+    }
+
+    /// Renders a boolean expression checking that `expr` is a `typedef`
+    ///
+    /// Checks are joined with `&&`, so a union's check is parenthesized.
+    /// `depth` numbers the parameters of nested callbacks, so that each has
+    /// a distinct name.
+    fn check(&self, typedef: &TypeDef, expr: &str, depth: usize) -> String {
+        match typedef {
+            TypeDef::Primitive(p) => primitive(*p, expr),
+            TypeDef::Literal(lit) => format!("{} === {}", expr, lit.render()),
+            TypeDef::Array(inner) => {
+                let item = format!("v{}", depth);
+                format!(
+                    "Array.isArray({0}) && {0}.every(({1}) => {2})",
+                    expr,
+                    item,
+                    self.check(inner, &item, depth + 1)
+                )
+            }
+            TypeDef::Tuple(items) => {
+                let mut checks = vec![
+                    format!("Array.isArray({})", expr),
+                    format!("{}.length === {}", expr, items.len()),
+                ];
+                for (i, item) in items.iter().enumerate() {
+                    checks.push(self.check(item, &format!("{}[{}]", expr, i), depth));
+                }
+                checks.join(" && ")
+            }
+            TypeDef::Object(fields) => self.object(fields, expr, None, depth),
+            TypeDef::Union(variants) => self.union(variants, expr, depth),
+            TypeDef::Intersection(types) => {
+                let checks: Vec<_> = types.iter().map(|t| self.check(t, expr, depth)).collect();
+                checks.join(" && ")
+            }
+            TypeDef::Record { key, value } => {
+                let (k, v) = (format!("k{}", depth), format!("v{}", depth));
+                let check = self.check(value, &v, depth + 1);
+                if matches!(key.as_ref(), TypeDef::Primitive(Primitive::String | Primitive::Number)) {
+                    format!("isPlainObject({0}) && Object.values({0}).every(({1}) => {2})", expr, v, check)
+                } else {
+                    format!(
+                        "isPlainObject({0}) && Object.entries({0}).every(([{1}, {2}]) => {3} && {4})",
+                        expr,
+                        k,
+                        v,
+                        self.check(key, &k, depth + 1),
+                        check
+                    )
+                }
+            }
+            TypeDef::Named { namespace, name, .. } => {
+                let mut qualified = namespace.clone();
+                qualified.push(name.clone());
+                self.reference(&qualified.join("."), expr)
+            }
+            TypeDef::Ref(name) => self.reference(name, expr),
+            TypeDef::Generic { base, args, .. } => match self.registry.get(base) {
+                Some(TypeDef::GenericDef { .. }) => {
+                    let mut call = format!("{}({}", guard_name(base), expr);
+                    for arg in args {
+                        let param = format!("v{}", depth);
+                        call.push_str(&format!(
+                            ", ({0}: unknown): {0} is {1} => {2}",
+                            param,
+                            arg.render(),
+                            self.check(arg, &param, depth + 1)
+                        ));
+                    }
+                    call.push(')');
+                    call
+                }
+                _ => match (base.as_str(), args.as_slice()) {
+                    ("Prettify" | "Readonly", [inner]) => self.check(inner, expr, depth),
+                    _ => "true".to_string(),
+                },
+            },
+            TypeDef::TemplateLiteral { strings, types } => format!(
+                "typeof {0} === \"string\" && /{1}/.test({0})",
+                expr,
+                template_regex(strings, types).replace('/', "\\/")
+            ),
+            TypeDef::TypeParamRef(name) => format!("is{}({})", name, expr),
+            TypeDef::Enum { members, .. } => {
+                let values: Vec<_> = members.iter().map(|m| TypeDef::Literal(m.value.clone())).collect();
+                self.union(&values, expr, depth)
+            }
+            TypeDef::LargeInt { width, policy } => self.check(&width.repr(*policy), expr, depth),
+            TypeDef::Function { .. } => format!("typeof {} === \"function\"", expr),
+            TypeDef::IndexedAccess { .. } | TypeDef::GenericDef { .. } => "true".to_string(),
+        }
+    }
+
+    /// Renders the check of an object type, checking `first` before the
+    /// other fields
+    fn object(&self, fields: &[Field], expr: &str, first: Option<&str>, depth: usize) -> String {
+        let mut fields: Vec<_> = fields.iter().collect();
+        if let Some(first) = first {
+            fields.sort_by_key(|f| f.name != first);
+        }
+        let mut checks = vec![format!("isPlainObject({})", expr)];
+        for field in fields {
+            let value = format!("{}[{}]", expr, Literal::String(field.name.clone()).render());
+            let check = self.check(&field.ty, &value, depth);
+            if field.optional {
+                checks.push(format!("({} === undefined || {})", value, check));
+            } else {
+                checks.push(check);
+            }
+        }
+        checks.join(" && ")
+    }
+
+    /// Renders the check of a union
+    ///
+    /// The variants of a discriminated union check their tag first, so that
+    /// only the matching variant's other fields are checked.
+    fn union(&self, variants: &[TypeDef], expr: &str, depth: usize) -> String {
+        if variants.is_empty() {
+            return "false".to_string();
+        }
+        let tag = discriminant(variants);
+        let checks: Vec<_> = variants
+            .iter()
+            .map(|variant| match (variant, tag) {
+                (TypeDef::Object(fields), Some(tag)) => self.object(fields, expr, Some(tag), depth),
+                _ => self.check(variant, expr, depth),
+            })
+            .collect();
+        if let [single] = checks.as_slice() {
+            return single.clone();
+        }
+        format!("({})", checks.join(" || "))
+    }
+
+    /// Calls the guard of a registered type, or accepts any value of an
+    /// unknown one
+    fn reference(&self, name: &str, expr: &str) -> String {
+        if self.registry.get(name).is_some() {
+            format!("{}({})", guard_name(name), expr)
+        } else {
+            "true".to_string()
+        }
+    }
+}
+
+fn primitive(p: Primitive, expr: &str) -> String {
+    match p {
+        Primitive::String => format!("typeof {} === \"string\"", expr),
+        Primitive::Number => format!("typeof {} === \"number\"", expr),
+        Primitive::Boolean => format!("typeof {} === \"boolean\"", expr),
+        Primitive::BigInt => format!("typeof {} === \"bigint\"", expr),
+        Primitive::Null => format!("{} === null", expr),
+        Primitive::Undefined | Primitive::Void => format!("{} === undefined", expr),
+        Primitive::Never => "false".to_string(),
+        Primitive::Any | Primitive::Unknown => "true".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferro_type::TypeParam;
+
+    fn named(name: &str, def: TypeDef) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(def),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    fn string() -> TypeDef {
+        TypeDef::Primitive(Primitive::String)
+    }
+
+    fn guard(typedef: TypeDef, name: &str) -> String {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(typedef);
+        Guards::new(&registry).render(name, true, false).unwrap()
+    }
+
+    #[test]
+    fn test_object_guard() {
+        let user = named(
+            "User",
+            TypeDef::Object(vec![
+                Field::new("id", TypeDef::Primitive(Primitive::Number)),
+                Field::optional("nickname", TypeDef::Union(vec![string(), TypeDef::Primitive(Primitive::Null)])),
+                Field::new("tags", TypeDef::Array(Box::new(string()))),
+                Field::new("scores", TypeDef::Record {
+                    key: Box::new(string()),
+                    value: Box::new(TypeDef::Tuple(vec![string(), TypeDef::Primitive(Primitive::Boolean)])),
+                }),
+            ]),
+        );
+        let post = named("Post", TypeDef::Object(vec![Field::new("author", user.clone())]));
+
+        assert_eq!(
+            guard(user, "User"),
+            "export function isUser(value: unknown): value is User {\n    return isPlainObject(value) \
+             && typeof value[\"id\"] === \"number\" \
+             && (value[\"nickname\"] === undefined || (typeof value[\"nickname\"] === \"string\" || value[\"nickname\"] === null)) \
+             && Array.isArray(value[\"tags\"]) && value[\"tags\"].every((v0) => typeof v0 === \"string\") \
+             && isPlainObject(value[\"scores\"]) && Object.values(value[\"scores\"]).every((v0) => \
+             Array.isArray(v0) && v0.length === 2 && typeof v0[0] === \"string\" && typeof v0[1] === \"boolean\");\n}"
+        );
+        assert_eq!(
+            guard(post, "Post"),
+            "export function isPost(value: unknown): value is Post {\n    return isPlainObject(value) && isUser(value[\"author\"]);\n}"
+        );
+    }
+
+    #[test]
+    fn test_discriminated_union_guard() {
+        let variant = |kind: &str, field: &str| {
+            TypeDef::Object(vec![
+                Field::new(field, TypeDef::Primitive(Primitive::Number)),
+                Field::new("type", TypeDef::Literal(Literal::String(kind.into()))),
+            ])
+        };
+        let shape = named("Shape", TypeDef::Union(vec![variant("circle", "radius"), variant("square", "side")]));
+        assert_eq!(
+            guard(shape, "Shape"),
+            "export function isShape(value: unknown): value is Shape {\n    return (\
+             isPlainObject(value) && value[\"type\"] === \"circle\" && typeof value[\"radius\"] === \"number\" || \
+             isPlainObject(value) && value[\"type\"] === \"square\" && typeof value[\"side\"] === \"number\");\n}"
+        );
+    }
+
+    #[test]
+    fn test_generic_and_template_literal_guards() {
+        let page = TypeDef::Generic {
+            base: "Page".into(),
+            args: vec![string()],
+            def: Some(Box::new(TypeDef::GenericDef {
+                name: "Page".into(),
+                type_params: vec![TypeParam::new("T")],
+                def: Box::new(TypeDef::Array(Box::new(TypeDef::TypeParamRef("T".into())))),
+                module: None,
+                docs: None,
+                declaration_style: None,
+            })),
+        };
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named("Names", page));
+        registry.add_typedef(named(
+            "VmId",
+            TypeDef::TemplateLiteral {
+                strings: vec!["vm/".into(), "".into()],
+                types: vec![Box::new(TypeDef::Primitive(Primitive::Number))],
+            },
+        ));
+        let guards = Guards::new(&registry);
+
+        assert_eq!(
+            guards.render("Page", false, false).unwrap(),
+            "function isPage<T>(value: unknown, isT: (value: unknown) => value is T): value is Page<T> {\n    \
+             return Array.isArray(value) && value.every((v0) => isT(v0));\n}"
+        );
+        assert_eq!(
+            guards.render("Names", false, false).unwrap(),
+            "function isNames(value: unknown): value is Names {\n    \
+             return isPage(value, (v0: unknown): v0 is string => typeof v0 === \"string\");\n}"
+        );
+        assert_eq!(
+            guards.render("VmId", true, true).unwrap(),
+            "export declare function isVmId(value: unknown): value is VmId;"
+        );
+        assert!(guards
+            .render("VmId", false, false)
+            .unwrap()
+            .contains(r#"typeof value === "string" && /^vm\/-?\d+(?:\.\d+)?$/.test(value)"#));
+    }
+}
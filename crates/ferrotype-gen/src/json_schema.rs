@@ -0,0 +1,519 @@
+//! JSON Schema (draft 2020-12) generation from a [`TypeRegistry`]
+
+use crate::zod::{discriminant, template_regex};
+use ferro_type::{Docs, IntPolicy, Literal, Primitive, TypeDef, TypeRegistry};
+use serde_json::{json, Map, Value};
+
+/// The `$schema` URI of the generated documents
+pub const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Renders a JSON Schema document defining every type in `registry`
+///
+/// Each named type is defined under `$defs` by its qualified name, and
+/// references between types become `$ref`s, so recursive types need no
+/// special handling. Generic types have no JSON Schema equivalent and are
+/// only defined where they are instantiated, with their arguments inlined.
+///
+/// Object keys are sorted, so the output is stable across runs and suitable
+/// for committing.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<User>();
+/// let schema = render_json_schema(&registry);
+/// // {
+/// //   "$defs": { "User": { "type": "object", "properties": { ... } } },
+/// //   "$schema": "https://json-schema.org/draft/2020-12/schema"
+/// // }
+/// ```
+pub fn render_json_schema(registry: &TypeRegistry) -> Value {
+    let defs = JsonSchema::new(registry, "#/$defs/").definitions();
+    json!({ "$schema": JSON_SCHEMA_DRAFT, "$defs": defs })
+}
+
+/// The `$ref` of a type defined by [`render_json_schema`]
+pub fn json_schema_ref(name: &str) -> String {
+    format!("#/$defs/{}", escape_pointer(name))
+}
+
+/// Escapes a name for use in a JSON Pointer
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Renders JSON Schemas of types, referring to named types by `$ref`
+pub(crate) struct JsonSchema<'a> {
+    registry: &'a TypeRegistry,
+    /// Where named types are defined, such as `#/$defs/`
+    ref_prefix: &'static str,
+    /// Whether discriminated unions carry an OpenAPI `discriminator` object,
+    /// with each variant defined on its own
+    discriminators: bool,
+}
+
+impl<'a> JsonSchema<'a> {
+    pub(crate) fn new(registry: &'a TypeRegistry, ref_prefix: &'static str) -> Self {
+        Self { registry, ref_prefix, discriminators: false }
+    }
+
+    /// Adds an OpenAPI `discriminator` object to named discriminated unions
+    ///
+    /// A discriminator maps tag values to the named schemas of the variants,
+    /// so each variant is defined as `{Name}_{tag value}`. Unions used inline,
+    /// or whose variant names are taken, are left without one.
+    pub(crate) fn discriminators(mut self) -> Self {
+        self.discriminators = true;
+        self
+    }
+
+    /// The schemas of the named types in the registry, by qualified name
+    pub(crate) fn definitions(&self) -> Map<String, Value> {
+        let mut defs = Map::new();
+        for name in self.registry.sorted_types() {
+            if let Some(TypeDef::Named { def, wrapper, docs, .. }) = self.registry.get(name) {
+                if let Some(schema) = self.discriminated(name, def, &mut defs) {
+                    defs.insert(name.to_string(), with_docs(schema, docs.as_ref()));
+                    continue;
+                }
+                let mut schema = self.schema(def, &[]);
+                if wrapper.as_deref().is_some_and(|w| w.contains("Partial<")) {
+                    if let Some(object) = schema.as_object_mut() {
+                        object.remove("required");
+                    }
+                }
+                defs.insert(name.to_string(), with_docs(schema, docs.as_ref()));
+            }
+        }
+        defs
+    }
+
+    /// Renders the named discriminated union `name` as a `oneOf` of its
+    /// variants with a `discriminator`, defining each variant in `defs`
+    ///
+    /// Returns `None` unless discriminators are enabled and `def` is a
+    /// discriminated union whose variants can all be named.
+    fn discriminated(&self, name: &str, def: &TypeDef, defs: &mut Map<String, Value>) -> Option<Value> {
+        let TypeDef::Union(variants) = def else {
+            return None;
+        };
+        let tag = discriminant(variants).filter(|_| self.discriminators)?;
+
+        let mut named = Vec::new();
+        for variant in variants {
+            let TypeDef::Object(fields) = variant else {
+                return None;
+            };
+            let value = match &fields.iter().find(|f| f.name == tag)?.ty {
+                TypeDef::Literal(Literal::String(s)) => s.clone(),
+                TypeDef::Literal(lit) => lit.render(),
+                _ => return None,
+            };
+            let component: String = format!("{}_{}", name, value)
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+                .collect();
+            let taken = self.registry.get(&component).is_some()
+                || defs.contains_key(&component)
+                || named.iter().any(|(_, other, _)| *other == component);
+            if taken {
+                return None;
+            }
+            named.push((value, component, variant));
+        }
+
+        let mut one_of = Vec::new();
+        let mut mapping = Map::new();
+        for (value, component, variant) in named {
+            let reference = self.reference(&component);
+            mapping.insert(value, reference["$ref"].clone());
+            one_of.push(reference);
+            defs.insert(component, self.schema(variant, &[]));
+        }
+        Some(json!({ "oneOf": one_of, "discriminator": { "propertyName": tag, "mapping": mapping } }))
+    }
+
+    /// The `$ref` to the named type `name`
+    fn reference(&self, name: &str) -> Value {
+        json!({ "$ref": format!("{}{}", self.ref_prefix, escape_pointer(name)) })
+    }
+
+    /// Renders the schema of a type
+    ///
+    /// `params` binds the type parameters of the generic type being inlined
+    /// to the schemas of its arguments.
+    pub(crate) fn schema(&self, typedef: &TypeDef, params: &[(&str, Value)]) -> Value {
+        match typedef {
+            TypeDef::Primitive(p) => primitive(*p),
+            TypeDef::Array(inner) => json!({ "type": "array", "items": self.schema(inner, params) }),
+            TypeDef::Tuple(items) => {
+                let items: Vec<_> = items.iter().map(|t| self.schema(t, params)).collect();
+                json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "items": false,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                })
+            }
+            TypeDef::Object(fields) => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for field in fields {
+                    let mut schema = with_docs(self.schema(&field.ty, params), field.docs.as_ref());
+                    if field.readonly {
+                        insert(&mut schema, "readOnly", Value::Bool(true));
+                    }
+                    properties.insert(field.name.clone(), schema);
+                    if !field.optional {
+                        required.push(Value::String(field.name.clone()));
+                    }
+                }
+                let mut schema = json!({ "type": "object", "properties": properties });
+                if !required.is_empty() {
+                    insert(&mut schema, "required", Value::Array(required));
+                }
+                schema
+            }
+            TypeDef::Union(variants) => self.union(variants, params),
+            TypeDef::Intersection(types) => {
+                let all: Vec<_> = types.iter().map(|t| self.schema(t, params)).collect();
+                json!({ "allOf": all })
+            }
+            TypeDef::Record { key, value } => {
+                let mut schema = json!({
+                    "type": "object",
+                    "additionalProperties": self.schema(value, params),
+                });
+                let key = self.schema(key, params);
+                if key != json!({ "type": "string" }) {
+                    insert(&mut schema, "propertyNames", key);
+                }
+                schema
+            }
+            TypeDef::Named { namespace, name, .. } => {
+                let mut qualified = namespace.clone();
+                qualified.push(name.clone());
+                self.reference(&qualified.join("."))
+            }
+            TypeDef::Ref(name) => {
+                if self.registry.get(name).is_some() {
+                    self.reference(name)
+                } else {
+                    raw(name)
+                }
+            }
+            TypeDef::Literal(lit) => json!({ "const": literal(lit) }),
+            TypeDef::Generic { base, args, def } => {
+                let args: Vec<_> = args.iter().map(|a| self.schema(a, params)).collect();
+                let generic = def.as_deref().or_else(|| self.registry.get(base));
+                match (generic, args.as_slice()) {
+                    (Some(TypeDef::GenericDef { type_params, def, .. }), _) => {
+                        let bound: Vec<_> = type_params
+                            .iter()
+                            .zip(args)
+                            .map(|(param, arg)| (param.name.as_str(), arg))
+                            .collect();
+                        self.schema(def, &bound)
+                    }
+                    (_, [inner]) if matches!(base.as_str(), "Prettify" | "Readonly") => inner.clone(),
+                    (_, [inner]) if base == "Partial" => {
+                        let mut inner = inner.clone();
+                        if let Some(object) = inner.as_object_mut() {
+                            object.remove("required");
+                        }
+                        inner
+                    }
+                    _ => json!({}),
+                }
+            }
+            TypeDef::IndexedAccess { base, key } => match self.registry.get(base) {
+                Some(TypeDef::Named { def, .. }) => match def.as_ref() {
+                    TypeDef::Object(fields) => fields
+                        .iter()
+                        .find(|f| f.name == *key)
+                        .map(|f| self.schema(&f.ty, params))
+                        .unwrap_or_else(|| json!({})),
+                    _ => json!({}),
+                },
+                _ => json!({}),
+            },
+            TypeDef::TemplateLiteral { strings, types } => {
+                json!({ "type": "string", "pattern": template_regex(strings, types) })
+            }
+            TypeDef::TypeParamRef(name) => params
+                .iter()
+                .find(|(param, _)| param == name)
+                .map(|(_, schema)| schema.clone())
+                .unwrap_or_else(|| json!({})),
+            TypeDef::Enum { members, .. } => {
+                let values: Vec<_> = members.iter().map(|m| literal(&m.value)).collect();
+                json!({ "enum": values })
+            }
+            // Unlike TypeScript, JSON Schema can require a whole number
+            TypeDef::LargeInt { width, policy } => {
+                let integer = json!({ "type": "integer" });
+                let digits = json!({ "type": "string", "pattern": "^-?[0-9]+$" });
+                match policy.unwrap_or(width.default_policy()) {
+                    IntPolicy::Number | IntPolicy::BigInt => integer,
+                    IntPolicy::String => digits,
+                    IntPolicy::NumberOrString => json!({ "anyOf": [integer, digits] }),
+                }
+            }
+            TypeDef::Function { .. } | TypeDef::GenericDef { .. } => json!({}),
+        }
+    }
+
+    /// Renders the schema of a union
+    ///
+    /// `undefined` members are dropped, as they have no JSON representation.
+    /// Literals become an `enum`, and objects sharing a literal tag field a
+    /// `oneOf` whose members each match a single `const` tag.
+    fn union(&self, variants: &[TypeDef], params: &[(&str, Value)]) -> Value {
+        let variants: Vec<_> = variants
+            .iter()
+            .filter(|v| !matches!(v, TypeDef::Primitive(Primitive::Undefined)))
+            .cloned()
+            .collect();
+        if let [single] = variants.as_slice() {
+            return self.schema(single, params);
+        }
+
+        let literals: Option<Vec<_>> = variants
+            .iter()
+            .map(|v| match v {
+                TypeDef::Literal(lit) => Some(literal(lit)),
+                _ => None,
+            })
+            .collect();
+        if let Some(literals) = literals.filter(|l| !l.is_empty()) {
+            return json!({ "enum": literals });
+        }
+
+        let schemas: Vec<_> = variants.iter().map(|v| self.schema(v, params)).collect();
+        if discriminant(&variants).is_some() {
+            json!({ "oneOf": schemas })
+        } else if schemas.is_empty() {
+            Value::Bool(false)
+        } else {
+            json!({ "anyOf": schemas })
+        }
+    }
+}
+
+fn primitive(p: Primitive) -> Value {
+    match p {
+        Primitive::String => json!({ "type": "string" }),
+        Primitive::Number => json!({ "type": "number" }),
+        Primitive::BigInt => json!({ "type": "integer" }),
+        Primitive::Boolean => json!({ "type": "boolean" }),
+        // serde serializes `()` as `null`
+        Primitive::Null | Primitive::Undefined | Primitive::Void => json!({ "type": "null" }),
+        Primitive::Never => Value::Bool(false),
+        Primitive::Any | Primitive::Unknown => json!({}),
+    }
+}
+
+/// The schema of a raw TypeScript type, such as a type override
+///
+/// Only primitive type names are understood; anything else accepts any value.
+fn raw(ts: &str) -> Value {
+    let p = match ts.trim() {
+        "string" => Primitive::String,
+        "number" => Primitive::Number,
+        "bigint" => Primitive::BigInt,
+        "boolean" => Primitive::Boolean,
+        "null" => Primitive::Null,
+        "never" => Primitive::Never,
+        _ => Primitive::Unknown,
+    };
+    primitive(p)
+}
+
+fn literal(lit: &Literal) -> Value {
+    match lit {
+        Literal::String(s) => Value::String(s.clone()),
+        Literal::Boolean(b) => Value::Bool(*b),
+        Literal::Number(n) if n.fract() == 0.0 && n.abs() < 9007199254740992.0 => json!(*n as i64),
+        Literal::Number(n) => json!(n),
+    }
+}
+
+/// Adds `description` and `deprecated` annotations to a schema
+fn with_docs(mut schema: Value, docs: Option<&Docs>) -> Value {
+    if let Some(docs) = docs {
+        if let Some(description) = &docs.description {
+            insert(&mut schema, "description", Value::String(description.clone()));
+        }
+        if docs.deprecated.is_some() {
+            insert(&mut schema, "deprecated", Value::Bool(true));
+        }
+    }
+    schema
+}
+
+/// Sets a keyword of a schema, turning a boolean schema into an object
+fn insert(schema: &mut Value, key: &str, value: Value) {
+    if let Value::Bool(accept) = schema {
+        *schema = if *accept { json!({}) } else { json!({ "not": {} }) };
+    }
+    if let Value::Object(object) = schema {
+        object.insert(key.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferro_type::{EnumMember, EnumStyle, Field, TypeParam};
+
+    fn named(name: &str, def: TypeDef) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(def),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    fn string() -> TypeDef {
+        TypeDef::Primitive(Primitive::String)
+    }
+
+    fn defs(typedef: TypeDef) -> Value {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(typedef);
+        render_json_schema(&registry)["$defs"].clone()
+    }
+
+    #[test]
+    fn test_object_schema() {
+        let user = named(
+            "User",
+            TypeDef::Object(vec![
+                Field::new("id", string()).with_docs(Docs::new("Unique id.")).readonly(),
+                Field::optional("nickname", TypeDef::Union(vec![string(), TypeDef::Primitive(Primitive::Null)])),
+                Field::new("scores", TypeDef::Record {
+                    key: Box::new(string()),
+                    value: Box::new(TypeDef::Tuple(vec![TypeDef::Primitive(Primitive::Number), string()])),
+                }),
+            ]),
+        );
+        let post = named("Post", TypeDef::Object(vec![Field::new("author", user)]));
+
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(post);
+        assert_eq!(
+            render_json_schema(&registry),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$defs": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "string", "description": "Unique id.", "readOnly": true },
+                            "nickname": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                            "scores": {
+                                "type": "object",
+                                "additionalProperties": {
+                                    "type": "array",
+                                    "prefixItems": [{ "type": "number" }, { "type": "string" }],
+                                    "items": false,
+                                    "minItems": 2,
+                                    "maxItems": 2,
+                                },
+                            },
+                        },
+                        "required": ["id", "scores"],
+                    },
+                    "Post": {
+                        "type": "object",
+                        "properties": { "author": { "$ref": "#/$defs/User" } },
+                        "required": ["author"],
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_union_schemas() {
+        let variant = |kind: &str| {
+            TypeDef::Object(vec![Field::new("type", TypeDef::Literal(Literal::String(kind.into())))])
+        };
+        let shape = named("Shape", TypeDef::Union(vec![variant("circle"), variant("square")]));
+        assert_eq!(
+            defs(shape)["Shape"],
+            json!({ "oneOf": [
+                { "type": "object", "properties": { "type": { "const": "circle" } }, "required": ["type"] },
+                { "type": "object", "properties": { "type": { "const": "square" } }, "required": ["type"] },
+            ] })
+        );
+
+        let status = named(
+            "Status",
+            TypeDef::Union(vec![TypeDef::Literal(Literal::String("a".into())), TypeDef::Literal(Literal::Number(1.0))]),
+        );
+        assert_eq!(defs(status)["Status"], json!({ "enum": ["a", 1] }));
+
+        let code = named(
+            "Code",
+            TypeDef::Enum {
+                members: vec![EnumMember::new("A", Literal::Number(1.0)), EnumMember::new("B", Literal::Number(2.5))],
+                style: EnumStyle::Enum,
+            },
+        );
+        assert_eq!(defs(code)["Code"], json!({ "enum": [1, 2.5] }));
+    }
+
+    #[test]
+    fn test_template_literal_and_generic_schemas() {
+        let id = named(
+            "VmId",
+            TypeDef::TemplateLiteral {
+                strings: vec!["vm/".into(), "".into()],
+                types: vec![Box::new(TypeDef::Primitive(Primitive::Number))],
+            },
+        );
+        assert_eq!(defs(id)["VmId"], json!({ "type": "string", "pattern": r"^vm/-?\d+(?:\.\d+)?$" }));
+
+        let page = TypeDef::Generic {
+            base: "Page".into(),
+            args: vec![string()],
+            def: Some(Box::new(TypeDef::GenericDef {
+                name: "Page".into(),
+                type_params: vec![TypeParam::new("T")],
+                def: Box::new(TypeDef::Array(Box::new(TypeDef::TypeParamRef("T".into())))),
+                module: None,
+                docs: None,
+                declaration_style: None,
+            })),
+        };
+        let defs = defs(named("Names", page));
+        assert_eq!(defs["Names"], json!({ "type": "array", "items": { "type": "string" } }));
+        assert!(defs.get("Page").is_none());
+    }
+
+    #[test]
+    fn test_recursive_and_overridden_schemas() {
+        let tree = named(
+            "Tree",
+            TypeDef::Object(vec![Field::new("children", TypeDef::Array(Box::new(TypeDef::Ref("Tree".into()))))]),
+        );
+        let mut registry = TypeRegistry::new();
+        registry.override_type("Timestamp", "string");
+        registry.add_typedef(named("Timestamp", TypeDef::Primitive(Primitive::Number)));
+        registry.add_typedef(tree);
+
+        let schema = render_json_schema(&registry);
+        assert_eq!(schema["$defs"]["Timestamp"], json!({ "type": "string" }));
+        assert_eq!(
+            schema["$defs"]["Tree"]["properties"]["children"]["items"],
+            json!({ "$ref": "#/$defs/Tree" })
+        );
+    }
+}
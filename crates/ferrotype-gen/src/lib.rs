@@ -43,11 +43,41 @@
 //!         .expect("TypeScript generation failed");
 //! }
 //! ```
+//!
+//! # Checking Generated Files
+//!
+//! ```ignore
+//! // tests/typescript.rs
+//! #[test]
+//! fn typescript_is_up_to_date() {
+//!     let mut generator = Generator::new(Config::new().output("../frontend/src/types/api.ts"));
+//!     generator.register::<api::User>();
+//!
+//!     let report = generator.check().unwrap();
+//!     assert!(report.is_ok(), "TypeScript is out of date:\n{}", report);
+//! }
+//! ```
 
-use ferro_type::{TypeDef, TypeRegistry, TS};
-use std::collections::HashMap;
+use ferro_type::{
+    DeclarationOptions, DeclarationStyle, Format, IntPolicy, IntWidth, Shape, TypeDef, TypeRegistry, TS,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
+mod diff;
+mod guards;
+mod json_schema;
+mod load;
+mod openapi;
+mod zod;
+
+pub use guards::guard_name;
+pub use json_schema::{json_schema_ref, render_json_schema, JSON_SCHEMA_DRAFT};
+pub use load::ConfigError;
+pub use openapi::{render_openapi_schemas, Method, OpenApi, Operation, Parameter, OPENAPI_VERSION};
+pub use zod::{render_zod, schema_name};
+
 // ============================================================================
 // UTILITY TYPES
 // ============================================================================
@@ -85,6 +115,25 @@ pub enum ExportStyle {
     Grouped,
 }
 
+/// How barrel `index.ts` files re-export generated modules
+///
+/// Every name a barrel exports must be unique across all modules below it.
+/// The strategies differ in what happens when two modules export the same
+/// name, such as a namespace root declared in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BarrelStrategy {
+    /// Re-export names directly, failing on duplicates (default):
+    /// `export type { User } from "./user";`
+    #[default]
+    Error,
+    /// Re-export names directly, prefixing duplicates with their module path:
+    /// `export type { Id as ModelsUserId } from "./user";`
+    Prefix,
+    /// Re-export each module as a namespace:
+    /// `export * as user from "./user";`
+    Namespace,
+}
+
 /// Configuration for TypeScript generation
 #[derive(Debug, Clone, Default)]
 pub struct Config {
@@ -94,18 +143,80 @@ pub struct Config {
     /// Export style for generated types
     pub export_style: ExportStyle,
 
+    /// How object types are declared, unless a type sets its own style
+    /// with `#[ts(interface)]`
+    pub declaration_style: DeclarationStyle,
+
+    /// Pretty-print declarations over several lines in this format, instead
+    /// of rendering each on one line
+    pub format: Option<Format>,
+
     /// Whether to generate .d.ts (declarations only) vs .ts
+    ///
+    /// Enums and namespaces are emitted as ambient `declare` declarations, so
+    /// the output describes a JavaScript package rather than implementing
+    /// it. In multi-file mode every file is written with a `.d.ts` extension
+    /// and is kept a module with `export {};` when it has no imports or
+    /// exports. A single file with [`ExportStyle::None`] stays a global
+    /// script, declaring its types for every file of the program.
     pub declaration_only: bool,
 
+    /// Output directory for multi-file generation
+    ///
+    /// Only used by config files and tools driving the generator; the
+    /// multi-file methods take the directory as an argument.
+    pub output_dir: Option<PathBuf>,
+
     /// Custom header comment to prepend
+    ///
+    /// Each line becomes a `//` comment. The placeholders `{file}` (the
+    /// output file name, relative to the output directory in multi-file
+    /// mode), `{module}` (the Rust module of a multi-file module file) and
+    /// `{version}` (the ferro-type-gen version) are substituted.
     pub header: Option<String>,
 
-    /// Whether to add ESM-style .js extensions to imports
-    /// (for future multi-file mode)
+    /// Per-module overrides in multi-file mode, keyed by Rust module path
+    pub modules: BTreeMap<String, ModuleConfig>,
+
+    /// TypeScript to declare in place of derived definitions, keyed by type
+    /// name (see [`TypeRegistry::override_type`])
+    pub type_overrides: BTreeMap<String, String>,
+
+    /// Whether to add ESM-style .js extensions to imports between
+    /// generated files
     pub esm_extensions: bool,
 
+    /// Whether multi-file generation fails when generated files import
+    /// each other in a cycle
+    pub deny_import_cycles: bool,
+
+    /// Emit `index.ts` barrel files re-exporting every generated module in
+    /// multi-file mode, using the given strategy
+    pub barrel: Option<BarrelStrategy>,
+
     /// Include common utility types (Prettify, etc.) in the output
     pub include_utilities: bool,
+
+    /// Emit an `isName(value: unknown): value is Name` type guard after
+    /// each type
+    pub type_guards: bool,
+
+    /// How 64-bit integers are represented, unless a field sets its own
+    /// policy with `#[ts(int64 = "...")]`
+    ///
+    /// Without a policy they are `number`, and [`Generator::lossy_integers`]
+    /// reports them.
+    pub int64: Option<IntPolicy>,
+
+    /// How 128-bit integers are represented, unless a field sets its own
+    /// policy with `#[ts(int128 = "...")]`; `bigint` without one
+    pub int128: Option<IntPolicy>,
+
+    /// The side of serialization to generate types for
+    ///
+    /// Without one, a field that may be left out on either side, such as
+    /// with `#[serde(default)]` or `skip_serializing_if`, is optional.
+    pub shape: Option<Shape>,
 }
 
 impl Config {
@@ -126,29 +237,146 @@ impl Config {
         self
     }
 
+    /// Set how object types are declared
+    pub fn declaration_style(mut self, style: DeclarationStyle) -> Self {
+        self.declaration_style = style;
+        self
+    }
+
+    /// Pretty-print declarations in the given format
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
     /// Generate .d.ts declaration file instead of .ts
     pub fn declaration_only(mut self) -> Self {
         self.declaration_only = true;
         self
     }
 
+    /// Set the output directory for multi-file generation
+    pub fn output_dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.output_dir = Some(path.as_ref().to_owned());
+        self
+    }
+
     /// Set a custom header comment
+    ///
+    /// See [`Config::header`] for the placeholders it may contain.
     pub fn header(mut self, header: impl Into<String>) -> Self {
         self.header = Some(header.into());
         self
     }
 
-    /// Enable ESM-style .js extensions in imports (for future multi-file mode)
+    /// Write a module to `path`, relative to the output directory, instead
+    /// of the path derived from its module path
+    pub fn module_output(mut self, module: impl Into<String>, path: impl AsRef<Path>) -> Self {
+        self.modules.entry(module.into()).or_default().output = Some(path.as_ref().to_owned());
+        self
+    }
+
+    /// Use a different header for a module's file than [`Config::header`]
+    pub fn module_header(mut self, module: impl Into<String>, header: impl Into<String>) -> Self {
+        self.modules.entry(module.into()).or_default().header = Some(header.into());
+        self
+    }
+
+    /// Declare the named type `name` as the TypeScript type `ts`
+    pub fn type_override(mut self, name: impl Into<String>, ts: impl Into<String>) -> Self {
+        self.type_overrides.insert(name.into(), ts.into());
+        self
+    }
+
+    /// Enable ESM-style .js extensions in imports between generated files
     pub fn esm_extensions(mut self) -> Self {
         self.esm_extensions = true;
         self
     }
 
+    /// Fail multi-file generation if generated files import each other in a cycle
+    ///
+    /// Type-only import cycles are valid TypeScript, but some bundlers and
+    /// lint rules reject them.
+    pub fn deny_import_cycles(mut self) -> Self {
+        self.deny_import_cycles = true;
+        self
+    }
+
+    /// Emit `index.ts` barrel files in multi-file mode
+    ///
+    /// The output directory and each directory below it get an `index.ts`
+    /// that re-exports the files and subdirectories it contains.
+    pub fn barrel(mut self, strategy: BarrelStrategy) -> Self {
+        self.barrel = Some(strategy);
+        self
+    }
+
     /// Include common utility types (Prettify, etc.) in the generated output
     pub fn include_utilities(mut self) -> Self {
         self.include_utilities = true;
         self
     }
+
+    /// Emit a runtime type guard for each type, such as
+    /// `isUser(value: unknown): value is User`
+    ///
+    /// Guards check values such as parsed JSON against the generated types,
+    /// calling each other for the types they reference. With
+    /// [`Config::declaration_only`], only their signatures are declared.
+    pub fn type_guards(mut self) -> Self {
+        self.type_guards = true;
+        self
+    }
+
+    /// Represent 64-bit integers with the given policy
+    ///
+    /// Setting any policy, including [`IntPolicy::Number`], opts in to it:
+    /// [`Generator::lossy_integers`] only reports integers without one.
+    pub fn int64(mut self, policy: IntPolicy) -> Self {
+        self.int64 = Some(policy);
+        self
+    }
+
+    /// Represent 128-bit integers with the given policy
+    pub fn int128(mut self, policy: IntPolicy) -> Self {
+        self.int128 = Some(policy);
+        self
+    }
+
+    /// Generate types for one side of serialization, such as
+    /// [`Shape::Output`] for a client that only reads responses
+    pub fn shape(mut self, shape: Shape) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+}
+
+/// Overrides for one module in multi-file mode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleConfig {
+    /// File to write the module to, relative to the output directory
+    pub output: Option<PathBuf>,
+    /// Header template replacing [`Config::header`] for this module
+    pub header: Option<String>,
+}
+
+/// A type-only import of names from another generated file.
+///
+/// Renders as `import type { Post, User } from "../models/user";`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// Relative module specifier, e.g. `"../models/user"` or `"./user.js"`
+    pub from: String,
+    /// The imported names, sorted
+    pub names: Vec<String>,
+}
+
+impl Import {
+    /// Renders this import as a TypeScript statement.
+    pub fn render(&self) -> String {
+        format!("import type {{ {} }} from \"{}\";", self.names.join(", "), self.from)
+    }
 }
 
 /// TypeScript file generator
@@ -163,10 +391,22 @@ pub struct Generator {
 impl Generator {
     /// Create a new generator with the given config
     pub fn new(config: Config) -> Self {
-        Self {
-            config,
-            registry: TypeRegistry::new(),
+        let mut registry = TypeRegistry::new();
+        registry.set_declaration_style(config.declaration_style);
+        registry.set_format(config.format);
+        for (name, ts) in &config.type_overrides {
+            registry.override_type(name, ts);
+        }
+        if let Some(policy) = config.int64 {
+            registry.set_int_policy(IntWidth::Bits64, policy);
+        }
+        if let Some(policy) = config.int128 {
+            registry.set_int_policy(IntWidth::Bits128, policy);
+        }
+        if let Some(shape) = config.shape {
+            registry.set_shape(shape);
         }
+        Self { config, registry }
     }
 
     /// Create a new generator with default config
@@ -203,18 +443,8 @@ impl Generator {
 
     /// Generate TypeScript and return as string
     pub fn generate(&self) -> String {
-        let mut output = String::new();
-
-        // Header comment
-        if let Some(ref header) = self.config.header {
-            output.push_str("// ");
-            output.push_str(header);
-            output.push('\n');
-        } else {
-            output.push_str("// Generated by ferro-type-gen\n");
-            output.push_str("// Do not edit manually\n");
-        }
-        output.push('\n');
+        let file = self.config.output.as_ref().and_then(|path| path.file_name()).map(Path::new);
+        let mut output = self.file_header(self.config.header.as_deref(), file, None);
 
         // Utility types (if configured)
         if self.config.include_utilities {
@@ -230,18 +460,38 @@ impl Generator {
         }
 
         // Types in dependency order
+        let declaration_only = self.config.declaration_only;
         match self.config.export_style {
+            ExportStyle::None if declaration_only => {
+                output.push_str(&self.registry.render_ambient());
+            }
             ExportStyle::None => {
                 output.push_str(&self.registry.render());
             }
+            ExportStyle::Named if declaration_only => {
+                output.push_str(&self.registry.render_exported_ambient());
+            }
             ExportStyle::Named => {
                 output.push_str(&self.registry.render_exported());
             }
             ExportStyle::Grouped => {
                 // Render without exports
-                output.push_str(&self.registry.render());
+                if declaration_only {
+                    output.push_str(&self.registry.render_ambient());
+                } else {
+                    output.push_str(&self.registry.render());
+                }
+                let sorted = self.registry.sorted_types();
+                let guards = self.render_guards(&sorted, false);
+                if !guards.is_empty() {
+                    output.push('\n');
+                    output.push_str(&guards);
+                }
                 // Add grouped export at end
-                let names: Vec<_> = self.registry.sorted_types().into_iter().collect();
+                let mut names: Vec<_> = sorted.iter().map(|name| name.to_string()).collect();
+                if self.config.type_guards {
+                    names.extend(sorted.iter().map(|name| guard_name(name)));
+                }
                 if !names.is_empty() {
                     output.push_str("\nexport { ");
                     output.push_str(&names.join(", "));
@@ -249,10 +499,67 @@ impl Generator {
                 }
             }
         }
+        if self.config.export_style != ExportStyle::Grouped {
+            let export = self.config.export_style == ExportStyle::Named;
+            let guards = self.render_guards(&self.registry.sorted_types(), export);
+            if !guards.is_empty() {
+                output.push('\n');
+                output.push_str(&guards);
+            }
+        }
+
+        // An empty module declaration file would otherwise be a global script
+        if declaration_only
+            && self.config.export_style != ExportStyle::None
+            && self.registry.is_empty()
+        {
+            output.push_str("\nexport {};\n");
+        }
+
+        output
+    }
+
+    /// Type guards for the types `names`, preceded by the helper they share
+    ///
+    /// Returns an empty string unless [`Config::type_guards`] is set, and
+    /// otherwise ends with a newline.
+    fn render_guards(&self, names: &[&str], export: bool) -> String {
+        if !self.config.type_guards {
+            return String::new();
+        }
+        let renderer = guards::Guards::new(&self.registry);
+        let mut rendered: Vec<_> = names
+            .iter()
+            .filter_map(|name| renderer.render(name, export, self.config.declaration_only))
+            .collect();
+        if rendered.iter().any(|guard| guard.contains("isPlainObject(")) {
+            rendered.insert(0, guards::IS_PLAIN_OBJECT.to_string());
+        }
+        if rendered.is_empty() {
+            return String::new();
+        }
+        rendered.join("\n\n") + "\n"
+    }
 
+    /// Generate Zod schemas for the registered types and return as string
+    ///
+    /// See [`render_zod`] for how each type is checked.
+    pub fn generate_zod(&self) -> String {
+        let file = self.config.output.as_ref().and_then(|path| path.file_name()).map(Path::new);
+        let mut output = self.file_header(self.config.header.as_deref(), file, None);
+        output.push_str(&render_zod(&self.registry));
         output
     }
 
+    /// Generate a JSON Schema document for the registered types
+    ///
+    /// The document is pretty-printed with sorted keys; see
+    /// [`render_json_schema`] for how each type is defined.
+    pub fn generate_json_schema(&self) -> String {
+        let schema = render_json_schema(&self.registry);
+        serde_json::to_string_pretty(&schema).expect("JSON Schema serialization failed") + "\n"
+    }
+
     /// Generate TypeScript to the configured output file
     ///
     /// # Errors
@@ -315,6 +622,68 @@ impl Generator {
         Ok(true) // Changed
     }
 
+    /// Compare the generated output with the file on disk, without writing
+    ///
+    /// Useful in tests or CI to assert that committed TypeScript is up to
+    /// date with the Rust types.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no output path is configured or the file cannot
+    /// be read.
+    pub fn check(&self) -> std::io::Result<CheckReport> {
+        let output_path = self
+            .config
+            .output
+            .as_ref()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "No output path configured"))?;
+
+        let mut report = CheckReport::default();
+        report.compare(output_path.clone(), output_path, self.generate())?;
+        Ok(report)
+    }
+
+    /// Where 64-bit integers are rendered as `number` without opting in
+    ///
+    /// A JavaScript `number` only holds integers exactly up to
+    /// `Number.MAX_SAFE_INTEGER`, so IDs and other large values silently lose
+    /// precision. Each location is the type name followed by the field path,
+    /// such as `User.id`, in dependency order. Setting [`Config::int64`] or
+    /// `#[ts(int64 = "...")]` on the field, even to `"number"`, silences it.
+    ///
+    /// Map keys are not reported, as serde_json writes them as strings.
+    pub fn lossy_integers(&self) -> Vec<String> {
+        self.find(false, |typedef| matches!(typedef, TypeDef::LargeInt { width: IntWidth::Bits64, policy: None }))
+    }
+
+    /// Where maps are keyed by types serde_json can't write as JSON object
+    /// keys, such as structs and tuples
+    ///
+    /// Serializing such a map fails at runtime with "key must be a string".
+    /// Locations are reported like [`lossy_integers`](Self::lossy_integers).
+    pub fn invalid_map_keys(&self) -> Vec<String> {
+        self.find(true, |typedef| matches!(typedef, TypeDef::Record { key, .. } if !key.is_json_key()))
+    }
+
+    /// The locations of the types matching `predicate` in the definitions of
+    /// the registered types, as `Type.field` paths in dependency order
+    ///
+    /// The key types of maps are only searched if `keys` is set.
+    fn find(&self, keys: bool, predicate: impl Fn(&TypeDef) -> bool) -> Vec<String> {
+        let mut found = Vec::new();
+        for name in self.registry.sorted_types() {
+            if let Some(TypeDef::Named { def, .. } | TypeDef::GenericDef { def, .. }) = self.registry.get(name) {
+                visit_types(def, name, keys, &mut |typedef, path| {
+                    if predicate(typedef) {
+                        found.push(path.to_string());
+                    }
+                });
+            }
+        }
+        found.dedup();
+        found
+    }
+
     // ========================================================================
     // MULTI-FILE GENERATION
     // ========================================================================
@@ -327,27 +696,31 @@ impl Generator {
         let mut result: HashMap<String, Vec<String>> = HashMap::new();
 
         for name in self.registry.type_names() {
-            if let Some(typedef) = self.registry.get(name) {
-                let module = match typedef {
-                    TypeDef::Named { module, .. } => {
-                        module.clone().unwrap_or_else(|| "default".to_string())
-                    }
-                    _ => "default".to_string(),
-                };
-                result.entry(module).or_default().push(name.to_string());
-            }
+            result.entry(self.module_of(name)).or_default().push(name.to_string());
         }
 
         result
     }
 
+    /// Returns the module a registered type is generated into.
+    fn module_of(&self, name: &str) -> String {
+        match self.registry.get(name) {
+            Some(TypeDef::Named { module: Some(module), .. } | TypeDef::GenericDef { module: Some(module), .. }) => {
+                module.clone()
+            }
+            _ => "default".to_string(),
+        }
+    }
+
     /// Convert a module path to a file path
     ///
     /// For example:
     /// - `my_crate::models::user` -> `models/user.ts`
     /// - `my_crate::api::requests` -> `api/requests.ts`
     ///
-    /// The crate name is stripped from the beginning.
+    /// The crate name is stripped from the beginning. With
+    /// [`Config::declaration_only`], [`file_for_module`](Self::file_for_module)
+    /// turns the extension into `.d.ts`.
     pub fn module_to_path(module: &str) -> PathBuf {
         // Split by :: and skip the crate name (first segment)
         let parts: Vec<&str> = module.split("::").collect();
@@ -365,25 +738,210 @@ impl Generator {
         path
     }
 
-    /// Generate TypeScript content for a specific module
+    /// Convert a module path to the file it is written to, relative to the
+    /// output directory
     ///
-    /// Only includes types from the specified module.
-    pub fn generate_for_module(&self, module: &str, type_names: &[String]) -> String {
-        let mut output = String::new();
+    /// Like [`module_to_path`](Self::module_to_path), except that types
+    /// without a module (`"default"`) go to `types.ts`.
+    pub fn module_file(module: &str) -> PathBuf {
+        if module == "default" {
+            PathBuf::from("types.ts")
+        } else {
+            Self::module_to_path(module)
+        }
+    }
 
-        // Header comment
-        if let Some(ref header) = self.config.header {
-            output.push_str("// ");
-            output.push_str(header);
-            output.push('\n');
+    /// The file a module is written to in multi-file mode, relative to the
+    /// output directory
+    ///
+    /// This is [`module_file`](Self::module_file) unless the module's output
+    /// is overridden in [`Config::modules`], with a `.d.ts` extension if
+    /// [`Config::declaration_only`] is set.
+    pub fn file_for_module(&self, module: &str) -> PathBuf {
+        let file = match self.config.modules.get(module).and_then(|m| m.output.as_ref()) {
+            Some(output) => output.clone(),
+            None => Self::module_file(module),
+        };
+        if self.config.declaration_only {
+            let mut file = strip_ts_extension(&file).into_os_string();
+            file.push(".d.ts");
+            PathBuf::from(file)
+        } else {
+            file
+        }
+    }
+
+    /// The file name of barrels: `index.ts`, or `index.d.ts` with
+    /// [`Config::declaration_only`]
+    fn barrel_file(&self) -> &'static str {
+        if self.config.declaration_only {
+            "index.d.ts"
+        } else {
+            "index.ts"
+        }
+    }
+
+    /// Compute the imports a module's file needs from other generated files
+    ///
+    /// Every type in `type_names` that references a type generated into a
+    /// different module imports it from that module's file. A namespaced
+    /// type such as `VM.Git.State` is reached through its root namespace, so
+    /// `VM` is imported. Imports are sorted by specifier.
+    pub fn imports_for_module(&self, module: &str, type_names: &[String]) -> Vec<Import> {
+        self.dependencies_by_module(module, type_names, |dep| {
+            dep.split('.').next().unwrap_or(dep).to_string()
+        })
+    }
+
+    /// The type guards a module's file imports from other generated files
+    ///
+    /// Unlike types, guards are imported as values, by their own name.
+    fn guard_imports_for_module(&self, module: &str, type_names: &[String]) -> Vec<Import> {
+        self.dependencies_by_module(module, type_names, guard_name)
+    }
+
+    /// Group the names of the types `type_names` reference from other
+    /// modules by the file they are imported from
+    fn dependencies_by_module(
+        &self,
+        module: &str,
+        type_names: &[String],
+        import_name: impl Fn(&str) -> String,
+    ) -> Vec<Import> {
+        let mut by_module: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+        for name in type_names {
+            for dep in self.registry.dependencies(name) {
+                let dep_module = self.module_of(dep);
+                if dep_module == module {
+                    continue;
+                }
+                by_module.entry(dep_module).or_default().insert(import_name(dep));
+            }
+        }
+
+        let from_file = self.file_for_module(module);
+        let mut imports: Vec<Import> = by_module
+            .into_iter()
+            .map(|(dep_module, names)| Import {
+                from: relative_specifier(&from_file, &self.file_for_module(&dep_module), self.config.esm_extensions),
+                names: names.into_iter().collect(),
+            })
+            .collect();
+        imports.sort_by(|a, b| a.from.cmp(&b.from));
+        imports
+    }
+
+    /// Find cycles of imports between generated files
+    ///
+    /// Each cycle lists the modules whose files import each other, sorted.
+    /// Cycles are sorted by their first module.
+    pub fn import_cycles(&self) -> Vec<Vec<String>> {
+        // Module-level dependency graph
+        let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (module, type_names) in self.types_by_module() {
+            let edges = graph.entry(module.clone()).or_default();
+            for name in &type_names {
+                for dep in self.registry.dependencies(name) {
+                    let dep_module = self.module_of(dep);
+                    if dep_module != module {
+                        edges.insert(dep_module);
+                    }
+                }
+            }
+        }
+
+        let reachable = |start: &String| -> BTreeSet<String> {
+            let mut seen = BTreeSet::new();
+            let mut stack = vec![start.clone()];
+            while let Some(module) = stack.pop() {
+                for next in graph.get(&module).into_iter().flatten() {
+                    if seen.insert(next.clone()) {
+                        stack.push(next.clone());
+                    }
+                }
+            }
+            seen
+        };
+        let reach: BTreeMap<&String, BTreeSet<String>> =
+            graph.keys().map(|module| (module, reachable(module))).collect();
+
+        // Two modules are in the same cycle if each reaches the other
+        let mut cycles: Vec<Vec<String>> = Vec::new();
+        let mut assigned: BTreeSet<&String> = BTreeSet::new();
+        for module in graph.keys() {
+            if assigned.contains(module) || !reach[module].contains(module) {
+                continue;
+            }
+            let cycle: Vec<String> = graph
+                .keys()
+                .filter(|other| reach[module].contains(*other) && reach[*other].contains(module))
+                .cloned()
+                .collect();
+            assigned.extend(graph.keys().filter(|other| cycle.contains(other)));
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Returns an error describing the first import cycle, if cycles are denied
+    fn check_import_cycles(&self) -> std::io::Result<()> {
+        if !self.config.deny_import_cycles {
+            return Ok(());
+        }
+        match self.import_cycles().first() {
+            Some(cycle) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("import cycle between generated modules: {}", cycle.join(", ")),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Header comment for a generated file, from `header` or the default
+    ///
+    /// The default names the module of a multi-file module file.
+    fn file_header(&self, header: Option<&str>, file: Option<&Path>, module: Option<&str>) -> String {
+        let mut output = String::new();
+        if let Some(header) = header {
+            let file = file.map(|f| f.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+            let header = header
+                .replace("{file}", &file)
+                .replace("{module}", module.unwrap_or_default())
+                .replace("{version}", env!("CARGO_PKG_VERSION"));
+            for line in header.lines() {
+                output.push_str("//");
+                if !line.is_empty() {
+                    output.push(' ');
+                    output.push_str(line);
+                }
+                output.push('\n');
+            }
         } else {
             output.push_str("// Generated by ferro-type-gen\n");
             output.push_str("// Do not edit manually\n");
-            output.push_str("// Module: ");
-            output.push_str(module);
-            output.push('\n');
+            if let Some(module) = module {
+                output.push_str("// Module: ");
+                output.push_str(module);
+                output.push('\n');
+            }
         }
         output.push('\n');
+        output
+    }
+
+    /// Generate TypeScript content for a specific module
+    ///
+    /// Only includes types from the specified module, preceded by `import type`
+    /// statements for the types it references from other modules.
+    pub fn generate_for_module(&self, module: &str, type_names: &[String]) -> String {
+        let header = self
+            .config
+            .modules
+            .get(module)
+            .and_then(|m| m.header.as_deref())
+            .or(self.config.header.as_deref());
+        let mut output = self.file_header(header, Some(&self.file_for_module(module)), Some(module));
 
         // Get types for this module in dependency order
         let sorted = self.registry.sorted_types();
@@ -392,22 +950,256 @@ impl Generator {
             .filter(|name| type_names.contains(&name.to_string()))
             .collect();
 
-        // TODO: Add import statements for types from other modules
+        // Import types referenced from other modules
+        let imports = self.imports_for_module(module, type_names);
+        for import in &imports {
+            output.push_str(&import.render());
+            output.push('\n');
+        }
+        if self.config.type_guards && !self.config.declaration_only {
+            for import in self.guard_imports_for_module(module, type_names) {
+                output.push_str(&format!("import {{ {} }} from \"{}\";\n", import.names.join(", "), import.from));
+            }
+        }
+        if !imports.is_empty() {
+            output.push('\n');
+        }
 
         // Render types
-        for name in module_types {
-            if let Some(typedef) = self.registry.get(name) {
-                if let TypeDef::Named { name, def, .. } = typedef {
-                    let export_prefix = match self.config.export_style {
-                        ExportStyle::None => "",
-                        ExportStyle::Named | ExportStyle::Grouped => "export ",
+        let exported = self.config.export_style != ExportStyle::None;
+        let options = DeclarationOptions {
+            export: exported,
+            ambient: self.config.declaration_only,
+            style: self.registry.declaration_style(),
+            format: self.registry.format().copied(),
+        };
+        let mut exports_any = false;
+        for &name in &module_types {
+            if let Some(typedef @ (TypeDef::Named { .. } | TypeDef::GenericDef { .. })) = self.registry.get(name) {
+                output.push_str(&typedef.render_declaration_with(&options));
+                output.push_str("\n\n");
+                exports_any |= exported;
+            }
+        }
+        let guards = self.render_guards(&module_types, exported);
+        if !guards.is_empty() {
+            output.push_str(&guards);
+            output.push('\n');
+        }
+
+        // Declaration files are imported as modules, so one without imports
+        // or exports must still be marked as a module
+        if self.config.declaration_only && imports.is_empty() && !exports_any {
+            output.push_str("export {};\n\n");
+        }
+
+        output
+    }
+
+    /// Names a module's file exports, each with whether it is also a value
+    ///
+    /// Enum declarations other than type aliases export a runtime value, so
+    /// barrels re-export them with `export { }` rather than `export type { }`.
+    fn module_exports(&self, type_names: &[String]) -> BTreeMap<String, bool> {
+        let mut exports = BTreeMap::new();
+        for name in type_names {
+            let is_value = matches!(
+                self.registry.get(name),
+                Some(TypeDef::Named { namespace, def, .. })
+                    if namespace.is_empty() && matches!(def.as_ref(), TypeDef::Enum { .. })
+            );
+            let root = name.split('.').next().unwrap_or(name);
+            *exports.entry(root.to_string()).or_insert(false) |= is_value;
+            if self.config.type_guards {
+                exports.insert(guard_name(name), true);
+            }
+        }
+        exports
+    }
+
+    /// Generate the barrel `index.ts` files for multi-file mode
+    ///
+    /// Returns each index file's content keyed by its path relative to the
+    /// output directory: `index.ts` at the root, plus one per subdirectory
+    /// (`index.d.ts` with [`Config::declaration_only`]).
+    /// Returns an empty map if [`Config::barrel`] is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidData`](std::io::ErrorKind::InvalidData) error if
+    /// two modules export the same name under [`BarrelStrategy::Error`], if a
+    /// module would be written to an `index.ts` itself, or if a file and a
+    /// directory would share a namespace under [`BarrelStrategy::Namespace`].
+    pub fn generate_barrels(&self) -> std::io::Result<BTreeMap<PathBuf, String>> {
+        let Some(strategy) = self.config.barrel else {
+            return Ok(BTreeMap::new());
+        };
+        let invalid =
+            |message: String| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+        // Files and subdirectories of every directory, keyed by directory path
+        let mut dirs: BTreeMap<PathBuf, (BTreeMap<String, String>, BTreeSet<String>)> =
+            BTreeMap::new();
+        let mut exports: BTreeMap<String, BTreeMap<String, bool>> = BTreeMap::new();
+        for (module, type_names) in self.types_by_module() {
+            let file = self.file_for_module(&module);
+            let stem = strip_ts_extension(&file)
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            if stem == "index" {
+                return Err(invalid(format!(
+                    "module {} would overwrite the barrel {}",
+                    module,
+                    self.barrel_file()
+                )));
+            }
+            let dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+            dirs.entry(dir.clone())
+                .or_default()
+                .0
+                .insert(stem, module.clone());
+
+            // Register the directory with each of its ancestors
+            let mut child = dir;
+            while let Some(parent) = child.parent() {
+                let name = child
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                dirs.entry(parent.to_path_buf()).or_default().1.insert(name);
+                child = parent.to_path_buf();
+            }
+            exports.insert(module, self.module_exports(&type_names));
+        }
+
+        // Exported name (possibly prefixed) for each name, per module
+        let mut owners: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (module, names) in &exports {
+            for name in names.keys() {
+                owners.entry(name).or_default().push(module);
+            }
+        }
+        let duplicates: BTreeMap<&str, Vec<&str>> = owners
+            .into_iter()
+            .filter(|(_, modules)| modules.len() > 1)
+            .collect();
+        if strategy == BarrelStrategy::Error {
+            if let Some((name, modules)) = duplicates.iter().next() {
+                return Err(invalid(format!(
+                    "duplicate export `{}` from modules {}",
+                    name,
+                    modules.join(", ")
+                )));
+            }
+        }
+
+        let extension = if self.config.esm_extensions {
+            ".js"
+        } else {
+            ""
+        };
+        let mut barrels = BTreeMap::new();
+        for (dir, (files, subdirs)) in &dirs {
+            let index = dir.join(self.barrel_file());
+            let mut output = self.file_header(self.config.header.as_deref(), Some(&index), None);
+
+            for (stem, module) in files {
+                let from = format!("./{}{}", stem, extension);
+                if strategy == BarrelStrategy::Namespace {
+                    if subdirs.contains(stem) {
+                        return Err(invalid(format!(
+                            "module {} and directory {} would both be exported as namespace `{}`",
+                            module,
+                            dir.join(stem).display(),
+                            stem
+                        )));
+                    }
+                    output.push_str(&format!("export * as {} from \"{}\";\n", stem, from));
+                    continue;
+                }
+
+                let prefix = module_prefix(&self.file_for_module(module));
+                let mut types = Vec::new();
+                let mut values = Vec::new();
+                for (name, is_value) in &exports[module] {
+                    let export = if duplicates.contains_key(name.as_str()) {
+                        format!("{} as {}{}", name, prefix, name)
+                    } else {
+                        name.clone()
                     };
-                    output.push_str(&format!("{}type {} = {};\n\n", export_prefix, name, def.render()));
+                    if *is_value {
+                        values.push(export)
+                    } else {
+                        types.push(export)
+                    }
+                }
+                if !types.is_empty() {
+                    output.push_str(&format!(
+                        "export type {{ {} }} from \"{}\";\n",
+                        types.join(", "),
+                        from
+                    ));
+                }
+                if !values.is_empty() {
+                    output.push_str(&format!(
+                        "export {{ {} }} from \"{}\";\n",
+                        values.join(", "),
+                        from
+                    ));
                 }
             }
+
+            for subdir in subdirs {
+                let from = format!("./{}/index{}", subdir, extension);
+                match strategy {
+                    BarrelStrategy::Namespace => {
+                        output.push_str(&format!("export * as {} from \"{}\";\n", subdir, from))
+                    }
+                    BarrelStrategy::Error | BarrelStrategy::Prefix => {
+                        output.push_str(&format!("export * from \"{}\";\n", from))
+                    }
+                }
+            }
+
+            barrels.insert(index, output);
         }
 
-        output
+        Ok(barrels)
+    }
+
+    /// Generate every file of multi-file mode without writing anything
+    ///
+    /// Returns each file's content keyed by its path relative to the output
+    /// directory: one file per module (see [`file_for_module`](Self::file_for_module))
+    /// plus the barrel index files if [`Config::barrel`] is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if generated files import each other in a cycle and
+    /// [`Config::deny_import_cycles`] is set, if two modules would be written
+    /// to the same file, or if the barrels cannot be generated (see
+    /// [`generate_barrels`](Self::generate_barrels)).
+    pub fn generate_multi_file(&self) -> std::io::Result<BTreeMap<PathBuf, String>> {
+        self.check_import_cycles()?;
+        let mut files = self.generate_barrels()?;
+        let mut owners: BTreeMap<PathBuf, String> = BTreeMap::new();
+        let mut modules: Vec<_> = self.types_by_module().into_iter().collect();
+        modules.sort();
+        for (module, type_names) in modules {
+            let file = self.file_for_module(&module);
+            if let Some(other) = owners.insert(file.clone(), module.clone()) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("modules {} and {} would both be written to {}", other, module, file.display()),
+                ));
+            }
+            let content = self.generate_for_module(&module, &type_names);
+            files.insert(file, content);
+        }
+        Ok(files)
     }
 
     /// Write TypeScript to multiple files, organized by module
@@ -417,28 +1209,91 @@ impl Generator {
     /// - Types from `my_crate::models::user` go to `<output_dir>/models/user.ts`
     /// - Types from `my_crate::api` go to `<output_dir>/api.ts`
     ///
+    /// The files written are recorded in a [`MANIFEST_FILE`] in `output_dir`.
+    /// Files listed there by a previous run that are no longer generated are
+    /// removed; files the generator did not write are never touched.
+    ///
     /// # Arguments
     ///
     /// * `output_dir` - Base directory for output files
     ///
     /// # Returns
     ///
-    /// Returns the number of files written.
+    /// Returns the number of files written, including barrel index files.
     ///
     /// # Errors
     ///
-    /// Returns an error if files cannot be written.
+    /// Returns an error if files cannot be written, if the manifest cannot be
+    /// read, or if the files cannot be generated (see
+    /// [`generate_multi_file`](Self::generate_multi_file)).
     pub fn write_multi_file(&self, output_dir: impl AsRef<Path>) -> std::io::Result<usize> {
+        self.sync_multi_file(output_dir.as_ref(), true)
+            .map(|summary| summary.written())
+    }
+
+    /// Write multi-file only if content has changed
+    ///
+    /// Like [`write_multi_file`](Self::write_multi_file), but files whose
+    /// content is already up to date are left alone. Returns which files were
+    /// created, updated, removed or left unchanged.
+    pub fn write_multi_file_if_changed(
+        &self,
+        output_dir: impl AsRef<Path>,
+    ) -> std::io::Result<WriteSummary> {
+        self.sync_multi_file(output_dir.as_ref(), false)
+    }
+
+    /// Compare multi-file output with the files in `output_dir`, without
+    /// writing
+    ///
+    /// Reports generated files that differ or are missing, and files from
+    /// the manifest that [`write_multi_file`](Self::write_multi_file) would
+    /// remove. Paths are relative to `output_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if files or the manifest cannot be read, or if the
+    /// files cannot be generated (see
+    /// [`generate_multi_file`](Self::generate_multi_file)).
+    pub fn check_multi_file(&self, output_dir: impl AsRef<Path>) -> std::io::Result<CheckReport> {
         let output_dir = output_dir.as_ref();
-        let types_by_module = self.types_by_module();
-        let mut count = 0;
-
-        for (module, type_names) in &types_by_module {
-            let file_path = if module == "default" {
-                output_dir.join("types.ts")
-            } else {
-                output_dir.join(Self::module_to_path(module))
+        let files = self.generate_multi_file()?;
+
+        let mut report = CheckReport::default();
+        for (path, content) in &files {
+            report.compare(path.clone(), &output_dir.join(path), content.clone())?;
+        }
+        for path in Manifest::read(output_dir)? {
+            if !files.contains_key(&path) && output_dir.join(&path).exists() {
+                report.extra.push(path);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Brings `output_dir` in line with the generated files and the manifest
+    fn sync_multi_file(&self, output_dir: &Path, force: bool) -> std::io::Result<WriteSummary> {
+        let files = self.generate_multi_file()?;
+        let owned = Manifest::read(output_dir)?;
+        let mut summary = WriteSummary::default();
+
+        for (path, content) in &files {
+            let file_path = output_dir.join(path);
+
+            // Check if file exists and has same content
+            let existing = match std::fs::read_to_string(&file_path) {
+                Ok(existing) => Some(existing),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e),
             };
+            match existing {
+                Some(existing) if !force && existing == *content => {
+                    summary.unchanged.push(path.clone());
+                    continue;
+                }
+                Some(_) => summary.updated.push(path.clone()),
+                None => summary.created.push(path.clone()),
+            }
 
             // Create parent directories
             if let Some(parent) = file_path.parent() {
@@ -446,53 +1301,222 @@ impl Generator {
                     std::fs::create_dir_all(parent)?;
                 }
             }
-
-            let content = self.generate_for_module(module, type_names);
             std::fs::write(&file_path, content)?;
-            count += 1;
         }
 
-        Ok(count)
+        // Remove files from a previous run that are no longer generated
+        for path in owned.difference(&files.keys().cloned().collect()) {
+            match std::fs::remove_file(output_dir.join(path)) {
+                Ok(()) => summary.removed.push(path.clone()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+
+            // Clean up directories the removal left empty
+            let mut dir = path.parent();
+            while let Some(parent) = dir.filter(|d| !d.as_os_str().is_empty()) {
+                if std::fs::remove_dir(output_dir.join(parent)).is_err() {
+                    break;
+                }
+                dir = parent.parent();
+            }
+        }
+
+        Manifest::write(output_dir, files.keys())?;
+        Ok(summary)
     }
+}
 
-    /// Write multi-file only if content has changed
+/// Name of the manifest that records which files multi-file mode owns
+///
+/// It is written to the output directory by
+/// [`Generator::write_multi_file`] and
+/// [`Generator::write_multi_file_if_changed`], which remove files listed in
+/// it once they are no longer generated.
+pub const MANIFEST_FILE: &str = ".ferrotype-manifest.json";
+
+/// Files affected by [`Generator::write_multi_file_if_changed`]
+///
+/// Paths are relative to the output directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteSummary {
+    /// Files that did not exist before
+    pub created: Vec<PathBuf>,
+    /// Files whose content changed
+    pub updated: Vec<PathBuf>,
+    /// Files from a previous run that are no longer generated
+    pub removed: Vec<PathBuf>,
+    /// Files that were already up to date
+    pub unchanged: Vec<PathBuf>,
+}
+
+impl WriteSummary {
+    /// Number of files created or updated
+    pub fn written(&self) -> usize {
+        self.created.len() + self.updated.len()
+    }
+
+    /// Whether nothing was created, updated or removed
+    pub fn is_unchanged(&self) -> bool {
+        self.written() == 0 && self.removed.is_empty()
+    }
+}
+
+/// Differences between generated output and files on disk
+///
+/// Returned by [`Generator::check`] and [`Generator::check_multi_file`]. The
+/// [`Display`](std::fmt::Display) implementation renders every difference,
+/// with changed files as unified diffs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckReport {
+    /// Files whose content differs from the generated output
+    pub differ: Vec<FileDiff>,
+    /// Generated files that do not exist on disk
+    pub missing: Vec<PathBuf>,
+    /// Previously generated files that are no longer produced
+    pub extra: Vec<PathBuf>,
+}
+
+impl CheckReport {
+    /// Whether the files on disk match the generated output
+    pub fn is_ok(&self) -> bool {
+        self.differ.is_empty() && self.missing.is_empty() && self.extra.is_empty()
+    }
+
+    /// Compares `expected` with the content at `file_path`
+    fn compare(&mut self, path: PathBuf, file_path: &Path, expected: String) -> std::io::Result<()> {
+        match std::fs::read_to_string(file_path) {
+            Ok(actual) if actual == expected => {}
+            Ok(actual) => self.differ.push(FileDiff { path, expected, actual }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => self.missing.push(path),
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for diff in &self.differ {
+            f.write_str(&diff.unified_diff())?;
+        }
+        for path in &self.missing {
+            writeln!(f, "missing: {}", path.display())?;
+        }
+        for path in &self.extra {
+            writeln!(f, "extra: {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// A file whose content differs from the generated output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Content the generator produces
+    pub expected: String,
+    /// Content currently on disk
+    pub actual: String,
+}
+
+impl FileDiff {
+    /// Renders the change from the file on disk to the generated content as
+    /// a unified diff
     ///
-    /// Returns the number of files that were written (changed).
-    pub fn write_multi_file_if_changed(&self, output_dir: impl AsRef<Path>) -> std::io::Result<usize> {
-        let output_dir = output_dir.as_ref();
-        let types_by_module = self.types_by_module();
-        let mut count = 0;
-
-        for (module, type_names) in &types_by_module {
-            let file_path = if module == "default" {
-                output_dir.join("types.ts")
-            } else {
-                output_dir.join(Self::module_to_path(module))
-            };
+    /// Relative paths get the usual `a/` and `b/` prefixes.
+    pub fn unified_diff(&self) -> String {
+        let path = self.path.display();
+        let (old_name, new_name) = if self.path.is_absolute() {
+            (path.to_string(), path.to_string())
+        } else {
+            (format!("a/{}", path), format!("b/{}", path))
+        };
+        diff::unified_diff(&self.actual, &self.expected, &old_name, &new_name)
+    }
+}
+
+/// Contents of [`MANIFEST_FILE`]
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    /// Owned files relative to the output directory, `/`-separated
+    files: BTreeSet<String>,
+}
 
-            let new_content = self.generate_for_module(module, type_names);
+impl Manifest {
+    const VERSION: u32 = 1;
 
-            // Check if file exists and has same content
-            let should_write = if file_path.exists() {
-                let existing = std::fs::read_to_string(&file_path)?;
-                existing != new_content
-            } else {
-                true
-            };
+    /// Reads the files owned by a previous run, if any
+    fn read(output_dir: &Path) -> std::io::Result<BTreeSet<PathBuf>> {
+        let path = output_dir.join(MANIFEST_FILE);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeSet::new()),
+            Err(e) => return Err(e),
+        };
+        let invalid = |message: String| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), message),
+            )
+        };
 
-            if should_write {
-                // Create parent directories
-                if let Some(parent) = file_path.parent() {
-                    if !parent.as_os_str().is_empty() {
-                        std::fs::create_dir_all(parent)?;
-                    }
-                }
-                std::fs::write(&file_path, new_content)?;
-                count += 1;
-            }
+        let manifest: Manifest =
+            serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        if manifest.version != Self::VERSION {
+            return Err(invalid(format!(
+                "unsupported manifest version {}",
+                manifest.version
+            )));
         }
 
-        Ok(count)
+        // Only ever remove files inside the output directory
+        manifest
+            .files
+            .into_iter()
+            .map(|file| {
+                let path = PathBuf::from(&file);
+                let inside = path
+                    .components()
+                    .all(|c| matches!(c, std::path::Component::Normal(_)));
+                if inside && !file.is_empty() {
+                    Ok(path)
+                } else {
+                    Err(invalid(format!("`{}` is outside the output directory", file)))
+                }
+            })
+            .collect()
+    }
+
+    /// Records `files` as owned, leaving the manifest alone if up to date
+    fn write<'a>(
+        output_dir: &Path,
+        files: impl IntoIterator<Item = &'a PathBuf>,
+    ) -> std::io::Result<()> {
+        let manifest = Manifest {
+            version: Self::VERSION,
+            files: files
+                .into_iter()
+                .map(|path| {
+                    path.components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                })
+                .collect(),
+        };
+        let mut content = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        content.push('\n');
+
+        let path = output_dir.join(MANIFEST_FILE);
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+            std::fs::create_dir_all(output_dir)?;
+            std::fs::write(path, content)?;
+        }
+        Ok(())
     }
 }
 
@@ -502,10 +1526,112 @@ impl Default for Generator {
     }
 }
 
+/// PascalCase prefix for a module's duplicate exports, from its file path:
+/// `models/user_profile.ts` gives `ModelsUserProfile`.
+fn module_prefix(file: &Path) -> String {
+    strip_ts_extension(file)
+        .components()
+        .flat_map(|c| {
+            c.as_os_str()
+                .to_string_lossy()
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                        None => String::new(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Strips the extension of a generated file, including both parts of `.d.ts`
+fn strip_ts_extension(file: &Path) -> PathBuf {
+    let name = file.file_name().unwrap_or_default().to_string_lossy();
+    match name.strip_suffix(".d.ts") {
+        Some(stem) => file.with_file_name(stem),
+        None => file.with_extension(""),
+    }
+}
+
+/// Computes the import specifier for `to` as seen from `from`, both paths
+/// relative to the output directory.
+///
+/// For example, `models/user.ts` importing `api/requests.ts` gives
+/// `"../api/requests"`, or `"../api/requests.js"` with ESM extensions.
+fn relative_specifier(from: &Path, to: &Path, esm_extensions: bool) -> String {
+    let components = |path: &Path| -> Vec<String> {
+        path.components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect()
+    };
+    let from_dir = components(from.parent().unwrap_or(Path::new("")));
+    let to_parts = components(&strip_ts_extension(to));
+
+    // Only directories are shared; the last part of `to` is its file name
+    let common = from_dir
+        .iter()
+        .zip(&to_parts[..to_parts.len().saturating_sub(1)])
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut specifier = if common == from_dir.len() {
+        "./".to_string()
+    } else {
+        "../".repeat(from_dir.len() - common)
+    };
+    specifier.push_str(&to_parts[common..].join("/"));
+    if esm_extensions {
+        specifier.push_str(".js");
+    }
+    specifier
+}
+
 // ============================================================================
 // CONVENIENCE FUNCTIONS
 // ============================================================================
 
+/// Calls `visit` with `typedef` and every type nested in it, along with the
+/// field path each is found at, starting from `path`
+///
+/// Named types referenced from `typedef` are visited as types of their own,
+/// and the key types of maps only if `keys` is set.
+fn visit_types(typedef: &TypeDef, path: &str, keys: bool, visit: &mut dyn FnMut(&TypeDef, &str)) {
+    visit(typedef, path);
+    match typedef {
+        TypeDef::Object(fields) => {
+            for field in fields {
+                visit_types(&field.ty, &format!("{}.{}", path, field.name), keys, visit);
+            }
+        }
+        TypeDef::Array(inner) => visit_types(inner, path, keys, visit),
+        TypeDef::Tuple(items) | TypeDef::Union(items) | TypeDef::Intersection(items) => {
+            for item in items {
+                visit_types(item, path, keys, visit);
+            }
+        }
+        TypeDef::Record { key, value } => {
+            if keys {
+                visit_types(key, path, keys, visit);
+            }
+            visit_types(value, path, keys, visit);
+        }
+        TypeDef::Generic { args, .. } => {
+            for arg in args {
+                visit_types(arg, path, keys, visit);
+            }
+        }
+        TypeDef::TemplateLiteral { types, .. } => {
+            for ty in types {
+                visit_types(ty, path, keys, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Generate TypeScript for a single type
 ///
 /// Returns the TypeScript definition as a string.
@@ -539,7 +1665,7 @@ pub fn export_to_file<P: AsRef<Path>>(path: P, registry: &TypeRegistry) -> std::
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ferro_type::{Field, Primitive, TypeDef};
+    use ferro_type::{Docs, EnumMember, EnumStyle, Field, FieldShape, Literal, Primitive, TypeDef, TypeParam};
 
     #[test]
     fn test_config_builder() {
@@ -581,6 +1707,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         generator.add(user_type);
@@ -599,6 +1727,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let output = generator.generate();
@@ -616,6 +1746,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let output = generator.generate();
@@ -632,6 +1764,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         generator.add(TypeDef::Named {
             namespace: vec![],
@@ -639,6 +1773,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let output = generator.generate();
@@ -708,6 +1844,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         generator.write().unwrap();
@@ -729,6 +1867,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         // First write should return true (changed)
@@ -744,6 +1884,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         // Third write should return true (changed)
@@ -778,6 +1920,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         export_to_file(&output_path, &registry).unwrap();
@@ -816,6 +1960,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::models".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         generator.add(TypeDef::Named {
             namespace: vec![],
@@ -823,6 +1969,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::models".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         generator.add(TypeDef::Named {
             namespace: vec![],
@@ -830,6 +1978,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::api".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         generator.add(TypeDef::Named {
             namespace: vec![],
@@ -837,6 +1987,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let by_module = generator.types_by_module();
@@ -858,6 +2010,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::models".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         generator.add(TypeDef::Named {
             namespace: vec![],
@@ -865,6 +2019,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::Number)),
             module: Some("my_crate::models".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let output = generator.generate_for_module("my_crate::models", &["User".to_string(), "Post".to_string()]);
@@ -874,6 +2030,103 @@ mod tests {
         assert!(output.contains("export type Post = number;"));
     }
 
+    #[test]
+    fn test_generate_docs() {
+        let mut generator = Generator::new(Config::new().export_style(ExportStyle::Named));
+        generator.add(TypeDef::Named {
+            namespace: vec![],
+            name: "User".to_string(),
+            def: Box::new(TypeDef::Object(vec![Field::new(
+                "id",
+                TypeDef::Primitive(Primitive::String),
+            )
+            .with_docs(Docs::new("Unique id."))])),
+            module: Some("my_crate::models".to_string()),
+            wrapper: None,
+            docs: Some(Docs::new("A registered user.")),
+            declaration_style: None,
+        });
+
+        let expected = "/** A registered user. */\nexport type User = { /** Unique id. */ id: string };";
+        assert!(generator.generate().contains(expected));
+        assert!(generator
+            .generate_for_module("my_crate::models", &["User".to_string()])
+            .contains(expected));
+    }
+
+    #[test]
+    fn test_generate_declaration_style() {
+        let mut generator = Generator::new(Config::new().declaration_style(DeclarationStyle::Interface));
+        generator.add(named_in(
+            "my_crate::models",
+            "User",
+            TypeDef::Object(vec![Field::new("id", TypeDef::Primitive(Primitive::String))]),
+        ));
+        generator.add(named_in(
+            "my_crate::models",
+            "Role",
+            TypeDef::Union(vec![
+                TypeDef::Literal(Literal::String("admin".into())),
+                TypeDef::Literal(Literal::String("member".into())),
+            ]),
+        ));
+
+        let output = generator.generate();
+        assert!(output.contains("export interface User { id: string }"));
+        assert!(output.contains("export type Role = \"admin\" | \"member\";"));
+        let module = generator.generate_for_module("my_crate::models", &["User".to_string()]);
+        assert!(module.contains("export interface User { id: string }"));
+    }
+
+    #[test]
+    fn test_generate_pretty() {
+        let mut generator = Generator::new(Config::new().format(Format::new().max_width(30)));
+        generator.add(named_in(
+            "my_crate::models",
+            "User",
+            TypeDef::Object(vec![
+                Field::new("id", TypeDef::Primitive(Primitive::String)),
+                Field::new("name", TypeDef::Primitive(Primitive::String)),
+            ]),
+        ));
+
+        let expected = "export type User = {\n    id: string;\n    name: string;\n};";
+        assert!(generator.generate().contains(expected));
+        assert!(generator
+            .generate_for_module("my_crate::models", &["User".to_string()])
+            .contains(expected));
+    }
+
+    #[test]
+    fn test_generate_json_schema() {
+        let mut generator = Generator::with_defaults();
+        generator.add(named_in("my_crate::models", "Id", TypeDef::Primitive(Primitive::String)));
+
+        assert_eq!(
+            generator.generate_json_schema(),
+            "{\n  \"$defs\": {\n    \"Id\": {\n      \"type\": \"string\"\n    }\n  },\n  \
+             \"$schema\": \"https://json-schema.org/draft/2020-12/schema\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_zod() {
+        let mut generator = Generator::new(Config::new().header("Schemas for {file}").output("schemas.ts"));
+        generator.add(named_in(
+            "my_crate::models",
+            "User",
+            TypeDef::Object(vec![Field::new("id", TypeDef::Primitive(Primitive::String))]),
+        ));
+
+        assert_eq!(
+            generator.generate_zod(),
+            "// Schemas for schemas.ts\n\n\
+             import { z } from \"zod\";\n\n\
+             export const UserSchema = z.object({ id: z.string() });\n\
+             export type User = z.infer<typeof UserSchema>;\n"
+        );
+    }
+
     #[test]
     fn test_write_multi_file() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -886,6 +2139,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::models::user".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         generator.add(TypeDef::Named {
             namespace: vec![],
@@ -893,6 +2148,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::api".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let count = generator.write_multi_file(temp_dir.path()).unwrap();
@@ -924,15 +2181,18 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: Some("my_crate::models".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         // First write should write
-        let count1 = generator.write_multi_file_if_changed(temp_dir.path()).unwrap();
-        assert_eq!(count1, 1);
+        let summary1 = generator.write_multi_file_if_changed(temp_dir.path()).unwrap();
+        assert_eq!(summary1.created, vec![PathBuf::from("models.ts")]);
 
         // Second write should not write (unchanged)
-        let count2 = generator.write_multi_file_if_changed(temp_dir.path()).unwrap();
-        assert_eq!(count2, 0);
+        let summary2 = generator.write_multi_file_if_changed(temp_dir.path()).unwrap();
+        assert!(summary2.is_unchanged());
+        assert_eq!(summary2.unchanged, vec![PathBuf::from("models.ts")]);
 
         // Add another type
         generator.add(TypeDef::Named {
@@ -941,11 +2201,77 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::Number)),
             module: Some("my_crate::models".to_string()),
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         // Third write should write (changed)
-        let count3 = generator.write_multi_file_if_changed(temp_dir.path()).unwrap();
-        assert_eq!(count3, 1);
+        let summary3 = generator.write_multi_file_if_changed(temp_dir.path()).unwrap();
+        assert_eq!(summary3.updated, vec![PathBuf::from("models.ts")]);
+        assert_eq!(summary3.written(), 1);
+    }
+
+    #[test]
+    fn test_write_multi_file_removes_stale_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let string = || TypeDef::Primitive(Primitive::String);
+
+        let mut old = Generator::with_defaults();
+        old.add(named_in("my_crate::models::user", "User", string()))
+            .add(named_in("my_crate::api", "Request", string()));
+        old.write_multi_file(temp_dir.path()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join(MANIFEST_FILE)).unwrap(),
+            "{\n  \"version\": 1,\n  \"files\": [\n    \"api.ts\",\n    \"models/user.ts\"\n  ]\n}\n"
+        );
+
+        // A hand-written file next to the generated ones
+        std::fs::write(temp_dir.path().join("custom.ts"), "export {};\n").unwrap();
+
+        // The user module was renamed
+        let mut new = Generator::with_defaults();
+        new.add(named_in("my_crate::accounts", "User", string()))
+            .add(named_in("my_crate::api", "Request", string()));
+        let summary = new.write_multi_file_if_changed(temp_dir.path()).unwrap();
+        assert_eq!(
+            summary,
+            WriteSummary {
+                created: vec![PathBuf::from("accounts.ts")],
+                updated: vec![],
+                removed: vec![PathBuf::from("models/user.ts")],
+                unchanged: vec![PathBuf::from("api.ts")],
+            }
+        );
+        assert!(!temp_dir.path().join("models").exists());
+        assert!(temp_dir.path().join("custom.ts").exists());
+
+        // Files removed by hand are not reported again
+        std::fs::remove_file(temp_dir.path().join("accounts.ts")).unwrap();
+        let mut empty = Generator::with_defaults();
+        empty.add(named_in("my_crate::api", "Request", string()));
+        let summary = empty.write_multi_file_if_changed(temp_dir.path()).unwrap();
+        assert!(summary.removed.is_empty());
+        assert!(summary.is_unchanged());
+    }
+
+    #[test]
+    fn test_write_multi_file_rejects_foreign_manifest() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut generator = Generator::with_defaults();
+        generator.add(named_in("my_crate::api", "Request", TypeDef::Primitive(Primitive::String)));
+
+        std::fs::write(
+            temp_dir.path().join(MANIFEST_FILE),
+            r#"{ "version": 1, "files": ["../outside.ts"] }"#,
+        )
+        .unwrap();
+        let err = generator.write_multi_file(temp_dir.path()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("`../outside.ts` is outside the output directory"));
+
+        std::fs::write(temp_dir.path().join(MANIFEST_FILE), "not json").unwrap();
+        let err = generator.write_multi_file(temp_dir.path()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
     }
 
     #[test]
@@ -959,6 +2285,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         generator.write_multi_file(temp_dir.path()).unwrap();
@@ -970,4 +2298,668 @@ mod tests {
         let content = std::fs::read_to_string(&types_path).unwrap();
         assert!(content.contains("export type Orphan = string;"));
     }
+
+    fn named_in(module: &str, name: &str, def: TypeDef) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(def),
+            module: Some(module.to_string()),
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    #[test]
+    fn test_relative_specifier() {
+        let spec = |from: &str, to: &str| relative_specifier(Path::new(from), Path::new(to), false);
+        assert_eq!(spec("models/user.ts", "models/post.ts"), "./post");
+        assert_eq!(spec("models/user.ts", "api/requests.ts"), "../api/requests");
+        assert_eq!(spec("api.ts", "models/user.ts"), "./models/user");
+        assert_eq!(spec("models/user.ts", "types.ts"), "../types");
+        assert_eq!(spec("models/user.ts", "models.ts"), "../models");
+        assert_eq!(spec("a/b/c.ts", "a/d/e.ts"), "../d/e");
+        assert_eq!(spec("models/user.d.ts", "api.d.ts"), "../api");
+        assert_eq!(
+            relative_specifier(Path::new("api.ts"), Path::new("models/user.ts"), true),
+            "./models/user.js"
+        );
+    }
+
+    #[test]
+    fn test_generate_for_module_imports() {
+        let mut generator = Generator::with_defaults();
+        let user = named_in("my_crate::models::user", "User", TypeDef::Primitive(Primitive::String));
+        let role = named_in("my_crate::models::user", "Role", TypeDef::Primitive(Primitive::String));
+        let request = named_in(
+            "my_crate::api",
+            "Request",
+            TypeDef::Object(vec![
+                Field::new("user", user.clone()),
+                Field::new("role", role.clone()),
+                Field::new("next", TypeDef::Ref("Response".into())),
+            ]),
+        );
+        let response = named_in("my_crate::api", "Response", TypeDef::Primitive(Primitive::Null));
+        generator.add(response).add(request);
+
+        let output = generator.generate_for_module(
+            "my_crate::api",
+            &["Request".to_string(), "Response".to_string()],
+        );
+        assert!(output.contains(
+            "// Module: my_crate::api\n\nimport type { Role, User } from \"./models/user\";\n\nexport type Response"
+        ));
+
+        // Types within the same module are not imported
+        let output = generator.generate_for_module(
+            "my_crate::models::user",
+            &["User".to_string(), "Role".to_string()],
+        );
+        assert!(!output.contains("import"));
+    }
+
+    #[test]
+    fn test_generate_for_module_generics() {
+        let mut generator = Generator::with_defaults();
+        let user = named_in("my_crate::models::user", "User", TypeDef::Primitive(Primitive::String));
+        let page = TypeDef::Generic {
+            base: "Page".into(),
+            args: vec![user],
+            def: Some(Box::new(TypeDef::GenericDef {
+                name: "Page".into(),
+                type_params: vec![TypeParam::new("T")],
+                def: Box::new(TypeDef::Object(vec![Field::new(
+                    "items",
+                    TypeDef::Array(Box::new(TypeDef::TypeParamRef("T".into()))),
+                )])),
+                module: Some("my_crate::models::page".into()),
+                docs: None,
+                declaration_style: None,
+            })),
+        };
+        generator.add(named_in("my_crate::api", "Feed", TypeDef::Object(vec![Field::new("users", page)])));
+
+        let by_module = generator.types_by_module();
+        assert_eq!(by_module["my_crate::models::page"], vec!["Page".to_string()]);
+        assert!(!by_module.contains_key("default"));
+
+        let output = generator.generate_for_module("my_crate::models::page", &["Page".to_string()]);
+        assert!(output.contains("export type Page<T> = { items: T[] };"));
+        let output = generator.generate_for_module("my_crate::api", &["Feed".to_string()]);
+        assert!(output.contains(
+            "import type { Page } from \"./models/page\";\nimport type { User } from \"./models/user\";\n\n\
+             export type Feed = { users: Page<User> };"
+        ));
+    }
+
+    #[test]
+    fn test_generate_type_guards() {
+        let mut generator = Generator::new(Config::new().type_guards());
+        let id = named_in("my_crate::models", "Id", TypeDef::Primitive(Primitive::String));
+        let user = named_in("my_crate::models", "User", TypeDef::Object(vec![Field::new("id", id)]));
+        let request = named_in("my_crate::api", "Request", TypeDef::Object(vec![Field::new("user", user)]));
+        generator.add(request);
+
+        let output = generator.generate();
+        assert!(output.contains(
+            "export type Request = { user: User };\n\n\
+             function isPlainObject(value: unknown): value is Record<string, unknown> {"
+        ));
+        assert!(output.ends_with(
+            "export function isId(value: unknown): value is Id {\n    return typeof value === \"string\";\n}\n\n\
+             export function isUser(value: unknown): value is User {\n    return isPlainObject(value) && isId(value[\"id\"]);\n}\n\n\
+             export function isRequest(value: unknown): value is Request {\n    \
+             return isPlainObject(value) && isUser(value[\"user\"]);\n}\n"
+        ));
+
+        // Guards of types in other modules are imported as values
+        let output = generator.generate_for_module("my_crate::api", &["Request".to_string()]);
+        assert!(output.contains(
+            "import type { User } from \"./models\";\nimport { isUser } from \"./models\";\n\n"
+        ));
+        assert!(output.contains("\n\nexport function isRequest("));
+
+        // Only the types' own guards are needed without objects
+        let output = generator.generate_for_module("my_crate::models", &["Id".to_string()]);
+        assert!(output.ends_with("export type Id = string;\n\nexport function isId(value: unknown): value is Id {\n    return typeof value === \"string\";\n}\n\n"));
+
+        let grouped = Generator::new(Config::new().type_guards().export_style(ExportStyle::Grouped));
+        assert!(!grouped.generate().contains("isPlainObject"));
+        let mut grouped = grouped;
+        grouped.add(named_in("my_crate::models", "Id", TypeDef::Primitive(Primitive::String)));
+        assert!(grouped.generate().ends_with("\nfunction isId(value: unknown): value is Id {\n    return typeof value === \"string\";\n}\n\nexport { Id, isId };\n"));
+    }
+
+    #[test]
+    fn test_int_policies() {
+        let id = || TypeDef::LargeInt { width: IntWidth::Bits64, policy: None };
+        let message = |name: &str| {
+            named_in(
+                "my_crate",
+                name,
+                TypeDef::Object(vec![
+                    Field::new("id", id()),
+                    Field::new("replies", TypeDef::Array(Box::new(id()))),
+                    Field::new("count", id().with_int_policy(IntWidth::Bits64, IntPolicy::Number)),
+                    Field::new("total", TypeDef::LargeInt { width: IntWidth::Bits128, policy: None }),
+                    // Map keys are written as strings, so they lose nothing
+                    Field::new("by_id", TypeDef::map(id(), TypeDef::Primitive(Primitive::String))),
+                ]),
+            )
+        };
+
+        // Without a policy, 64-bit integers are numbers and reported
+        let mut generator = Generator::with_defaults();
+        generator.add(message("Message"));
+        assert!(generator.generate().contains("id: number; replies: number[]; count: number; total: bigint; by_id"));
+        assert_eq!(generator.lossy_integers(), vec!["Message.id", "Message.replies"]);
+
+        let mut generator =
+            Generator::new(Config::new().int64(IntPolicy::NumberOrString).int128(IntPolicy::String));
+        generator.add(message("Message"));
+        assert!(generator
+            .generate()
+            .contains("id: number | string; replies: (number | string)[]; count: number; total: string"));
+        assert!(generator.lossy_integers().is_empty());
+    }
+
+    #[test]
+    fn test_shape() {
+        let post = || {
+            named_in(
+                "my_crate",
+                "Post",
+                TypeDef::Object(vec![
+                    Field::optional("tags", TypeDef::Array(Box::new(TypeDef::Primitive(Primitive::String))))
+                        .with_shape(FieldShape::Defaulted),
+                    Field::optional("note", TypeDef::Primitive(Primitive::String))
+                        .with_shape(FieldShape::SkippedNone),
+                ]),
+            )
+        };
+        let generate = |config: Config| {
+            let mut generator = Generator::new(config);
+            generator.add(post());
+            generator.generate()
+        };
+        assert!(generate(Config::new()).contains("type Post = { tags?: string[]; note?: string };"));
+        assert!(generate(Config::new().shape(Shape::Output)).contains("type Post = { tags: string[]; note?: string };"));
+        assert!(generate(Config::new().shape(Shape::Input))
+            .contains("type Post = { tags?: string[]; note?: string | null };"));
+    }
+
+    #[test]
+    fn test_map_keys() {
+        let point = named_in(
+            "my_crate",
+            "Point",
+            TypeDef::Object(vec![Field::new("x", TypeDef::Primitive(Primitive::Number))]),
+        );
+        let number = || TypeDef::Primitive(Primitive::Number);
+        let mut generator = Generator::with_defaults();
+        generator.add(named_in(
+            "my_crate",
+            "Lookup",
+            TypeDef::Object(vec![
+                Field::new("by_id", TypeDef::map(number(), number())),
+                Field::new("by_point", TypeDef::map(point, number())),
+            ]),
+        ));
+
+        assert!(generator.generate().contains("by_id: Record<`${number}`, number>; by_point: Record<Point, number>"));
+        assert!(generator.generate_zod().contains(r"by_id: z.record(z.string().regex(/^-?\d+(?:\.\d+)?$/), z.number())"));
+        assert_eq!(generator.invalid_map_keys(), vec!["Lookup.by_point"]);
+    }
+
+    #[test]
+    fn test_imports_esm_and_namespaces() {
+        let mut generator = Generator::new(Config::new().esm_extensions());
+        let state = TypeDef::Named {
+            namespace: vec!["VM".into(), "Git".into()],
+            name: "State".to_string(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: Some("my_crate::vm".to_string()),
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        generator.add(named_in(
+            "my_crate::models::repo",
+            "Repo",
+            TypeDef::Object(vec![Field::new("state", state)]),
+        ));
+
+        assert_eq!(
+            generator.imports_for_module("my_crate::models::repo", &["Repo".to_string()]),
+            vec![Import {
+                from: "../vm.js".to_string(),
+                names: vec!["VM".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write_multi_file_imports() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut generator = Generator::with_defaults();
+        let user = named_in("my_crate::models::user", "User", TypeDef::Primitive(Primitive::String));
+        generator.add(named_in(
+            "my_crate::models::post",
+            "Post",
+            TypeDef::Object(vec![Field::new("author", user)]),
+        ));
+
+        generator.write_multi_file(temp_dir.path()).unwrap();
+
+        let post = std::fs::read_to_string(temp_dir.path().join("models/post.ts")).unwrap();
+        assert!(post.contains("import type { User } from \"./user\";"));
+        assert!(post.contains("export type Post = { author: User };"));
+    }
+
+    #[test]
+    fn test_import_cycles() {
+        let mut generator = Generator::with_defaults();
+        generator
+            .add(named_in(
+                "my_crate::a",
+                "A",
+                TypeDef::Object(vec![Field::new("b", TypeDef::Ref("B".into()))]),
+            ))
+            .add(named_in(
+                "my_crate::b",
+                "B",
+                TypeDef::Object(vec![Field::new("a", TypeDef::Ref("A".into()))]),
+            ))
+            .add(named_in(
+                "my_crate::c",
+                "C",
+                TypeDef::Object(vec![Field::new("a", TypeDef::Ref("A".into()))]),
+            ));
+
+        assert_eq!(
+            generator.import_cycles(),
+            vec![vec!["my_crate::a".to_string(), "my_crate::b".to_string()]]
+        );
+
+        // Allowed by default, since type-only imports may be cyclic
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert_eq!(generator.write_multi_file(temp_dir.path()).unwrap(), 3);
+
+        let mut strict = Generator::new(Config::new().deny_import_cycles());
+        for name in ["A", "B", "C"] {
+            strict.add(generator.registry().get(name).unwrap().clone());
+        }
+        let err = strict.write_multi_file(temp_dir.path()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("my_crate::a, my_crate::b"));
+    }
+
+    fn barrel_generator(strategy: BarrelStrategy) -> Generator {
+        let mut generator = Generator::new(Config::new().barrel(strategy));
+        generator
+            .add(named_in(
+                "my_crate::api",
+                "Request",
+                TypeDef::Primitive(Primitive::String),
+            ))
+            .add(named_in(
+                "my_crate::models::user",
+                "User",
+                TypeDef::Primitive(Primitive::String),
+            ))
+            .add(named_in(
+                "my_crate::models::user",
+                "UserId",
+                TypeDef::Primitive(Primitive::String),
+            ))
+            .add(named_in(
+                "my_crate::models::user",
+                "Role",
+                TypeDef::Enum {
+                    members: vec![EnumMember::new("Admin", Literal::String("Admin".into()))],
+                    style: EnumStyle::Enum,
+                },
+            ));
+        generator
+    }
+
+    #[test]
+    fn test_generate_barrels() {
+        let barrels = barrel_generator(BarrelStrategy::Error)
+            .generate_barrels()
+            .unwrap();
+        assert_eq!(
+            barrels.keys().collect::<Vec<_>>(),
+            vec![Path::new("index.ts"), Path::new("models/index.ts")]
+        );
+        assert_eq!(
+            barrels[Path::new("index.ts")],
+            "// Generated by ferro-type-gen\n// Do not edit manually\n\n\
+             export type { Request } from \"./api\";\n\
+             export * from \"./models/index\";\n"
+        );
+        // Enum declarations are values, so they are not re-exported as types
+        assert!(barrels[Path::new("models/index.ts")].ends_with(
+            "export type { User, UserId } from \"./user\";\nexport { Role } from \"./user\";\n"
+        ));
+
+        // Nothing is generated unless barrels are enabled
+        let mut generator = Generator::with_defaults();
+        generator.add(named_in(
+            "my_crate::api",
+            "Request",
+            TypeDef::Primitive(Primitive::String),
+        ));
+        assert!(generator.generate_barrels().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_barrel_namespace_strategy() {
+        let mut generator = barrel_generator(BarrelStrategy::Namespace);
+        generator.config.esm_extensions = true;
+        let barrels = generator.generate_barrels().unwrap();
+        assert!(barrels[Path::new("index.ts")].ends_with(
+            "export * as api from \"./api.js\";\nexport * as models from \"./models/index.js\";\n"
+        ));
+        assert!(barrels[Path::new("models/index.ts")]
+            .ends_with("export * as user from \"./user.js\";\n"));
+
+        // A file and a directory cannot share a namespace
+        generator.add(named_in(
+            "my_crate::models",
+            "Model",
+            TypeDef::Primitive(Primitive::String),
+        ));
+        let err = generator.generate_barrels().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "module my_crate::models and directory models would both be exported as namespace `models`"
+        );
+    }
+
+    #[test]
+    fn test_barrel_duplicate_exports() {
+        // Registry names are unique, so a clash comes from a namespaced type
+        // whose root is also declared by another module
+        let mut generator = barrel_generator(BarrelStrategy::Error);
+        generator.add(TypeDef::Named {
+            namespace: vec!["Request".into()],
+            name: "Body".into(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: Some("my_crate::models::user".into()),
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        });
+        let err = generator.generate_barrels().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "duplicate export `Request` from modules my_crate::api, my_crate::models::user"
+        );
+
+        generator.config.barrel = Some(BarrelStrategy::Prefix);
+        let barrels = generator.generate_barrels().unwrap();
+        assert!(barrels[Path::new("index.ts")]
+            .contains("export type { Request as ApiRequest } from \"./api\";"));
+        assert!(barrels[Path::new("models/index.ts")].contains(
+            "export type { Request as ModelsUserRequest, User, UserId } from \"./user\";"
+        ));
+    }
+
+    #[test]
+    fn test_barrel_rejects_index_module() {
+        let mut generator = Generator::new(Config::new().barrel(BarrelStrategy::Error));
+        generator.add(named_in(
+            "my_crate::index",
+            "Index",
+            TypeDef::Primitive(Primitive::String),
+        ));
+        let err = generator.generate_barrels().unwrap_err();
+        assert!(err.to_string().contains("my_crate::index would overwrite"));
+
+        // Without barrels the module is written as usual
+        generator.config.barrel = None;
+        assert_eq!(generator.generate_multi_file().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_write_multi_file_barrels() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let generator = barrel_generator(BarrelStrategy::Error);
+
+        // Two modules plus two barrels
+        assert_eq!(generator.write_multi_file(temp_dir.path()).unwrap(), 4);
+        assert!(temp_dir.path().join("index.ts").exists());
+        assert!(temp_dir.path().join("models/index.ts").exists());
+        assert!(generator
+            .write_multi_file_if_changed(temp_dir.path())
+            .unwrap()
+            .is_unchanged());
+    }
+
+    #[test]
+    fn test_generate_declaration_only() {
+        let role = || {
+            named_in(
+                "my_crate",
+                "Role",
+                TypeDef::Enum {
+                    members: vec![EnumMember::new("Admin", Literal::String("Admin".into()))],
+                    style: EnumStyle::Enum,
+                },
+            )
+        };
+        let mut generator = Generator::new(Config::new().declaration_only());
+        generator.add(role());
+        let output = generator.generate();
+        assert!(output.contains("export declare enum Role { Admin = \"Admin\" }"));
+        assert!(!output.contains("export {};"));
+
+        // Without exports the file stays a global script
+        let mut generator = Generator::new(
+            Config::new()
+                .declaration_only()
+                .export_style(ExportStyle::None),
+        );
+        generator.add(role());
+        let output = generator.generate();
+        assert!(output.contains("\ndeclare enum Role { Admin = \"Admin\" }"));
+        assert!(!output.contains("export {};"));
+
+        // An empty module is marked as one
+        let generator = Generator::new(Config::new().declaration_only());
+        assert!(generator.generate().ends_with("\nexport {};\n"));
+    }
+
+    #[test]
+    fn test_declaration_only_multi_file() {
+        let mut generator = barrel_generator(BarrelStrategy::Error);
+        generator.config.declaration_only = true;
+        generator.config.modules.insert(
+            "my_crate::api".into(),
+            ModuleConfig {
+                output: Some("requests/api.ts".into()),
+                header: None,
+            },
+        );
+        generator.add(named_in(
+            "my_crate::models::post",
+            "Post",
+            TypeDef::Ref("User".into()),
+        ));
+
+        let files = generator.generate_multi_file().unwrap();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec![
+                Path::new("index.d.ts"),
+                Path::new("models/index.d.ts"),
+                Path::new("models/post.d.ts"),
+                Path::new("models/user.d.ts"),
+                Path::new("requests/api.d.ts"),
+                Path::new("requests/index.d.ts"),
+            ]
+        );
+        assert!(files[Path::new("models/post.d.ts")]
+            .contains("import type { User } from \"./user\";"));
+        assert!(files[Path::new("models/user.d.ts")]
+            .contains("export declare enum Role { Admin = \"Admin\" }"));
+        assert!(files[Path::new("models/index.d.ts")]
+            .contains("export type { Post } from \"./post\";"));
+        assert!(files[Path::new("index.d.ts")]
+            .contains("export * from \"./requests/index\";"));
+
+        // Files without imports or exports are still modules
+        generator.config.export_style = ExportStyle::None;
+        generator.config.barrel = None;
+        let files = generator.generate_multi_file().unwrap();
+        assert!(files[Path::new("models/user.d.ts")].ends_with("export {};\n\n"));
+        assert!(!files[Path::new("models/post.d.ts")].contains("export {};"));
+    }
+
+    #[test]
+    fn test_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("types.ts");
+
+        let mut generator = Generator::new(Config::new().output(&output));
+        generator.add(named_in("my_crate", "User", TypeDef::Primitive(Primitive::String)));
+
+        let report = generator.check().unwrap();
+        assert_eq!(report.missing, vec![output.clone()]);
+        assert!(!report.is_ok());
+
+        generator.write().unwrap();
+        assert!(generator.check().unwrap().is_ok());
+
+        generator.add(named_in("my_crate", "Post", TypeDef::Primitive(Primitive::Number)));
+        let report = generator.check().unwrap();
+        assert_eq!(report.differ.len(), 1);
+        assert_eq!(report.differ[0].actual, std::fs::read_to_string(&output).unwrap());
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "--- {0}\n+++ {0}\n@@ -5,3 +5,5 @@\n\
+                 \x20// Do not edit manually\n \n export type User = string;\n\
+                 +\n+export type Post = number;\n",
+                output.display()
+            )
+        );
+
+        let err = Generator::with_defaults().check().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_check_multi_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let string = || TypeDef::Primitive(Primitive::String);
+
+        let mut old = Generator::with_defaults();
+        old.add(named_in("my_crate::models::user", "User", string()))
+            .add(named_in("my_crate::api", "Request", string()));
+        old.write_multi_file(temp_dir.path()).unwrap();
+        assert!(old.check_multi_file(temp_dir.path()).unwrap().is_ok());
+
+        let mut new = Generator::with_defaults();
+        new.add(named_in("my_crate::accounts", "User", string()))
+            .add(named_in("my_crate::api", "Request", TypeDef::Primitive(Primitive::Number)));
+        let report = new.check_multi_file(temp_dir.path()).unwrap();
+        assert_eq!(report.missing, vec![PathBuf::from("accounts.ts")]);
+        assert_eq!(report.extra, vec![PathBuf::from("models/user.ts")]);
+        assert_eq!(report.differ.len(), 1);
+        assert_eq!(
+            report.to_string(),
+            "--- a/api.ts\n+++ b/api.ts\n@@ -2,5 +2,5 @@\n\
+             \x20// Do not edit manually\n // Module: my_crate::api\n \n\
+             -export type Request = string;\n+export type Request = number;\n \n\
+             missing: accounts.ts\nextra: models/user.ts\n"
+        );
+
+        // Checking never writes
+        assert!(!temp_dir.path().join("accounts.ts").exists());
+        new.write_multi_file(temp_dir.path()).unwrap();
+        assert!(new.check_multi_file(temp_dir.path()).unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_header_template() {
+        let mut generator = Generator::new(
+            Config::new()
+                .output("out/api.ts")
+                .header("{file} from {module}\n\nferro-type-gen {version}"),
+        );
+        generator.add(named_in("my_crate::api", "Request", TypeDef::Primitive(Primitive::String)));
+
+        let version = env!("CARGO_PKG_VERSION");
+        assert!(generator
+            .generate()
+            .starts_with(&format!("// api.ts from \n//\n// ferro-type-gen {}\n\n", version)));
+        assert!(generator
+            .generate_for_module("my_crate::api", &["Request".to_string()])
+            .starts_with("// api.ts from my_crate::api\n//\n"));
+    }
+
+    #[test]
+    fn test_module_overrides() {
+        let string = || TypeDef::Primitive(Primitive::String);
+        let mut generator = Generator::new(
+            Config::new()
+                .header("{file}")
+                .module_output("my_crate::models::user", "users.ts")
+                .module_header("my_crate::api", "API for {module}"),
+        );
+        generator
+            .add(named_in("my_crate::models::user", "User", string()))
+            .add(named_in(
+                "my_crate::api",
+                "Request",
+                TypeDef::Object(vec![Field::new("user", TypeDef::Ref("User".into()))]),
+            ));
+
+        let files = generator.generate_multi_file().unwrap();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec![Path::new("api.ts"), Path::new("users.ts")]
+        );
+        assert!(files[Path::new("users.ts")].starts_with("// users.ts\n"));
+        let api = &files[Path::new("api.ts")];
+        assert!(api.starts_with("// API for my_crate::api\n"));
+        assert!(api.contains("import type { User } from \"./users\";"));
+
+        // Two modules cannot share a file
+        generator.config.modules.clear();
+        generator.config = generator.config.clone().module_output("my_crate::api", "models/user.ts");
+        let err = generator.generate_multi_file().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "modules my_crate::api and my_crate::models::user would both be written to models/user.ts"
+        );
+    }
+
+    #[test]
+    fn test_type_overrides() {
+        let mut generator = Generator::new(Config::new().type_override("Timestamp", "string"));
+        generator.add(TypeDef::Named {
+            namespace: vec![],
+            name: "Event".into(),
+            def: Box::new(TypeDef::Object(vec![Field::new(
+                "at",
+                named_in("my_crate", "Timestamp", TypeDef::Primitive(Primitive::Number)),
+            )])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        });
+
+        let output = generator.generate();
+        assert!(output.contains("export type Timestamp = string;"));
+        assert!(output.contains("export type Event = { at: Timestamp };"));
+    }
 }
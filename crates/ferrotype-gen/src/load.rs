@@ -0,0 +1,589 @@
+//! Loading [`Config`] from `ferrotype.toml` or `[package.metadata.ferrotype]`
+
+use crate::{BarrelStrategy, Config, ExportStyle, ModuleConfig};
+use ferro_type::{DeclarationStyle, Format, Indent, IntPolicy, Separator, Shape};
+use std::path::{Component, Path, PathBuf};
+use toml::{Table, Value};
+
+/// Placeholders allowed in header templates
+const PLACEHOLDERS: [&str; 3] = ["file", "module", "version"];
+
+/// An invalid or unreadable config
+///
+/// Displays as `path: `key`: message`, leaving out whichever of the path and
+/// key are unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// File the config was read from
+    pub path: Option<PathBuf>,
+    /// Dotted path of the offending key, e.g. `modules."my_crate::api".output`
+    pub key: Option<String>,
+    /// What is wrong
+    pub message: String,
+}
+
+impl ConfigError {
+    fn at(key: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: None,
+            key: Some(key.to_string()),
+            message: message.into(),
+        }
+    }
+
+    fn in_file(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ref path) = self.path {
+            write!(f, "{}: ", path.display())?;
+        }
+        if let Some(ref key) = self.key {
+            write!(f, "`{}`: ", key)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Parse a config in the `ferrotype.toml` format
+    ///
+    /// ```toml
+    /// output = "frontend/src/types.ts"      # or `output_dir` for multi-file
+    /// export_style = "named"                # "none", "named" or "grouped"
+    /// declaration_style = "type"            # "type" or "interface"
+    /// declaration_only = false
+    /// header = "Generated from {module} by ferro-type-gen {version}"
+    /// esm_extensions = false
+    /// deny_import_cycles = false
+    /// barrel = "error"                      # "error", "prefix" or "namespace"
+    /// include_utilities = false
+    /// type_guards = false
+    /// int64 = "string"                      # "number", "bigint", "string" or "number_or_string"
+    /// int128 = "string"
+    /// shape = "output"                      # "output" or "input"; omit for both
+    ///
+    /// [format]                              # pretty-print; omit for one line per type
+    /// indent = 4                            # spaces, or "tab"
+    /// max_width = 100
+    /// separator = "semicolon"               # "semicolon" or "comma"
+    /// split_discriminated_unions = true
+    ///
+    /// [modules."my_crate::api"]
+    /// output = "api/index.ts"               # relative to `output_dir`
+    /// header = "API types"
+    ///
+    /// [types]
+    /// Timestamp = "string"
+    /// ```
+    ///
+    /// Every key is optional, but `output` and `output_dir` are exclusive, and
+    /// `barrel` and module outputs require `output_dir`.
+    pub fn from_toml(content: &str) -> Result<Self, ConfigError> {
+        let table: Table = content.parse().map_err(|e: toml::de::Error| ConfigError {
+            path: None,
+            key: None,
+            message: e.to_string(),
+        })?;
+        parse_config(&table, "", Path::new(""))
+    }
+
+    /// Load a `ferrotype.toml`
+    ///
+    /// Relative `output` and `output_dir` paths are resolved against the
+    /// directory containing the file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let table = read_table(path)?;
+        parse_config(&table, "", path.parent().unwrap_or(Path::new("")))
+            .map_err(|e| e.in_file(path))
+    }
+
+    /// Load the `[package.metadata.ferrotype]` section of a `Cargo.toml`
+    ///
+    /// The section takes the same keys as `ferrotype.toml`. Returns `None`
+    /// if the manifest has no such section.
+    pub fn from_cargo_manifest(path: impl AsRef<Path>) -> Result<Option<Self>, ConfigError> {
+        let path = path.as_ref();
+        let table = read_table(path)?;
+        let section = table
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("ferrotype"));
+        let Some(section) = section else {
+            return Ok(None);
+        };
+
+        let prefix = "package.metadata.ferrotype";
+        let section = expect_table(section, prefix).map_err(|e| e.in_file(path))?;
+        parse_config(section, &format!("{}.", prefix), path.parent().unwrap_or(Path::new("")))
+            .map(Some)
+            .map_err(|e| e.in_file(path))
+    }
+
+    /// Load the config for the crate in `dir`
+    ///
+    /// Reads `ferrotype.toml` if it exists, and the `Cargo.toml` metadata
+    /// section otherwise. Suited to build scripts:
+    ///
+    /// ```ignore
+    /// let config = Config::discover(env!("CARGO_MANIFEST_DIR"))?;
+    /// ```
+    pub fn discover(dir: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let dir = dir.as_ref();
+        let file = dir.join("ferrotype.toml");
+        if file.exists() {
+            return Self::from_file(file);
+        }
+        let manifest = dir.join("Cargo.toml");
+        if manifest.exists() {
+            if let Some(config) = Self::from_cargo_manifest(manifest)? {
+                return Ok(config);
+            }
+        }
+        Err(ConfigError {
+            path: None,
+            key: None,
+            message: format!(
+                "no ferrotype.toml or [package.metadata.ferrotype] found in {}",
+                dir.display()
+            ),
+        })
+    }
+}
+
+fn read_table(path: &Path) -> Result<Table, ConfigError> {
+    let content = std::fs::read_to_string(path).map_err(|e| ConfigError {
+        path: Some(path.to_owned()),
+        key: None,
+        message: e.to_string(),
+    })?;
+    content.parse().map_err(|e: toml::de::Error| ConfigError {
+        path: Some(path.to_owned()),
+        key: None,
+        message: e.to_string(),
+    })
+}
+
+/// Builds a config from its table, naming keys after `prefix`
+fn parse_config(table: &Table, prefix: &str, base: &Path) -> Result<Config, ConfigError> {
+    let mut config = Config::new();
+
+    for (name, value) in table {
+        let key = format!("{}{}", prefix, key_segment(name));
+        match name.as_str() {
+            "output" => config.output = Some(base.join(expect_str(value, &key)?)),
+            "output_dir" => config.output_dir = Some(base.join(expect_str(value, &key)?)),
+            "export_style" => {
+                config.export_style = match expect_str(value, &key)? {
+                    "none" => ExportStyle::None,
+                    "named" => ExportStyle::Named,
+                    "grouped" => ExportStyle::Grouped,
+                    other => return Err(unknown_value(&key, other, &["none", "named", "grouped"])),
+                }
+            }
+            "declaration_style" => {
+                config.declaration_style = match expect_str(value, &key)? {
+                    "type" => DeclarationStyle::TypeAlias,
+                    "interface" => DeclarationStyle::Interface,
+                    other => return Err(unknown_value(&key, other, &["type", "interface"])),
+                }
+            }
+            "declaration_only" => config.declaration_only = expect_bool(value, &key)?,
+            "header" => config.header = Some(expect_template(value, &key)?),
+            "esm_extensions" => config.esm_extensions = expect_bool(value, &key)?,
+            "deny_import_cycles" => config.deny_import_cycles = expect_bool(value, &key)?,
+            "barrel" => {
+                config.barrel = Some(match expect_str(value, &key)? {
+                    "error" => BarrelStrategy::Error,
+                    "prefix" => BarrelStrategy::Prefix,
+                    "namespace" => BarrelStrategy::Namespace,
+                    other => return Err(unknown_value(&key, other, &["error", "prefix", "namespace"])),
+                })
+            }
+            "include_utilities" => config.include_utilities = expect_bool(value, &key)?,
+            "type_guards" => config.type_guards = expect_bool(value, &key)?,
+            "int64" => config.int64 = Some(parse_int_policy(value, &key)?),
+            "int128" => config.int128 = Some(parse_int_policy(value, &key)?),
+            "shape" => {
+                config.shape = Some(match expect_str(value, &key)? {
+                    "output" => Shape::Output,
+                    "input" => Shape::Input,
+                    other => return Err(unknown_value(&key, other, &["output", "input"])),
+                })
+            }
+            "format" => config.format = Some(parse_format(value, &key)?),
+            "modules" => {
+                for (module, value) in expect_table(value, &key)? {
+                    let key = format!("{}.{}", key, key_segment(module));
+                    config.modules.insert(module.clone(), parse_module(value, &key)?);
+                }
+            }
+            "types" => {
+                for (type_name, value) in expect_table(value, &key)? {
+                    let key = format!("{}.{}", key, key_segment(type_name));
+                    let ts = expect_str(value, &key)?;
+                    if ts.trim().is_empty() {
+                        return Err(ConfigError::at(&key, "expected a TypeScript type, found an empty string"));
+                    }
+                    config.type_overrides.insert(type_name.clone(), ts.to_string());
+                }
+            }
+            _ => {
+                return Err(ConfigError::at(
+                    &key,
+                    "unknown key; expected one of `output`, `output_dir`, `export_style`, \
+                     `declaration_style`, `declaration_only`, `header`, `esm_extensions`, \
+                     `deny_import_cycles`, `barrel`, `include_utilities`, `type_guards`, `int64`, `int128`, `shape`, \
+                     `format`, `modules` or `types`",
+                ))
+            }
+        }
+    }
+
+    // Options that only make sense together
+    let key = |name: &str| format!("{}{}", prefix, name);
+    if config.output.is_some() && config.output_dir.is_some() {
+        return Err(ConfigError::at(&key("output_dir"), "cannot be combined with `output`"));
+    }
+    if config.output_dir.is_none() {
+        if config.barrel.is_some() {
+            return Err(ConfigError::at(&key("barrel"), "requires `output_dir`"));
+        }
+        if let Some((module, _)) = config.modules.iter().find(|(_, m)| m.output.is_some()) {
+            let key = format!("{}.{}.output", key("modules"), key_segment(module));
+            return Err(ConfigError::at(&key, "requires `output_dir`"));
+        }
+    }
+
+    Ok(config)
+}
+
+fn parse_format(value: &Value, key: &str) -> Result<Format, ConfigError> {
+    let mut format = Format::new();
+    for (name, value) in expect_table(value, key)? {
+        let key = format!("{}.{}", key, key_segment(name));
+        match name.as_str() {
+            "indent" => {
+                format.indent = match value {
+                    Value::String(s) if s == "tab" => Indent::Tab,
+                    Value::String(s) => return Err(unknown_value(&key, s, &["tab"])),
+                    _ => Indent::Spaces(expect_usize(value, &key)?),
+                }
+            }
+            "max_width" => format.max_width = expect_usize(value, &key)?,
+            "separator" => {
+                format.separator = match expect_str(value, &key)? {
+                    "semicolon" => Separator::Semicolon,
+                    "comma" => Separator::Comma,
+                    other => return Err(unknown_value(&key, other, &["semicolon", "comma"])),
+                }
+            }
+            "split_discriminated_unions" => format.split_discriminated_unions = expect_bool(value, &key)?,
+            _ => {
+                return Err(ConfigError::at(
+                    &key,
+                    "unknown key; expected one of `indent`, `max_width`, `separator` or \
+                     `split_discriminated_unions`",
+                ));
+            }
+        }
+    }
+    Ok(format)
+}
+
+fn parse_int_policy(value: &Value, key: &str) -> Result<IntPolicy, ConfigError> {
+    match expect_str(value, key)? {
+        "number" => Ok(IntPolicy::Number),
+        "bigint" => Ok(IntPolicy::BigInt),
+        "string" => Ok(IntPolicy::String),
+        "number_or_string" => Ok(IntPolicy::NumberOrString),
+        other => Err(unknown_value(key, other, &["number", "bigint", "string", "number_or_string"])),
+    }
+}
+
+fn parse_module(value: &Value, key: &str) -> Result<ModuleConfig, ConfigError> {
+    let mut module = ModuleConfig::default();
+    for (name, value) in expect_table(value, key)? {
+        let key = format!("{}.{}", key, key_segment(name));
+        match name.as_str() {
+            "output" => {
+                let path = PathBuf::from(expect_str(value, &key)?);
+                let inside = path.components().all(|c| matches!(c, Component::Normal(_)));
+                if !inside || path.extension().is_none_or(|ext| ext != "ts") {
+                    return Err(ConfigError::at(
+                        &key,
+                        "expected a relative `.ts` path inside the output directory",
+                    ));
+                }
+                module.output = Some(path);
+            }
+            "header" => module.header = Some(expect_template(value, &key)?),
+            _ => {
+                return Err(ConfigError::at(&key, "unknown key; expected `output` or `header`"));
+            }
+        }
+    }
+    Ok(module)
+}
+
+/// Quotes a key segment that is not a bare TOML key
+fn key_segment(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+fn unknown_value(key: &str, value: &str, expected: &[&str]) -> ConfigError {
+    let expected: Vec<String> = expected.iter().map(|v| format!("\"{}\"", v)).collect();
+    ConfigError::at(
+        key,
+        format!("unknown value \"{}\"; expected one of {}", value, expected.join(", ")),
+    )
+}
+
+fn mismatch(key: &str, expected: &str, value: &Value) -> ConfigError {
+    ConfigError::at(key, format!("expected {}, found {}", expected, value.type_str()))
+}
+
+fn expect_str<'a>(value: &'a Value, key: &str) -> Result<&'a str, ConfigError> {
+    value.as_str().ok_or_else(|| mismatch(key, "a string", value))
+}
+
+fn expect_bool(value: &Value, key: &str) -> Result<bool, ConfigError> {
+    value.as_bool().ok_or_else(|| mismatch(key, "a boolean", value))
+}
+
+fn expect_usize(value: &Value, key: &str) -> Result<usize, ConfigError> {
+    value
+        .as_integer()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| mismatch(key, "a non-negative integer", value))
+}
+
+fn expect_table<'a>(value: &'a Value, key: &str) -> Result<&'a Table, ConfigError> {
+    value.as_table().ok_or_else(|| mismatch(key, "a table", value))
+}
+
+/// A header template, with every `{placeholder}` checked
+fn expect_template(value: &Value, key: &str) -> Result<String, ConfigError> {
+    let template = expect_str(value, key)?;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(ConfigError::at(
+                key,
+                format!(
+                    "unknown placeholder `{{{}}}`; expected `{{file}}`, `{{module}}` or `{{version}}`",
+                    name
+                ),
+            ));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    Ok(template.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(content: &str) -> String {
+        Config::from_toml(content).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml(
+            r#"
+            output_dir = "types"
+            export_style = "grouped"
+            declaration_style = "interface"
+            declaration_only = true
+            header = "{file} from {module}"
+            esm_extensions = true
+            deny_import_cycles = true
+            barrel = "prefix"
+            include_utilities = true
+            type_guards = true
+            int64 = "string"
+            int128 = "number_or_string"
+            shape = "input"
+
+            [format]
+            indent = "tab"
+            max_width = 80
+            separator = "comma"
+            split_discriminated_unions = false
+
+            [modules."my_crate::api"]
+            output = "api/index.ts"
+            header = "API"
+
+            [types]
+            Timestamp = "string"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.output, None);
+        assert_eq!(config.output_dir, Some(PathBuf::from("types")));
+        assert_eq!(config.export_style, ExportStyle::Grouped);
+        assert_eq!(config.declaration_style, DeclarationStyle::Interface);
+        assert!(config.declaration_only);
+        assert_eq!(config.header.as_deref(), Some("{file} from {module}"));
+        assert!(config.esm_extensions);
+        assert!(config.deny_import_cycles);
+        assert_eq!(config.barrel, Some(BarrelStrategy::Prefix));
+        assert!(config.include_utilities);
+        assert!(config.type_guards);
+        assert_eq!(config.int64, Some(IntPolicy::String));
+        assert_eq!(config.int128, Some(IntPolicy::NumberOrString));
+        assert_eq!(config.shape, Some(Shape::Input));
+        assert_eq!(
+            config.format,
+            Some(
+                Format::new()
+                    .indent(Indent::Tab)
+                    .max_width(80)
+                    .separator(Separator::Comma)
+                    .split_discriminated_unions(false)
+            )
+        );
+        assert_eq!(
+            config.modules["my_crate::api"],
+            ModuleConfig {
+                output: Some(PathBuf::from("api/index.ts")),
+                header: Some("API".into()),
+            }
+        );
+        assert_eq!(config.type_overrides["Timestamp"], "string");
+
+        // Everything is optional
+        assert_eq!(Config::from_toml("").unwrap().export_style, ExportStyle::Named);
+        assert_eq!(Config::from_toml("").unwrap().format, None);
+        assert_eq!(
+            Config::from_toml("[format]\nindent = 2").unwrap().format,
+            Some(Format::new().indent(Indent::Spaces(2)))
+        );
+    }
+
+    #[test]
+    fn test_errors_name_the_key() {
+        assert_eq!(
+            error("outptu = \"a.ts\""),
+            "`outptu`: unknown key; expected one of `output`, `output_dir`, `export_style`, \
+             `declaration_style`, `declaration_only`, `header`, `esm_extensions`, \
+             `deny_import_cycles`, `barrel`, `include_utilities`, `type_guards`, `int64`, `int128`, `shape`, \
+             `format`, `modules` or `types`"
+        );
+        assert_eq!(
+            error("export_style = \"default\""),
+            "`export_style`: unknown value \"default\"; expected one of \"none\", \"named\", \"grouped\""
+        );
+        assert_eq!(
+            error("declaration_style = \"class\""),
+            "`declaration_style`: unknown value \"class\"; expected one of \"type\", \"interface\""
+        );
+        assert_eq!(error("esm_extensions = \"yes\""), "`esm_extensions`: expected a boolean, found string");
+        assert_eq!(error("output = 1"), "`output`: expected a string, found integer");
+        assert_eq!(
+            error("[format]\nmax_width = -1"),
+            "`format.max_width`: expected a non-negative integer, found integer"
+        );
+        assert_eq!(
+            error("[format]\nindent = \"spaces\""),
+            "`format.indent`: unknown value \"spaces\"; expected one of \"tab\""
+        );
+        assert_eq!(
+            error("header = \"{crate}\""),
+            "`header`: unknown placeholder `{crate}`; expected `{file}`, `{module}` or `{version}`"
+        );
+        assert_eq!(
+            error("output_dir = \"t\"\n[modules.\"my_crate::api\"]\noutput = \"../api.ts\""),
+            "`modules.\"my_crate::api\".output`: expected a relative `.ts` path inside the output directory"
+        );
+        assert_eq!(
+            error("[modules.api]\nfile = \"api.ts\""),
+            "`modules.api.file`: unknown key; expected `output` or `header`"
+        );
+        assert_eq!(
+            error("[types]\nTimestamp = \" \""),
+            "`types.Timestamp`: expected a TypeScript type, found an empty string"
+        );
+        assert!(error("output = ").contains("line 1"));
+    }
+
+    #[test]
+    fn test_conflicting_keys() {
+        assert_eq!(
+            error("output = \"a.ts\"\noutput_dir = \"b\""),
+            "`output_dir`: cannot be combined with `output`"
+        );
+        assert_eq!(error("output = \"a.ts\"\nbarrel = \"error\""), "`barrel`: requires `output_dir`");
+        assert_eq!(
+            error("[modules.\"my_crate::api\"]\noutput = \"api.ts\""),
+            "`modules.\"my_crate::api\".output`: requires `output_dir`"
+        );
+    }
+
+    #[test]
+    fn test_from_file_and_cargo_manifest() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest = temp_dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"api\"\n\n[package.metadata.ferrotype]\noutput = \"web/types.ts\"\n",
+        )
+        .unwrap();
+
+        // Paths are relative to the config file
+        let config = Config::from_cargo_manifest(&manifest).unwrap().unwrap();
+        assert_eq!(config.output, Some(temp_dir.path().join("web/types.ts")));
+        assert_eq!(
+            Config::discover(temp_dir.path()).unwrap().output,
+            Some(temp_dir.path().join("web/types.ts"))
+        );
+
+        // ferrotype.toml takes precedence
+        let file = temp_dir.path().join("ferrotype.toml");
+        std::fs::write(&file, "output_dir = \"gen\"\nexport_style = 1\n").unwrap();
+        let err = Config::discover(temp_dir.path()).unwrap_err();
+        assert_eq!(err.path, Some(file.clone()));
+        assert_eq!(err.key.as_deref(), Some("export_style"));
+        std::fs::write(&file, "output_dir = \"gen\"\n").unwrap();
+        assert_eq!(
+            Config::discover(temp_dir.path()).unwrap().output_dir,
+            Some(temp_dir.path().join("gen"))
+        );
+
+        // Keys in the manifest are named in full
+        std::fs::write(&manifest, "[package.metadata.ferrotype]\nbarrel = \"flat\"\n").unwrap();
+        let err = Config::from_cargo_manifest(&manifest).unwrap_err();
+        assert_eq!(err.key.as_deref(), Some("package.metadata.ferrotype.barrel"));
+
+        std::fs::write(&manifest, "[package]\nname = \"api\"\n").unwrap();
+        assert!(Config::from_cargo_manifest(&manifest).unwrap().is_none());
+        std::fs::remove_file(&file).unwrap();
+        assert!(Config::discover(temp_dir.path())
+            .unwrap_err()
+            .message
+            .starts_with("no ferrotype.toml or [package.metadata.ferrotype] found"));
+    }
+}
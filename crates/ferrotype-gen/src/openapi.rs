@@ -0,0 +1,629 @@
+//! OpenAPI 3.1 document generation from a [`TypeRegistry`]
+
+use crate::json_schema::JsonSchema;
+use ferro_type::{TypeDef, TypeRegistry, TS};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Renders the `components.schemas` of an OpenAPI 3.1 document
+///
+/// OpenAPI 3.1 schemas are JSON Schema, so each type is defined as by
+/// [`render_json_schema`](crate::render_json_schema), with `$ref`s into
+/// `#/components/schemas/`. Named tagged enums also carry a `discriminator`
+/// naming their tag field, with each variant defined as its own schema,
+/// `{Name}_{tag value}`, that the discriminator's `mapping` points to.
+pub fn render_openapi_schemas(registry: &TypeRegistry) -> Value {
+    Value::Object(JsonSchema::new(registry, "#/components/schemas/").discriminators().definitions())
+}
+
+/// An OpenAPI 3.1 document whose schemas come from Rust types
+///
+/// Operations refer to their request and response bodies by Rust type, and
+/// those types are registered as the document's schemas along with any
+/// added with [`OpenApi::register`].
+///
+/// # Example
+///
+/// ```ignore
+/// use ferro_type_gen::{Method, OpenApi, Operation, Parameter};
+///
+/// let mut api = OpenApi::new("Users", "1.0.0");
+/// api.operation(
+///     Method::Get,
+///     "/users/{id}",
+///     Operation::new("getUser")
+///         .parameter(Parameter::path::<String>("id"))
+///         .response::<User>(200, "The user"),
+/// );
+/// api.operation(
+///     Method::Post,
+///     "/users",
+///     Operation::new("createUser")
+///         .request::<CreateUser>()
+///         .response::<User>(201, "The created user"),
+/// );
+/// std::fs::write("openapi.yaml", api.to_yaml())?;
+/// ```
+#[derive(Debug, Default)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    servers: Vec<String>,
+    paths: BTreeMap<String, BTreeMap<Method, Operation>>,
+    registry: TypeRegistry,
+}
+
+impl OpenApi {
+    /// Create a document for the API with the given title and version
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the description of the API
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a server URL the API is served from
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    /// Register a type as a schema, even if no operation uses it
+    pub fn register<T: TS>(&mut self) -> &mut Self {
+        self.registry.register::<T>();
+        self
+    }
+
+    /// Get a mutable reference to the registry of schemas
+    ///
+    /// This is where type overrides and the declaration of other types go.
+    pub fn registry_mut(&mut self) -> &mut TypeRegistry {
+        &mut self.registry
+    }
+
+    /// Add an operation on a path, such as `/users/{id}`
+    ///
+    /// The types of the operation's parameters and bodies are registered as
+    /// schemas. An operation replaces any with the same method and path.
+    pub fn operation(&mut self, method: Method, path: impl Into<String>, operation: Operation) -> &mut Self {
+        let types = operation
+            .parameters
+            .iter()
+            .map(|p| &p.schema)
+            .chain(&operation.request)
+            .chain(operation.responses.values().flat_map(|r| &r.body));
+        for typedef in types {
+            self.registry.add_typedef(typedef.clone());
+        }
+        self.paths.entry(path.into()).or_default().insert(method, operation);
+        self
+    }
+
+    /// Render the document
+    ///
+    /// Object keys are sorted, so the output is stable across runs.
+    pub fn render(&self) -> Value {
+        let schemas = JsonSchema::new(&self.registry, "#/components/schemas/").discriminators();
+
+        let mut paths = Map::new();
+        for (path, operations) in &self.paths {
+            let mut item = Map::new();
+            for (method, operation) in operations {
+                item.insert(method.as_str().to_string(), operation.render(&schemas));
+            }
+            paths.insert(path.clone(), Value::Object(item));
+        }
+
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+        let mut document = json!({
+            "openapi": OPENAPI_VERSION,
+            "info": info,
+            "paths": paths,
+            "components": { "schemas": schemas.definitions() },
+        });
+        if !self.servers.is_empty() {
+            let servers: Vec<_> = self.servers.iter().map(|url| json!({ "url": url })).collect();
+            document["servers"] = json!(servers);
+        }
+        document
+    }
+
+    /// Render the document as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.render()).expect("OpenAPI serialization failed") + "\n"
+    }
+
+    /// Render the document as YAML
+    pub fn to_yaml(&self) -> String {
+        let mut output = String::new();
+        write_yaml(&mut output, &self.render(), 0);
+        output
+    }
+}
+
+/// The OpenAPI version of the generated documents
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// An HTTP method of an [`Operation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Method {
+    /// `GET`
+    Get,
+    /// `PUT`
+    Put,
+    /// `POST`
+    Post,
+    /// `DELETE`
+    Delete,
+    /// `OPTIONS`
+    Options,
+    /// `HEAD`
+    Head,
+    /// `PATCH`
+    Patch,
+    /// `TRACE`
+    Trace,
+}
+
+impl Method {
+    /// The method as it is written in an OpenAPI path item
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Put => "put",
+            Method::Post => "post",
+            Method::Delete => "delete",
+            Method::Options => "options",
+            Method::Head => "head",
+            Method::Patch => "patch",
+            Method::Trace => "trace",
+        }
+    }
+}
+
+/// An API operation, added to an [`OpenApi`] document
+#[derive(Debug, Clone, Default)]
+pub struct Operation {
+    operation_id: String,
+    summary: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
+    parameters: Vec<Parameter>,
+    request: Option<TypeDef>,
+    responses: BTreeMap<u16, Response>,
+}
+
+#[derive(Debug, Clone)]
+struct Response {
+    description: String,
+    body: Option<TypeDef>,
+}
+
+impl Operation {
+    /// Create an operation with the given `operationId`
+    pub fn new(operation_id: impl Into<String>) -> Self {
+        Self {
+            operation_id: operation_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set a short summary of the operation
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Set a description of the operation
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a tag grouping the operation with others
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Add a path, query or header parameter
+    pub fn parameter(mut self, parameter: Parameter) -> Self {
+        self.parameters.push(parameter);
+        self
+    }
+
+    /// Set the JSON request body to a `T`
+    pub fn request<T: TS>(mut self) -> Self {
+        self.request = Some(T::typescript());
+        self
+    }
+
+    /// Add a response with a JSON body of a `T`
+    pub fn response<T: TS>(mut self, status: u16, description: impl Into<String>) -> Self {
+        let response = Response {
+            description: description.into(),
+            body: Some(T::typescript()),
+        };
+        self.responses.insert(status, response);
+        self
+    }
+
+    /// Add a response without a body, such as `204 No Content`
+    pub fn empty_response(mut self, status: u16, description: impl Into<String>) -> Self {
+        let response = Response {
+            description: description.into(),
+            body: None,
+        };
+        self.responses.insert(status, response);
+        self
+    }
+
+    fn render(&self, schemas: &JsonSchema) -> Value {
+        let mut operation = json!({ "operationId": self.operation_id });
+        if let Some(summary) = &self.summary {
+            operation["summary"] = json!(summary);
+        }
+        if let Some(description) = &self.description {
+            operation["description"] = json!(description);
+        }
+        if !self.tags.is_empty() {
+            operation["tags"] = json!(self.tags);
+        }
+        if !self.parameters.is_empty() {
+            let parameters: Vec<_> = self.parameters.iter().map(|p| p.render(schemas)).collect();
+            operation["parameters"] = json!(parameters);
+        }
+        if let Some(request) = &self.request {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": json_content(schemas.schema(request, &[])),
+            });
+        }
+        let mut responses = Map::new();
+        for (status, response) in &self.responses {
+            let mut rendered = json!({ "description": response.description });
+            if let Some(body) = &response.body {
+                rendered["content"] = json_content(schemas.schema(body, &[]));
+            }
+            responses.insert(status.to_string(), rendered);
+        }
+        operation["responses"] = Value::Object(responses);
+        operation
+    }
+}
+
+/// A parameter of an [`Operation`]
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    name: String,
+    location: &'static str,
+    required: bool,
+    description: Option<String>,
+    schema: TypeDef,
+}
+
+impl Parameter {
+    /// A parameter in the path, such as `id` in `/users/{id}`
+    pub fn path<T: TS>(name: impl Into<String>) -> Self {
+        Self::new::<T>(name, "path", true)
+    }
+
+    /// An optional query string parameter
+    pub fn query<T: TS>(name: impl Into<String>) -> Self {
+        Self::new::<T>(name, "query", false)
+    }
+
+    /// An optional header parameter
+    pub fn header<T: TS>(name: impl Into<String>) -> Self {
+        Self::new::<T>(name, "header", false)
+    }
+
+    fn new<T: TS>(name: impl Into<String>, location: &'static str, required: bool) -> Self {
+        Self {
+            name: name.into(),
+            location,
+            required,
+            description: None,
+            schema: T::typescript(),
+        }
+    }
+
+    /// Make the parameter required
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Set a description of the parameter
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    fn render(&self, schemas: &JsonSchema) -> Value {
+        let mut parameter = json!({
+            "name": self.name,
+            "in": self.location,
+            "schema": schemas.schema(&self.schema, &[]),
+        });
+        if self.required {
+            parameter["required"] = json!(true);
+        }
+        if let Some(description) = &self.description {
+            parameter["description"] = json!(description);
+        }
+        parameter
+    }
+}
+
+fn json_content(schema: Value) -> Value {
+    json!({ "application/json": { "schema": schema } })
+}
+
+/// Writes a JSON value as block-style YAML
+///
+/// Strings are written as double-quoted JSON strings, which YAML reads
+/// back unchanged, and keys are only quoted when they could be misread.
+fn write_yaml(output: &mut String, value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                output.push_str(&pad);
+                output.push_str(&yaml_key(key));
+                output.push(':');
+                write_yaml_nested(output, value, indent);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                output.push_str(&pad);
+                output.push('-');
+                match item {
+                    Value::Object(map) if !map.is_empty() => {
+                        // The first key shares the line of the dash
+                        let mut nested = String::new();
+                        write_yaml(&mut nested, item, indent + 1);
+                        output.push(' ');
+                        output.push_str(nested.trim_start());
+                    }
+                    _ => write_yaml_nested(output, item, indent),
+                }
+            }
+        }
+        scalar => {
+            output.push_str(&pad);
+            output.push_str(&scalar.to_string());
+            output.push('\n');
+        }
+    }
+}
+
+/// Writes the value of a mapping key or sequence item after its marker
+fn write_yaml_nested(output: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            output.push('\n');
+            write_yaml(output, value, indent + 1);
+        }
+        Value::Array(items) if !items.is_empty() => {
+            output.push('\n');
+            write_yaml(output, value, indent + 1);
+        }
+        scalar => {
+            output.push(' ');
+            output.push_str(&scalar.to_string());
+            output.push('\n');
+        }
+    }
+}
+
+/// A mapping key, quoted unless it is a plain identifier-like string
+fn yaml_key(key: &str) -> String {
+    let plain = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || "_$/".contains(c))
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || "_$.-/".contains(c))
+        && !matches!(key, "true" | "false" | "null" | "yes" | "no" | "on" | "off");
+    if plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferro_type::{Field, Literal, Primitive};
+
+    fn named(name: &str, def: TypeDef) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(def),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    struct User;
+
+    impl TS for User {
+        fn typescript() -> TypeDef {
+            named("User", TypeDef::Object(vec![Field::new("name", TypeDef::Primitive(Primitive::String))]))
+        }
+    }
+
+    struct Event;
+
+    impl TS for Event {
+        fn typescript() -> TypeDef {
+            let variant = |kind: &str| {
+                TypeDef::Object(vec![Field::new("type", TypeDef::Literal(Literal::String(kind.into())))])
+            };
+            named("Event", TypeDef::Union(vec![variant("created"), variant("deleted")]))
+        }
+    }
+
+    fn api() -> OpenApi {
+        let mut api = OpenApi::new("Users", "1.0.0").server("https://api.example.com");
+        api.operation(
+            Method::Get,
+            "/users/{id}",
+            Operation::new("getUser")
+                .tag("users")
+                .parameter(Parameter::path::<String>("id"))
+                .response::<User>(200, "The user")
+                .empty_response(404, "No such user"),
+        );
+        api.operation(Method::Post, "/users", Operation::new("createUser").request::<User>());
+        api
+    }
+
+    #[test]
+    fn test_render_schemas() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Event>();
+        let variant = |kind: &str| {
+            json!({ "type": "object", "properties": { "type": { "const": kind } }, "required": ["type"] })
+        };
+        assert_eq!(
+            render_openapi_schemas(&registry),
+            json!({
+                "Event": {
+                    "oneOf": [
+                        { "$ref": "#/components/schemas/Event_created" },
+                        { "$ref": "#/components/schemas/Event_deleted" },
+                    ],
+                    "discriminator": {
+                        "propertyName": "type",
+                        "mapping": {
+                            "created": "#/components/schemas/Event_created",
+                            "deleted": "#/components/schemas/Event_deleted",
+                        },
+                    },
+                },
+                "Event_created": variant("created"),
+                "Event_deleted": variant("deleted"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_schemas_inline_union() {
+        struct Log;
+        impl TS for Log {
+            fn typescript() -> TypeDef {
+                let variant = |kind: &str| {
+                    TypeDef::Object(vec![Field::new("kind", TypeDef::Literal(Literal::String(kind.into())))])
+                };
+                named("Log", TypeDef::Object(vec![Field::new(
+                    "entry",
+                    TypeDef::Union(vec![variant("info"), variant("error")]),
+                )]))
+            }
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Log>();
+        let entry = &render_openapi_schemas(&registry)["Log"]["properties"]["entry"];
+        assert!(entry.get("discriminator").is_none());
+        assert_eq!(entry["oneOf"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_render_document_discriminator() {
+        let mut api = OpenApi::new("Events", "1.0.0");
+        api.operation(Method::Post, "/events", Operation::new("createEvent").request::<Event>());
+        let schemas = &api.render()["components"]["schemas"];
+        let discriminator = &schemas["Event"]["discriminator"];
+        assert_eq!(discriminator["propertyName"], json!("type"));
+        for (value, target) in discriminator["mapping"].as_object().unwrap() {
+            let component = target.as_str().unwrap().strip_prefix("#/components/schemas/").unwrap();
+            assert_eq!(schemas[component]["properties"]["type"]["const"], json!(value));
+        }
+    }
+
+    #[test]
+    fn test_render_document() {
+        let document = api().render();
+        assert_eq!(document["openapi"], json!("3.1.0"));
+        assert_eq!(document["servers"], json!([{ "url": "https://api.example.com" }]));
+        assert_eq!(
+            document["paths"]["/users/{id}"]["get"],
+            json!({
+                "operationId": "getUser",
+                "tags": ["users"],
+                "parameters": [
+                    { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+                ],
+                "responses": {
+                    "200": {
+                        "description": "The user",
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/User" } },
+                        },
+                    },
+                    "404": { "description": "No such user" },
+                },
+            })
+        );
+        assert_eq!(
+            document["paths"]["/users"]["post"]["requestBody"]["content"]["application/json"]["schema"],
+            json!({ "$ref": "#/components/schemas/User" })
+        );
+        assert!(document["components"]["schemas"]["User"].is_object());
+    }
+
+    #[test]
+    fn test_to_yaml() {
+        let mut api = OpenApi::new("Users", "1.0.0");
+        api.operation(
+            Method::Get,
+            "/users",
+            Operation::new("listUsers").tag("users").response::<User>(200, "The users"),
+        );
+        assert_eq!(
+            api.to_yaml(),
+            r##"components:
+  schemas:
+    User:
+      properties:
+        name:
+          type: "string"
+      required:
+        - "name"
+      type: "object"
+info:
+  title: "Users"
+  version: "1.0.0"
+openapi: "3.1.0"
+paths:
+  /users:
+    get:
+      operationId: "listUsers"
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/User"
+          description: "The users"
+      tags:
+        - "users"
+"##
+        );
+    }
+}
@@ -0,0 +1,600 @@
+//! Zod schema generation from a [`TypeRegistry`]
+
+use ferro_type::{EnumStyle, Literal, Primitive, TypeDef, TypeRegistry};
+
+/// Renders a Zod schema for every type in `registry`, in dependency order
+///
+/// Each type `User` becomes `export const UserSchema = z.object({ ... });`
+/// followed by `export type User = z.infer<typeof UserSchema>;`. Some types
+/// are declared in TypeScript instead of inferred from their schema:
+///
+/// - Recursive types, whose schema is wrapped in `z.lazy` and annotated as
+///   `z.ZodType<Tree>`
+/// - Generic types, whose schema is a function of the parameters' schemas:
+///   `<T extends z.ZodTypeAny>(T: T) => z.object({ ... })`
+/// - Enums, checked with `z.nativeEnum`; a `const enum` is declared as a
+///   regular `enum` so that it exists at runtime
+///
+/// Types without a schema, such as raw TypeScript overrides or types
+/// referenced with `#[ts(extends)]`, are only checked by TypeScript, with
+/// `z.custom<T>()`.
+pub fn render_zod(registry: &TypeRegistry) -> String {
+    let zod = Zod { registry };
+    let mut output = String::from("import { z } from \"zod\";\n\n");
+    for name in registry.sorted_types() {
+        let Some(typedef) = registry.get(name) else {
+            continue;
+        };
+        output.push_str(&zod.declaration(name, typedef));
+        output.push_str("\n\n");
+    }
+    output.trim_end().to_string() + "\n"
+}
+
+/// The name of the schema constant for the type `name`
+///
+/// Namespaces are joined into the name, so `VM.Git.State` is checked by
+/// `VMGitStateSchema`.
+pub fn schema_name(name: &str) -> String {
+    format!("{}Schema", name.replace('.', ""))
+}
+
+struct Zod<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl Zod<'_> {
+    /// Renders the schema of a registered type, with its TypeScript type
+    fn declaration(&self, name: &str, typedef: &TypeDef) -> String {
+        let schema = schema_name(name);
+        match typedef {
+            TypeDef::Named { namespace, name: type_name, def, wrapper, .. } => {
+                if let TypeDef::Enum { members, style } = def.as_ref() {
+                    // `z.nativeEnum` needs the enum object at runtime
+                    let style = match style {
+                        EnumStyle::ConstEnum => EnumStyle::Enum,
+                        style => *style,
+                    };
+                    let mut declaration = typedef.clone();
+                    if let TypeDef::Named { def, .. } = &mut declaration {
+                        **def = TypeDef::Enum { members: members.clone(), style };
+                    }
+                    return format!(
+                        "{}\nexport const {} = z.nativeEnum({});",
+                        declaration.render_exported_declaration(),
+                        schema,
+                        name
+                    );
+                }
+
+                let mut expr = self.schema(def);
+                if let Some(wrapper) = wrapper {
+                    expr = self.wrap(wrapper, expr, typedef);
+                }
+                if self.registry.is_recursive(name) {
+                    format!(
+                        "{}\nexport const {}: z.ZodType<{}> = z.lazy(() => {});",
+                        typedef.render_exported_declaration(),
+                        schema,
+                        name,
+                        expr
+                    )
+                } else {
+                    let inferred = format!("export type {} = z.infer<typeof {}>;", type_name, schema);
+                    format!(
+                        "export const {} = {};\n{}",
+                        schema,
+                        expr,
+                        in_namespace(namespace, &inferred)
+                    )
+                }
+            }
+            TypeDef::GenericDef { type_params, def, .. } => {
+                let params: Vec<_> = type_params
+                    .iter()
+                    .map(|p| format!("{} extends z.ZodTypeAny", p.name))
+                    .collect();
+                let args: Vec<_> = type_params.iter().map(|p| format!("{0}: {0}", p.name)).collect();
+                format!(
+                    "{}\nexport const {} = <{}>({}) => {};",
+                    typedef.render_exported_declaration(),
+                    schema,
+                    params.join(", "),
+                    args.join(", "),
+                    self.schema(def)
+                )
+            }
+            other => format!("export const {} = {};", schema, self.schema(other)),
+        }
+    }
+
+    /// Renders the schema expression for a type
+    fn schema(&self, typedef: &TypeDef) -> String {
+        match typedef {
+            TypeDef::Primitive(p) => primitive(*p).to_string(),
+            TypeDef::Array(inner) => format!("z.array({})", self.schema(inner)),
+            TypeDef::Tuple(items) => format!("z.tuple([{}])", self.list(items)),
+            TypeDef::Object(fields) => {
+                if fields.is_empty() {
+                    return "z.object({})".to_string();
+                }
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|f| {
+                        let optional = if f.optional { ".optional()" } else { "" };
+                        format!("{}: {}{}", property_key(&f.name), self.schema(&f.ty), optional)
+                    })
+                    .collect();
+                format!("z.object({{ {} }})", fields.join(", "))
+            }
+            TypeDef::Union(variants) => self.union(variants),
+            TypeDef::Intersection(types) => {
+                let mut schemas = types.iter().map(|t| self.schema(t));
+                let first = schemas.next().unwrap_or_else(|| "z.unknown()".to_string());
+                schemas.fold(first, |acc, s| format!("z.intersection({}, {})", acc, s))
+            }
+            TypeDef::Record { key, value } => {
+                format!("z.record({}, {})", self.schema(key), self.schema(value))
+            }
+            TypeDef::Named { namespace, name, .. } => {
+                let qualified = if namespace.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", namespace.join("."), name)
+                };
+                self.reference(&qualified, typedef)
+            }
+            TypeDef::Ref(name) => self.reference(name, typedef),
+            TypeDef::Literal(lit) => format!("z.literal({})", lit.render()),
+            TypeDef::Generic { base, args, .. } => {
+                if self.registry.get(base).is_some() {
+                    format!("{}({})", schema_name(base), self.list(args))
+                } else {
+                    let inner = match args.as_slice() {
+                        [inner] => utility(base, self.schema(inner)),
+                        _ => None,
+                    };
+                    inner.unwrap_or_else(|| custom(typedef))
+                }
+            }
+            TypeDef::IndexedAccess { base, key } if self.registry.get(base).is_some() => {
+                format!("{}.shape[{}]", schema_name(base), Literal::String(key.clone()).render())
+            }
+            TypeDef::TemplateLiteral { strings, types } => {
+                format!("z.string().regex(/{}/)", template_regex(strings, types).replace('/', "\\/"))
+            }
+            TypeDef::GenericDef { name, .. } => schema_name(name),
+            TypeDef::TypeParamRef(name) => name.clone(),
+            TypeDef::Enum { members, .. } => {
+                let values: Vec<_> = members.iter().map(|m| TypeDef::Literal(m.value.clone())).collect();
+                self.union(&values)
+            }
+            TypeDef::LargeInt { width, policy } => self.schema(&width.repr(*policy)),
+            TypeDef::Function { .. } | TypeDef::IndexedAccess { .. } => custom(typedef),
+        }
+    }
+
+    /// Renders the schema of a union, using the most specific Zod schema
+    ///
+    /// - `T | null` becomes `T.nullable()`, and `T | undefined` `T.optional()`
+    /// - String literals become `z.enum([...])`
+    /// - Objects sharing a literal tag field become `z.discriminatedUnion`
+    fn union(&self, variants: &[TypeDef]) -> String {
+        match variants {
+            [] => return "z.never()".to_string(),
+            [single] => return self.schema(single),
+            [a, b] => {
+                let nullish = |t: &TypeDef| match t {
+                    TypeDef::Primitive(Primitive::Null) => Some("nullable"),
+                    TypeDef::Primitive(Primitive::Undefined) => Some("optional"),
+                    _ => None,
+                };
+                match (nullish(a), nullish(b)) {
+                    (None, Some(method)) => return format!("{}.{}()", self.schema(a), method),
+                    (Some(method), None) => return format!("{}.{}()", self.schema(b), method),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+
+        let strings: Option<Vec<_>> = variants
+            .iter()
+            .map(|v| match v {
+                TypeDef::Literal(lit @ Literal::String(_)) => Some(lit.render()),
+                _ => None,
+            })
+            .collect();
+        if let Some(strings) = strings {
+            return format!("z.enum([{}])", strings.join(", "));
+        }
+
+        match discriminant(variants) {
+            Some(tag) => format!(
+                "z.discriminatedUnion({}, [{}])",
+                Literal::String(tag.to_string()).render(),
+                self.list(variants)
+            ),
+            None => format!("z.union([{}])", self.list(variants)),
+        }
+    }
+
+    /// Refers to the schema of a registered type, or accepts any value of
+    /// an unknown one
+    fn reference(&self, name: &str, typedef: &TypeDef) -> String {
+        if self.registry.get(name).is_some() {
+            schema_name(name)
+        } else {
+            custom(typedef)
+        }
+    }
+
+    /// Applies a wrapper such as `"Prettify<Readonly<"` to a schema
+    fn wrap(&self, wrapper: &str, expr: String, typedef: &TypeDef) -> String {
+        let mut expr = Some(expr);
+        for base in wrapper.split('<').map(str::trim).filter(|s| !s.is_empty()).rev() {
+            expr = expr.and_then(|e| utility(base, e));
+        }
+        expr.unwrap_or_else(|| match typedef {
+            TypeDef::Named { def, .. } => format!("z.custom<{}>()", render_wrapped(wrapper, def)),
+            _ => custom(typedef),
+        })
+    }
+
+    fn list(&self, items: &[TypeDef]) -> String {
+        let schemas: Vec<_> = items.iter().map(|t| self.schema(t)).collect();
+        schemas.join(", ")
+    }
+}
+
+/// Renders `def` wrapped in utility types, as a TypeScript type
+fn render_wrapped(wrapper: &str, def: &TypeDef) -> String {
+    let mut rendered = def.render();
+    for base in wrapper.split('<').map(str::trim).filter(|s| !s.is_empty()).rev() {
+        rendered = format!("{}<{}>", base, rendered);
+    }
+    rendered
+}
+
+/// Applies a TypeScript utility type to a schema, if Zod has an equivalent
+fn utility(base: &str, expr: String) -> Option<String> {
+    match base {
+        "Prettify" => Some(expr),
+        "Readonly" => Some(format!("{}.readonly()", expr)),
+        "Partial" => Some(format!("{}.partial()", expr)),
+        "Required" => Some(format!("{}.required()", expr)),
+        _ => None,
+    }
+}
+
+/// A schema that accepts any value, typed as the TypeScript type of `typedef`
+fn custom(typedef: &TypeDef) -> String {
+    format!("z.custom<{}>()", typedef.render())
+}
+
+fn primitive(p: Primitive) -> &'static str {
+    match p {
+        Primitive::String => "z.string()",
+        Primitive::Number => "z.number()",
+        Primitive::Boolean => "z.boolean()",
+        Primitive::Null => "z.null()",
+        Primitive::Undefined => "z.undefined()",
+        Primitive::Void => "z.void()",
+        Primitive::Never => "z.never()",
+        Primitive::Any => "z.any()",
+        Primitive::Unknown => "z.unknown()",
+        Primitive::BigInt => "z.bigint()",
+    }
+}
+
+/// The field every variant has with a distinct literal type, if any
+pub(crate) fn discriminant(variants: &[TypeDef]) -> Option<&str> {
+    let TypeDef::Object(first) = variants.first()? else {
+        return None;
+    };
+    first.iter().map(|f| f.name.as_str()).find(|tag| {
+        let mut values = Vec::new();
+        variants.iter().all(|v| {
+            let TypeDef::Object(fields) = v else {
+                return false;
+            };
+            match fields.iter().find(|f| f.name == *tag) {
+                Some(f) if !f.optional => match &f.ty {
+                    TypeDef::Literal(lit) => {
+                        let value = lit.render();
+                        let unique = !values.contains(&value);
+                        values.push(value);
+                        unique
+                    }
+                    _ => false,
+                },
+                _ => false,
+            }
+        })
+    })
+}
+
+/// The regular expression matching the strings of a template literal type
+pub(crate) fn template_regex(strings: &[String], types: &[Box<TypeDef>]) -> String {
+    let mut pattern = String::from("^");
+    for (i, s) in strings.iter().enumerate() {
+        pattern.push_str(&escape_regex(s));
+        if let Some(ty) = types.get(i) {
+            pattern.push_str(&template_pattern(ty));
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// The regular expression matching the values of a template literal placeholder
+fn template_pattern(ty: &TypeDef) -> String {
+    match ty {
+        TypeDef::Primitive(Primitive::Number) => r"-?\d+(?:\.\d+)?".to_string(),
+        TypeDef::Primitive(Primitive::BigInt) => r"-?\d+".to_string(),
+        TypeDef::Primitive(Primitive::Boolean) => "(?:true|false)".to_string(),
+        TypeDef::Primitive(Primitive::Null) => "null".to_string(),
+        TypeDef::Primitive(Primitive::Undefined) => "undefined".to_string(),
+        TypeDef::Literal(Literal::String(s)) => escape_regex(s),
+        TypeDef::Literal(lit) => escape_regex(&lit.render()),
+        TypeDef::Union(variants) => {
+            let patterns: Vec<_> = variants.iter().map(template_pattern).collect();
+            format!("(?:{})", patterns.join("|"))
+        }
+        TypeDef::Enum { members, .. } => {
+            let patterns: Vec<_> = members.iter().map(|m| template_pattern(&TypeDef::Literal(m.value.clone()))).collect();
+            format!("(?:{})", patterns.join("|"))
+        }
+        TypeDef::LargeInt { width, policy } => template_pattern(&width.repr(*policy)),
+        // Named types in map keys, such as `${Level}`
+        TypeDef::Named { def, .. } => template_pattern(def),
+        _ => ".*".to_string(),
+    }
+}
+
+/// Escapes text for a JavaScript regular expression
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// An object key, quoted unless it is a valid identifier
+fn property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        Literal::String(name.to_string()).render()
+    }
+}
+
+/// Wraps a declaration in `export namespace` blocks
+fn in_namespace(namespace: &[String], declaration: &str) -> String {
+    let mut result = String::new();
+    for (depth, ns) in namespace.iter().enumerate() {
+        result.push_str(&"    ".repeat(depth));
+        result.push_str(&format!("export namespace {} {{\n", ns));
+    }
+    result.push_str(&"    ".repeat(namespace.len()));
+    result.push_str(declaration);
+    for depth in (0..namespace.len()).rev() {
+        result.push('\n');
+        result.push_str(&"    ".repeat(depth));
+        result.push('}');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferro_type::{Docs, EnumMember, Field, TypeParam};
+
+    fn named(name: &str, def: TypeDef) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(def),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    fn string() -> TypeDef {
+        TypeDef::Primitive(Primitive::String)
+    }
+
+    fn literal(value: &str) -> TypeDef {
+        TypeDef::Literal(Literal::String(value.to_string()))
+    }
+
+    fn render(typedefs: Vec<TypeDef>) -> String {
+        let mut registry = TypeRegistry::new();
+        for typedef in typedefs {
+            registry.add_typedef(typedef);
+        }
+        render_zod(&registry)
+    }
+
+    #[test]
+    fn test_object_schema() {
+        let user = named(
+            "User",
+            TypeDef::Object(vec![
+                Field::new("id", string()).with_docs(Docs::new("Unique id.")),
+                Field::optional("nickname", TypeDef::Union(vec![string(), TypeDef::Primitive(Primitive::Null)])),
+                Field::new("created-at", TypeDef::Primitive(Primitive::Number)),
+                Field::new("tags", TypeDef::Record {
+                    key: Box::new(string()),
+                    value: Box::new(TypeDef::Tuple(vec![string(), TypeDef::Primitive(Primitive::Boolean)])),
+                }),
+            ]),
+        );
+        let post = named("Post", TypeDef::Object(vec![Field::new("author", user.clone())]));
+
+        assert_eq!(
+            render(vec![post]),
+            "import { z } from \"zod\";\n\n\
+             export const UserSchema = z.object({ id: z.string(), nickname: z.string().nullable().optional(), \
+             \"created-at\": z.number(), tags: z.record(z.string(), z.tuple([z.string(), z.boolean()])) });\n\
+             export type User = z.infer<typeof UserSchema>;\n\n\
+             export const PostSchema = z.object({ author: UserSchema });\n\
+             export type Post = z.infer<typeof PostSchema>;\n"
+        );
+    }
+
+    #[test]
+    fn test_union_schemas() {
+        let zod = |def: TypeDef| {
+            let registry = TypeRegistry::new();
+            Zod { registry: &registry }.schema(&def)
+        };
+
+        assert_eq!(zod(TypeDef::Union(vec![literal("a"), literal("b")])), "z.enum([\"a\", \"b\"])");
+        assert_eq!(
+            zod(TypeDef::Union(vec![string(), TypeDef::Primitive(Primitive::Number)])),
+            "z.union([z.string(), z.number()])"
+        );
+        assert_eq!(
+            zod(TypeDef::Union(vec![TypeDef::Primitive(Primitive::Undefined), string()])),
+            "z.string().optional()"
+        );
+
+        // Variants sharing a literal field are discriminated by it
+        let variant = |kind: &str, field: &str| {
+            TypeDef::Object(vec![
+                Field::new(field, TypeDef::Primitive(Primitive::Number)),
+                Field::new("type", literal(kind)),
+            ])
+        };
+        assert_eq!(
+            zod(TypeDef::Union(vec![variant("circle", "radius"), variant("square", "side")])),
+            "z.discriminatedUnion(\"type\", [z.object({ radius: z.number(), type: z.literal(\"circle\") }), \
+             z.object({ side: z.number(), type: z.literal(\"square\") })])"
+        );
+        assert_eq!(
+            zod(TypeDef::Union(vec![variant("circle", "radius"), variant("circle", "side")])),
+            "z.union([z.object({ radius: z.number(), type: z.literal(\"circle\") }), \
+             z.object({ side: z.number(), type: z.literal(\"circle\") })])"
+        );
+    }
+
+    #[test]
+    fn test_template_literal_schema() {
+        let id = named(
+            "VmId",
+            TypeDef::TemplateLiteral {
+                strings: vec!["vm-".into(), ".".into(), "".into()],
+                types: vec![Box::new(string()), Box::new(TypeDef::Primitive(Primitive::Number))],
+            },
+        );
+        assert!(render(vec![id]).contains(r"export const VmIdSchema = z.string().regex(/^vm-.*\.-?\d+(?:\.\d+)?$/);"));
+    }
+
+    #[test]
+    fn test_recursive_schema() {
+        let tree = named(
+            "Tree",
+            TypeDef::Object(vec![Field::new("children", TypeDef::Array(Box::new(TypeDef::Ref("Tree".into()))))]),
+        );
+        assert_eq!(
+            render(vec![tree]),
+            "import { z } from \"zod\";\n\n\
+             export type Tree = { children: Tree[] };\n\
+             export const TreeSchema: z.ZodType<Tree> = z.lazy(() => z.object({ children: z.array(TreeSchema) }));\n"
+        );
+    }
+
+    #[test]
+    fn test_generic_schema() {
+        let page = TypeDef::Generic {
+            base: "Page".into(),
+            args: vec![string()],
+            def: Some(Box::new(TypeDef::GenericDef {
+                name: "Page".into(),
+                type_params: vec![TypeParam::new("T")],
+                def: Box::new(TypeDef::Object(vec![Field::new(
+                    "items",
+                    TypeDef::Array(Box::new(TypeDef::TypeParamRef("T".into()))),
+                )])),
+                module: None,
+                docs: None,
+                declaration_style: None,
+            })),
+        };
+        let names = named("Names", page);
+        assert_eq!(
+            render(vec![names]),
+            "import { z } from \"zod\";\n\n\
+             export type Page<T> = { items: T[] };\n\
+             export const PageSchema = <T extends z.ZodTypeAny>(T: T) => z.object({ items: z.array(T) });\n\n\
+             export const NamesSchema = PageSchema(z.string());\n\
+             export type Names = z.infer<typeof NamesSchema>;\n"
+        );
+    }
+
+    #[test]
+    fn test_enum_wrapper_and_override_schemas() {
+        let priority = named(
+            "Priority",
+            TypeDef::Enum {
+                members: vec![
+                    EnumMember::new("Low", Literal::Number(1.0)),
+                    EnumMember::new("High", Literal::Number(2.0)),
+                ],
+                style: EnumStyle::ConstEnum,
+            },
+        );
+        let mut wrapped = named("Settings", TypeDef::Object(vec![Field::new("priority", priority)]));
+        if let TypeDef::Named { wrapper, .. } = &mut wrapped {
+            *wrapper = Some("Prettify<Readonly<".into());
+        }
+        let mut registry = TypeRegistry::new();
+        registry.override_type("Timestamp", "string");
+        registry.add_typedef(named("Timestamp", TypeDef::Primitive(Primitive::Number)));
+        registry.add_typedef(wrapped);
+
+        assert_eq!(
+            render_zod(&registry),
+            "import { z } from \"zod\";\n\n\
+             export const TimestampSchema = z.custom<string>();\n\
+             export type Timestamp = z.infer<typeof TimestampSchema>;\n\n\
+             export enum Priority { Low = 1, High = 2 }\n\
+             export const PrioritySchema = z.nativeEnum(Priority);\n\n\
+             export const SettingsSchema = z.object({ priority: PrioritySchema }).readonly();\n\
+             export type Settings = z.infer<typeof SettingsSchema>;\n"
+        );
+    }
+
+    #[test]
+    fn test_namespaced_schema() {
+        let state = TypeDef::Named {
+            namespace: vec!["VM".into(), "Git".into()],
+            name: "State".into(),
+            def: Box::new(TypeDef::Union(vec![literal("clean"), literal("dirty")])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        assert_eq!(
+            render(vec![state]),
+            "import { z } from \"zod\";\n\n\
+             export const VMGitStateSchema = z.enum([\"clean\", \"dirty\"]);\n\
+             export namespace VM {\n    export namespace Git {\n        \
+             export type State = z.infer<typeof VMGitStateSchema>;\n    }\n}\n"
+        );
+    }
+}
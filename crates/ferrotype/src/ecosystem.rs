@@ -0,0 +1,313 @@
+//! TS implementations for types from common ecosystem crates
+//!
+//! Each crate is behind a cargo feature of the same name, and its types
+//! render as that crate's default serde representation.
+
+#[allow(unused_imports)]
+use crate::{Field, Literal, Primitive, TypeDef, TS};
+
+/// Implements TS for types that serialize as strings
+#[allow(unused_macros)]
+macro_rules! impl_ts_string {
+    ($($t:ty),*) => {
+        $(
+            impl TS for $t {
+                fn typescript() -> TypeDef {
+                    TypeDef::Primitive(Primitive::String)
+                }
+            }
+        )*
+    };
+}
+
+/// A tuple of `n` numbers, the compact serde representation of many
+/// date and time types
+#[allow(dead_code)]
+fn number_tuple(n: usize) -> TypeDef {
+    TypeDef::Tuple(vec![TypeDef::Primitive(Primitive::Number); n])
+}
+
+/// A union of string literals
+#[allow(dead_code)]
+fn string_literals(values: &[&str]) -> TypeDef {
+    TypeDef::Union(values.iter().map(|v| TypeDef::Literal(Literal::String(v.to_string()))).collect())
+}
+
+// ============================================================================
+// CHRONO
+// ============================================================================
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+
+    // Dates and times serialize as RFC 3339 / ISO 8601 strings
+    impl<Tz: chrono::TimeZone> TS for chrono::DateTime<Tz> {
+        fn typescript() -> TypeDef {
+            TypeDef::Primitive(Primitive::String)
+        }
+    }
+
+    impl_ts_string!(chrono::NaiveDate, chrono::NaiveTime, chrono::NaiveDateTime);
+
+    // `(secs, nanos)`
+    impl TS for chrono::TimeDelta {
+        fn typescript() -> TypeDef {
+            number_tuple(2)
+        }
+    }
+
+    impl TS for chrono::Weekday {
+        fn typescript() -> TypeDef {
+            string_literals(&["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"])
+        }
+    }
+
+    impl TS for chrono::Month {
+        fn typescript() -> TypeDef {
+            string_literals(&[
+                "January", "February", "March", "April", "May", "June", "July", "August", "September",
+                "October", "November", "December",
+            ])
+        }
+    }
+}
+
+// ============================================================================
+// TIME
+// ============================================================================
+
+// Without time's `serde-human-readable` feature, its types serialize as
+// tuples of their components; with it, enabled by our `time-human-readable`
+// feature, they serialize as strings in human-readable formats like JSON.
+// Feature unification can enable time's feature without ours, which makes
+// the tuple types wrong, so the two have to be kept in step by hand.
+
+#[cfg(all(feature = "time", not(feature = "time-human-readable")))]
+mod time_impls {
+    use super::*;
+
+    macro_rules! impl_ts_number_tuple {
+        ($($t:ty => $n:expr),*) => {
+            $(
+                impl TS for $t {
+                    fn typescript() -> TypeDef {
+                        number_tuple($n)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_ts_number_tuple!(
+        // `(year, ordinal)`
+        time::Date => 2,
+        // `(hour, minute, second, nanosecond)`
+        time::Time => 4,
+        // The date followed by the time
+        time::PrimitiveDateTime => 6,
+        // The date and time followed by the offset's hours, minutes and seconds
+        time::OffsetDateTime => 9,
+        // `(hours, minutes, seconds)`
+        time::UtcOffset => 3,
+        // `(seconds, nanoseconds)`
+        time::Duration => 2
+    );
+
+    // The number of the weekday from Monday, or of the month
+    impl TS for time::Weekday {
+        fn typescript() -> TypeDef {
+            TypeDef::Primitive(Primitive::Number)
+        }
+    }
+
+    impl TS for time::Month {
+        fn typescript() -> TypeDef {
+            TypeDef::Primitive(Primitive::Number)
+        }
+    }
+}
+
+#[cfg(feature = "time-human-readable")]
+mod time_impls {
+    use super::*;
+
+    impl_ts_string!(
+        time::Date,
+        time::Time,
+        time::PrimitiveDateTime,
+        time::OffsetDateTime,
+        time::UtcOffset,
+        time::Duration
+    );
+
+    impl TS for time::Weekday {
+        fn typescript() -> TypeDef {
+            string_literals(&["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"])
+        }
+    }
+
+    impl TS for time::Month {
+        fn typescript() -> TypeDef {
+            string_literals(&[
+                "January", "February", "March", "April", "May", "June", "July", "August", "September",
+                "October", "November", "December",
+            ])
+        }
+    }
+}
+
+// ============================================================================
+// UUID, URL, SEMVER, DECIMALS
+// ============================================================================
+
+#[cfg(feature = "uuid")]
+impl_ts_string!(uuid::Uuid);
+
+#[cfg(feature = "url")]
+impl_ts_string!(url::Url);
+
+#[cfg(feature = "semver")]
+impl_ts_string!(semver::Version, semver::VersionReq);
+
+// Decimals serialize as strings to keep their precision
+#[cfg(feature = "rust_decimal")]
+impl_ts_string!(rust_decimal::Decimal);
+
+#[cfg(feature = "bigdecimal")]
+impl_ts_string!(bigdecimal::BigDecimal);
+
+// ============================================================================
+// SERDE_JSON
+// ============================================================================
+
+#[cfg(feature = "serde_json")]
+mod serde_json_impls {
+    use super::*;
+
+    /// Any JSON value, declared as the recursive type `JsonValue`
+    impl TS for serde_json::Value {
+        fn typescript() -> TypeDef {
+            let value = || Box::new(TypeDef::Ref("JsonValue".into()));
+            TypeDef::Named {
+                namespace: vec![],
+                name: "JsonValue".into(),
+                def: Box::new(TypeDef::Union(vec![
+                    TypeDef::Primitive(Primitive::String),
+                    TypeDef::Primitive(Primitive::Number),
+                    TypeDef::Primitive(Primitive::Boolean),
+                    TypeDef::Primitive(Primitive::Null),
+                    TypeDef::Array(value()),
+                    TypeDef::Record {
+                        key: Box::new(TypeDef::Primitive(Primitive::String)),
+                        value: value(),
+                    },
+                ])),
+                module: None,
+                wrapper: None,
+                docs: None,
+                declaration_style: None,
+            }
+        }
+    }
+
+    impl TS for serde_json::Map<String, serde_json::Value> {
+        fn typescript() -> TypeDef {
+            TypeDef::Record {
+                key: Box::new(TypeDef::Primitive(Primitive::String)),
+                value: Box::new(serde_json::Value::typescript()),
+            }
+        }
+    }
+
+    impl TS for serde_json::Number {
+        fn typescript() -> TypeDef {
+            TypeDef::Primitive(Primitive::Number)
+        }
+    }
+}
+
+// ============================================================================
+// BYTES
+// ============================================================================
+
+// Bytes serialize as a byte array, which JSON writes as an array of numbers
+#[cfg(feature = "bytes")]
+mod bytes_impls {
+    use super::*;
+
+    impl TS for bytes::Bytes {
+        fn typescript() -> TypeDef {
+            TypeDef::Array(Box::new(TypeDef::Primitive(Primitive::Number)))
+        }
+    }
+
+    impl TS for bytes::BytesMut {
+        fn typescript() -> TypeDef {
+            TypeDef::Array(Box::new(TypeDef::Primitive(Primitive::Number)))
+        }
+    }
+}
+
+// ============================================================================
+// COLLECTIONS
+// ============================================================================
+
+#[cfg(feature = "indexmap")]
+mod indexmap_impls {
+    use super::*;
+
+    impl<K: TS, V: TS, S> TS for indexmap::IndexMap<K, V, S> {
+        fn typescript() -> TypeDef {
+            TypeDef::map(K::typescript(), V::typescript())
+        }
+    }
+
+    impl<T: TS, S> TS for indexmap::IndexSet<T, S> {
+        fn typescript() -> TypeDef {
+            TypeDef::Array(Box::new(T::typescript()))
+        }
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> TS for smallvec::SmallVec<A>
+where
+    A::Item: TS,
+{
+    fn typescript() -> TypeDef {
+        TypeDef::Array(Box::new(A::Item::typescript()))
+    }
+}
+
+// ============================================================================
+// ORDERED-FLOAT, EITHER
+// ============================================================================
+
+#[cfg(feature = "ordered-float")]
+mod ordered_float_impls {
+    use super::*;
+
+    impl<T: TS> TS for ordered_float::OrderedFloat<T> {
+        fn typescript() -> TypeDef {
+            T::typescript()
+        }
+    }
+
+    impl<T: TS> TS for ordered_float::NotNan<T> {
+        fn typescript() -> TypeDef {
+            T::typescript()
+        }
+    }
+}
+
+// Either derives its serde impls, so it is externally tagged
+#[cfg(feature = "either")]
+impl<L: TS, R: TS> TS for either::Either<L, R> {
+    fn typescript() -> TypeDef {
+        TypeDef::Union(vec![
+            TypeDef::Object(vec![Field::new("Left", L::typescript())]),
+            TypeDef::Object(vec![Field::new("Right", R::typescript())]),
+        ])
+    }
+}
@@ -20,7 +20,7 @@ pub use ferro_type_derive::TS;
 pub use ferro_type_derive::TypeScript;
 pub use linkme;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // ============================================================================
 // AUTO-REGISTRATION VIA DISTRIBUTED SLICE
@@ -78,6 +78,8 @@ pub static TYPESCRIPT_TYPES: [fn() -> TypeDef];
 ///             def: Box::new(TypeDef::Primitive(Primitive::String)),
 ///             module: None,
 ///             wrapper: None,
+///             docs: None,
+///             declaration_style: None,
 ///         }
 ///     }
 /// }
@@ -126,6 +128,10 @@ pub enum TypeDef {
     Intersection(Vec<TypeDef>),
 
     /// A record/dictionary type: `Record<K, V>` or `{ [key: K]: V }`
+    ///
+    /// Like a map, a record needn't have every key, so a finite key type,
+    /// such as a union of literals, renders as `Partial<Record<K, V>>`.
+    /// [`TypeDef::map`] builds the record of a Rust map.
     Record {
         key: Box<TypeDef>,
         value: Box<TypeDef>,
@@ -142,6 +148,10 @@ pub enum TypeDef {
     /// - `wrapper: None` → `type Name = Definition;`
     /// - `wrapper: Some("Prettify")` → `type Name = Prettify<Definition>;`
     /// - `wrapper: Some("Prettify<Required<")` → `type Name = Prettify<Required<Definition>>;`
+    ///
+    /// The optional `declaration_style` field chooses between a type alias and
+    /// an interface for object types (see [`DeclarationStyle`]):
+    /// - `declaration_style: Some(DeclarationStyle::Interface)` → `interface Name { ... }`
     Named {
         /// Optional namespace path, e.g., ["VM", "Git"] for `namespace VM { namespace Git { ... } }`
         namespace: Vec<String>,
@@ -153,6 +163,10 @@ pub enum TypeDef {
         module: Option<String>,
         /// Optional utility type wrapper (e.g., "Prettify" or "Prettify<Required<")
         wrapper: Option<String>,
+        /// Documentation emitted as a JSDoc block above the declaration
+        docs: Option<Docs>,
+        /// How the declaration is written, overriding the renderer's default
+        declaration_style: Option<DeclarationStyle>,
     },
 
     /// A reference to a named type. Used to avoid infinite recursion and
@@ -169,9 +183,14 @@ pub enum TypeDef {
     },
 
     /// A generic type application: `Generic<T1, T2>`
+    ///
+    /// `def` optionally carries the [`TypeDef::GenericDef`] being applied, as
+    /// `#[derive(TS)]` does for generic Rust types. A [`TypeRegistry`] emits
+    /// that declaration once, however many instantiations reference it.
     Generic {
         base: String,
         args: Vec<TypeDef>,
+        def: Option<Box<TypeDef>>,
     },
 
     /// An indexed access type: `T["K"]`
@@ -264,6 +283,9 @@ pub enum TypeDef {
     ///         Field::new("id", TypeDef::Primitive(Primitive::String)),
     ///         Field::new("data", TypeDef::TypeParamRef("T".into())),
     ///     ])),
+    ///     module: None,
+    ///     docs: None,
+    ///     declaration_style: None,
     /// };
     /// ```
     GenericDef {
@@ -273,6 +295,12 @@ pub enum TypeDef {
         type_params: Vec<TypeParam>,
         /// The type definition body (may reference type params via TypeParamRef)
         def: Box<TypeDef>,
+        /// Optional module path for multi-file export, as for `Named`
+        module: Option<String>,
+        /// Documentation emitted as a JSDoc block above the declaration
+        docs: Option<Docs>,
+        /// How the declaration is written, overriding the renderer's default
+        declaration_style: Option<DeclarationStyle>,
     },
 
     /// A reference to a type parameter within a generic definition.
@@ -280,6 +308,40 @@ pub enum TypeDef {
     /// This is used inside a `GenericDef` to reference one of its type parameters.
     /// For example, in `type Core<T> = { data: T }`, the `T` in `data: T` is a `TypeParamRef`.
     TypeParamRef(String),
+
+    /// A TypeScript enum, declared according to its [`EnumStyle`].
+    ///
+    /// As the `def` of a [`TypeDef::Named`], this renders as a declaration
+    /// rather than a type alias:
+    ///
+    /// ```typescript
+    /// enum Code { A = 1, B = 2 }                 // EnumStyle::Enum
+    /// const enum Code { A = 1, B = 2 }           // EnumStyle::ConstEnum
+    /// const Code = { A: 1, B: 2 } as const;      // EnumStyle::AsConst
+    /// type Code = (typeof Code)[keyof typeof Code];
+    /// ```
+    ///
+    /// Used inline, it renders as the union of its member values: `1 | 2`.
+    Enum {
+        /// The enum members, in declaration order
+        members: Vec<EnumMember>,
+        /// How the enum is declared
+        style: EnumStyle,
+    },
+
+    /// A 64-bit or 128-bit integer, which a JavaScript `number` only holds
+    /// exactly up to `Number.MAX_SAFE_INTEGER` (2^53 - 1).
+    ///
+    /// Renders as the TypeScript type of its [`IntPolicy`]. Without a policy
+    /// of its own or one set on the [`TypeRegistry`], it keeps the width's
+    /// [default](IntWidth::default_policy): `number` for 64 bits and `bigint`
+    /// for 128 bits.
+    LargeInt {
+        /// The width of the Rust integer
+        width: IntWidth,
+        /// How the integer is represented, if chosen explicitly
+        policy: Option<IntPolicy>,
+    },
 }
 
 /// Primitive TypeScript types.
@@ -307,6 +369,68 @@ pub enum Primitive {
     BigInt,
 }
 
+/// The width of a [`TypeDef::LargeInt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntWidth {
+    /// `i64`, `u64`, `isize`, `usize` and their `NonZero` types
+    Bits64,
+    /// `i128`, `u128` and their `NonZero` types
+    Bits128,
+}
+
+/// How a [`TypeDef::LargeInt`] is represented in TypeScript.
+///
+/// serde writes integers as JSON numbers, so every policy but
+/// [`IntPolicy::Number`] assumes the value is serialized to match, such as
+/// with `serde_with::DisplayFromStr` for [`IntPolicy::String`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntPolicy {
+    /// `number`, losing precision beyond `Number.MAX_SAFE_INTEGER`
+    Number,
+    /// `bigint`
+    BigInt,
+    /// `string`
+    String,
+    /// `number | string`, for values written as numbers while small enough
+    NumberOrString,
+}
+
+/// A side of serialization, which can see an object differently.
+///
+/// A field with `#[serde(default)]` is always written but may be left out
+/// when read, and one with `skip_serializing_if` may be left out when
+/// written. Rendered as one type, such fields are optional; rendered for
+/// one side with [`TypeDef::with_shape`], they are exact, including under
+/// TypeScript's `exactOptionalPropertyTypes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    /// Values as serialized, for reading responses
+    Output,
+    /// Values as deserialized, for writing requests
+    Input,
+}
+
+/// How a [`Field`] differs between its [`Shape`]s.
+///
+/// The field's `ty` and `optional` describe both shapes at once: optional
+/// if either side may leave it out, typed as it's written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FieldShape {
+    /// The same in both shapes.
+    #[default]
+    Exact,
+    /// Always written, but may be left out when read, as with
+    /// `#[serde(default)]`: `field: T` in output, `field?: T` in input.
+    Defaulted,
+    /// Left out when written if it matches `skip_serializing_if`, but
+    /// required when read: `field?: T` in output, `field: T` in input.
+    Skipped,
+    /// An `Option<T>` left out when written as `None` with
+    /// `skip_serializing_if = "Option::is_none"`, but which also reads
+    /// `null`: `field?: T` in output, `field?: T | null` in input.
+    SkippedNone,
+}
+
 /// A field in an object type.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Field {
@@ -318,6 +442,120 @@ pub struct Field {
     pub optional: bool,
     /// Whether the field is readonly.
     pub readonly: bool,
+    /// Documentation emitted as a JSDoc comment before the field.
+    pub docs: Option<Docs>,
+    /// How the field differs between serialized output and deserialized input.
+    pub shape: FieldShape,
+}
+
+/// Documentation attached to a type, field or variant.
+///
+/// Rendered as a JSDoc block (`/** ... */`), so editors surface it on hover.
+/// `#[derive(TS)]` fills this in from `///` comments and `#[deprecated]`.
+///
+/// # Example
+///
+/// ```ignore
+/// let docs = Docs::new("A registered user.").with_deprecated("use `Account`");
+/// assert_eq!(
+///     docs.render(),
+///     "/**\n * A registered user.\n * @deprecated use `Account`\n */"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Docs {
+    /// The description text; may span several lines.
+    pub description: Option<String>,
+    /// Set when the item is deprecated, holding the (possibly empty) note.
+    pub deprecated: Option<String>,
+}
+
+impl Docs {
+    /// Creates documentation with the given description.
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            deprecated: None,
+        }
+    }
+
+    /// Marks the documented item as deprecated: `@deprecated note`
+    pub fn with_deprecated(mut self, note: impl Into<String>) -> Self {
+        self.deprecated = Some(note.into());
+        self
+    }
+
+    /// Returns true if there is nothing to render.
+    pub fn is_empty(&self) -> bool {
+        self.description.as_deref().is_none_or(|d| d.trim().is_empty()) && self.deprecated.is_none()
+    }
+
+    /// Renders this documentation as a JSDoc block.
+    ///
+    /// A single line renders as `/** text */`; anything longer gets one
+    /// ` * ` prefixed line per line of text. Any `*/` in the text is escaped
+    /// so it cannot terminate the comment early.
+    pub fn render(&self) -> String {
+        let lines = self.lines();
+        match lines.as_slice() {
+            [] => String::new(),
+            [line] => format!("/** {} */", line),
+            _ => {
+                let mut result = String::from("/**\n");
+                for line in &lines {
+                    if line.is_empty() {
+                        result.push_str(" *\n");
+                    } else {
+                        result.push_str(" * ");
+                        result.push_str(line);
+                        result.push('\n');
+                    }
+                }
+                result.push_str(" */");
+                result
+            }
+        }
+    }
+
+    /// Renders this documentation as a single-line JSDoc block, for output
+    /// that keeps a whole type on one line.
+    ///
+    /// The lines of a longer comment are joined with spaces, so
+    /// `"Old id."` deprecated for `uuid` renders as
+    /// `/** Old id. @deprecated use uuid */`.
+    pub fn render_inline(&self) -> String {
+        let lines: Vec<String> = self.lines().into_iter().filter(|l| !l.trim().is_empty()).collect();
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("/** {} */", lines.join(" "))
+        }
+    }
+
+    /// The escaped lines of text in the JSDoc block
+    fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        if let Some(description) = &self.description {
+            lines.extend(description.trim().lines().map(|l| l.trim_end().to_string()));
+        }
+        if let Some(note) = &self.deprecated {
+            let note = note.trim();
+            if note.is_empty() {
+                lines.push("@deprecated".to_string());
+            } else {
+                lines.push(format!("@deprecated {}", note));
+            }
+        }
+        lines.iter().map(|l| l.replace("*/", "*\\/")).collect()
+    }
+
+    /// Prefixes `text` with the rendered JSDoc block on its own line.
+    fn prefix(docs: &Option<Docs>, text: String) -> String {
+        match docs {
+            Some(docs) if !docs.is_empty() => format!("{}\n{}", docs.render(), text),
+            _ => text,
+        }
+    }
 }
 
 /// A type parameter for generic type definitions.
@@ -401,6 +639,8 @@ impl Field {
             ty,
             optional: false,
             readonly: false,
+            docs: None,
+            shape: FieldShape::Exact,
         }
     }
 
@@ -411,6 +651,8 @@ impl Field {
             ty,
             optional: true,
             readonly: false,
+            docs: None,
+            shape: FieldShape::Exact,
         }
     }
 
@@ -419,6 +661,39 @@ impl Field {
         self.readonly = true;
         self
     }
+
+    /// Attaches documentation to this field.
+    pub fn with_docs(mut self, docs: Docs) -> Self {
+        self.docs = Some(docs);
+        self
+    }
+
+    /// Sets how this field differs between serialized output and
+    /// deserialized input.
+    pub fn with_shape(mut self, shape: FieldShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Makes this field exact for one side of serialization.
+    fn fill_shape(&mut self, shape: Shape) {
+        match (std::mem::take(&mut self.shape), shape) {
+            (FieldShape::Defaulted, Shape::Output) | (FieldShape::Skipped, Shape::Input) => {
+                self.optional = false;
+            }
+            (FieldShape::SkippedNone, Shape::Input) => {
+                let ty = std::mem::replace(&mut self.ty, TypeDef::Primitive(Primitive::Never));
+                self.ty = match ty {
+                    TypeDef::Union(mut variants) => {
+                        variants.push(TypeDef::Primitive(Primitive::Null));
+                        TypeDef::Union(variants)
+                    }
+                    ty => TypeDef::Union(vec![ty, TypeDef::Primitive(Primitive::Null)]),
+                };
+            }
+            _ => {}
+        }
+    }
 }
 
 /// A literal TypeScript type with a specific value.
@@ -440,7 +715,7 @@ impl TypeDef {
             TypeDef::Array(inner) => {
                 let inner_str = inner.render();
                 // Wrap union types in parens for array syntax
-                if matches!(inner.as_ref(), TypeDef::Union(_)) {
+                if inner.is_union() {
                     format!("({})[]", inner_str)
                 } else {
                     format!("{}[]", inner_str)
@@ -457,9 +732,13 @@ impl TypeDef {
                     let fields_str: Vec<_> = fields
                         .iter()
                         .map(|f| {
+                            let doc = match &f.docs {
+                                Some(docs) if !docs.is_empty() => format!("{} ", docs.render_inline()),
+                                _ => String::new(),
+                            };
                             let readonly = if f.readonly { "readonly " } else { "" };
                             let opt = if f.optional { "?" } else { "" };
-                            format!("{}{}{}: {}", readonly, f.name, opt, f.ty.render())
+                            format!("{}{}{}{}: {}", doc, readonly, f.name, opt, f.ty.render())
                         })
                         .collect();
                     format!("{{ {} }}", fields_str.join("; "))
@@ -470,11 +749,20 @@ impl TypeDef {
                 variants_str.join(" | ")
             }
             TypeDef::Intersection(types) => {
-                let types_str: Vec<_> = types.iter().map(|t| t.render()).collect();
+                // Wrap union types in parens, which bind looser than `&`
+                let types_str: Vec<_> = types
+                    .iter()
+                    .map(|t| if t.is_union() { format!("({})", t.render()) } else { t.render() })
+                    .collect();
                 types_str.join(" & ")
             }
             TypeDef::Record { key, value } => {
-                format!("Record<{}, {}>", key.render(), value.render())
+                let record = format!("Record<{}, {}>", key.render(), value.render());
+                if key.is_finite() {
+                    format!("Partial<{}>", record)
+                } else {
+                    record
+                }
             }
             TypeDef::Named { namespace, name, .. } => {
                 if namespace.is_empty() {
@@ -495,7 +783,7 @@ impl TypeDef {
                     .collect();
                 format!("({}) => {}", params_str.join(", "), return_type.render())
             }
-            TypeDef::Generic { base, args } => {
+            TypeDef::Generic { base, args, .. } => {
                 let args_str: Vec<_> = args.iter().map(|t| t.render()).collect();
                 format!("{}<{}>", base, args_str.join(", "))
             }
@@ -526,173 +814,1210 @@ impl TypeDef {
                 // Type parameter references render as just the parameter name
                 name.clone()
             }
+            TypeDef::Enum { members, .. } => {
+                if members.is_empty() {
+                    "never".to_string()
+                } else {
+                    let values: Vec<_> = members.iter().map(|m| m.value.render()).collect();
+                    values.join(" | ")
+                }
+            }
+            TypeDef::LargeInt { width, policy } => width.repr(*policy).render(),
         }
     }
 
-    /// Renders a full type declaration for named types.
-    ///
-    /// For `Named` types, this returns `type Name = Definition;`
-    /// For `GenericDef` types, this returns `type Name<T, ...> = Definition;`
-    /// If the type has a namespace, it wraps in `namespace X { ... }`.
-    /// If the type has a wrapper, wraps in utility type: `type Name = Wrapper<Definition>;`
-    /// For other types, this just returns the rendered type.
-    pub fn render_declaration(&self) -> String {
+    /// Whether this type renders as a union, needing parentheses in array
+    /// syntax.
+    fn is_union(&self) -> bool {
         match self {
-            TypeDef::Named { namespace, name, def, wrapper, .. } => {
-                let def_rendered = def.render();
-                let wrapped = match wrapper {
-                    Some(w) => Self::apply_wrapper(w, &def_rendered),
-                    None => def_rendered,
-                };
-                let inner = format!("type {} = {};", name, wrapped);
-                Self::wrap_in_namespace(namespace, &inner)
-            }
-            TypeDef::GenericDef {
-                name,
-                type_params,
-                def,
-            } => {
-                let params_str: Vec<_> = type_params.iter().map(|p| p.render()).collect();
-                format!("type {}<{}> = {};", name, params_str.join(", "), def.render())
-            }
-            _ => self.render(),
+            TypeDef::Union(_) => true,
+            TypeDef::LargeInt { width, policy } => width.repr(*policy).is_union(),
+            _ => false,
         }
     }
 
-    /// Applies a utility type wrapper to a definition.
+    /// Represents the integers of `width` in this type with `policy`, unless
+    /// they already have a policy.
     ///
-    /// The wrapper can be:
-    /// - `"Prettify"` → `Prettify<Definition>`
-    /// - `"Prettify<Required<"` → `Prettify<Required<Definition>>` (closes unclosed `<`)
-    fn apply_wrapper(wrapper: &str, definition: &str) -> String {
-        // Count unclosed '<' characters to know how many '>' to add
-        let open_count = wrapper.chars().filter(|c| *c == '<').count();
-        let close_count = wrapper.chars().filter(|c| *c == '>').count();
-        let needed_closes = open_count.saturating_sub(close_count);
-
-        if open_count == 0 {
-            // Simple wrapper like "Prettify" → "Prettify<Definition>"
-            format!("{}<{}>", wrapper, definition)
-        } else {
-            // Wrapper with open angle brackets like "Prettify<Required<"
-            // Just append definition and closing brackets
-            format!("{}{}{}", wrapper, definition, ">".repeat(needed_closes))
-        }
+    /// Named and generic types referenced from this one keep their own
+    /// definitions; only integers written out in this type, including in
+    /// generic arguments, are affected. This is how `#[ts(int64 = "...")]`
+    /// applies to a field.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let ids = <Vec<u64>>::typescript().with_int_policy(IntWidth::Bits64, IntPolicy::String);
+    /// assert_eq!(ids.render(), "string[]");
+    /// ```
+    pub fn with_int_policy(mut self, width: IntWidth, policy: IntPolicy) -> Self {
+        self.fill_int_policy(width, policy);
+        self
     }
 
-    /// Wraps a declaration in namespace blocks.
+    /// A [`TypeDef::Record`] from the JSON object keys of `key` to `value`,
+    /// as serde_json writes a map keyed by `key`.
     ///
-    /// For namespace `["VM", "Git"]` and inner `type State = "clean";`:
-    /// ```typescript
-    /// namespace VM {
-    ///     namespace Git {
-    ///         type State = "clean";
-    ///     }
-    /// }
+    /// See [`into_json_key`](Self::into_json_key) for how keys are mapped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let scores = TypeDef::map(u32::typescript(), f64::typescript());
+    /// assert_eq!(scores.render(), "Record<`${number}`, number>");
     /// ```
-    fn wrap_in_namespace(namespace: &[String], inner: &str) -> String {
-        if namespace.is_empty() {
-            return inner.to_string();
+    pub fn map(key: TypeDef, value: TypeDef) -> TypeDef {
+        TypeDef::Record {
+            key: Box::new(key.into_json_key()),
+            value: Box::new(value),
         }
+    }
 
-        let mut result = String::new();
-        let indent = "    ";
+    /// The type of the JSON object keys serde_json writes for map keys of
+    /// this type.
+    ///
+    /// JSON object keys are always strings, so numbers, booleans and large
+    /// integers become template literals of their text, like
+    /// `` `${number}` ``, and number and boolean literals become string
+    /// literals. A named type whose keys differ from its values is
+    /// referenced the same way: `` `${Level}` ``. Types serde_json can't
+    /// write as keys, such as objects, are returned unchanged; see
+    /// [`is_json_key`](Self::is_json_key).
+    pub fn into_json_key(self) -> TypeDef {
+        let template = |ty| TypeDef::TemplateLiteral {
+            strings: vec![String::new(), String::new()],
+            types: vec![Box::new(ty)],
+        };
+        match self {
+            TypeDef::Primitive(Primitive::Number | Primitive::BigInt | Primitive::Boolean)
+            | TypeDef::LargeInt { .. } => template(self),
+            TypeDef::Literal(lit @ (Literal::Number(_) | Literal::Boolean(_))) => {
+                TypeDef::Literal(Literal::String(lit.render()))
+            }
+            TypeDef::Union(variants) => TypeDef::Union(variants.into_iter().map(Self::into_json_key).collect()),
+            TypeDef::Named { ref def, .. } if def.as_ref().clone().into_json_key() != **def => template(self),
+            other => other,
+        }
+    }
 
-        // Opening namespace declarations
-        for (i, ns) in namespace.iter().enumerate() {
-            for _ in 0..i {
-                result.push_str(indent);
+    /// Whether serde_json can write map keys of this type, as mapped by
+    /// [`into_json_key`](Self::into_json_key).
+    ///
+    /// Objects, arrays, tuples and `null` can't be JSON object keys, and
+    /// serde_json fails to serialize maps keyed by them. References to
+    /// types not known here are assumed to be valid keys.
+    pub fn is_json_key(&self) -> bool {
+        match self {
+            TypeDef::Primitive(p) => {
+                !matches!(p, Primitive::Null | Primitive::Undefined | Primitive::Void | Primitive::Never)
             }
-            result.push_str("namespace ");
-            result.push_str(ns);
-            result.push_str(" {\n");
+            TypeDef::Union(variants) => variants.iter().all(TypeDef::is_json_key),
+            TypeDef::Named { def, .. } => def.is_json_key(),
+            TypeDef::Array(_)
+            | TypeDef::Tuple(_)
+            | TypeDef::Object(_)
+            | TypeDef::Intersection(_)
+            | TypeDef::Record { .. }
+            | TypeDef::Function { .. } => false,
+            _ => true,
         }
+    }
 
-        // Inner content with proper indentation
-        let depth = namespace.len();
-        for _ in 0..depth {
-            result.push_str(indent);
+    /// Whether this type has finitely many values, such as a union of
+    /// literals, so that a record keyed by it could have every key.
+    fn is_finite(&self) -> bool {
+        match self {
+            TypeDef::Literal(_) | TypeDef::Primitive(Primitive::Boolean) | TypeDef::Enum { .. } => true,
+            TypeDef::Union(variants) => variants.iter().all(TypeDef::is_finite),
+            TypeDef::Named { def, .. } => def.is_finite(),
+            TypeDef::TemplateLiteral { types, .. } => types.iter().all(|ty| ty.is_finite()),
+            _ => false,
         }
-        result.push_str(inner);
-        result.push('\n');
+    }
 
-        // Closing braces
-        for i in (0..depth).rev() {
-            for _ in 0..i {
-                result.push_str(indent);
-            }
-            result.push('}');
-            if i > 0 {
-                result.push('\n');
+    /// Renders the objects in this type as they are on one side of
+    /// serialization, according to each field's [`FieldShape`].
+    ///
+    /// The definitions of named types are left as they are; set the shape
+    /// on a [`TypeRegistry`] to apply it to every type.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // #[serde(default)] tags: Vec<String>
+    /// assert_eq!(post.render(), "{ tags?: string[] }");
+    /// assert_eq!(post.clone().with_shape(Shape::Output).render(), "{ tags: string[] }");
+    /// assert_eq!(post.with_shape(Shape::Input).render(), "{ tags?: string[] }");
+    /// ```
+    pub fn with_shape(mut self, shape: Shape) -> Self {
+        self.fill_shape(shape);
+        self
+    }
+
+    /// Makes the fields of the objects in this type exact for `shape`, in place.
+    fn fill_shape(&mut self, shape: Shape) {
+        if let TypeDef::Object(fields) = self {
+            for field in fields.iter_mut() {
+                field.fill_shape(shape);
             }
         }
-
-        result
+        self.for_each_child_mut(&mut |ty| ty.fill_shape(shape));
     }
-}
 
-impl Primitive {
-    /// Renders this primitive to its TypeScript keyword.
-    pub fn render(&self) -> &'static str {
+    /// Sets the policy of the integers of `width` without one, in place.
+    fn fill_int_policy(&mut self, width: IntWidth, policy: IntPolicy) {
         match self {
-            Primitive::String => "string",
-            Primitive::Number => "number",
-            Primitive::Boolean => "boolean",
-            Primitive::Null => "null",
-            Primitive::Undefined => "undefined",
-            Primitive::Void => "void",
-            Primitive::Never => "never",
-            Primitive::Any => "any",
-            Primitive::Unknown => "unknown",
-            Primitive::BigInt => "bigint",
+            TypeDef::LargeInt { width: w, policy: p @ None } if *w == width => *p = Some(policy),
+            _ => self.for_each_child_mut(&mut |ty| ty.fill_int_policy(width, policy)),
         }
     }
-}
 
-impl Literal {
-    /// Renders this literal to TypeScript syntax.
-    pub fn render(&self) -> String {
+    /// Calls `f` on each type directly inside this one, except the
+    /// definitions of named and generic types.
+    fn for_each_child_mut(&mut self, f: &mut dyn FnMut(&mut TypeDef)) {
         match self {
-            Literal::String(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
-            Literal::Number(n) => {
-                if n.fract() == 0.0 {
-                    format!("{}", *n as i64)
-                } else {
-                    format!("{}", n)
+            TypeDef::Array(inner) => f(inner),
+            TypeDef::Tuple(items) | TypeDef::Union(items) | TypeDef::Intersection(items) => {
+                items.iter_mut().for_each(f);
+            }
+            TypeDef::Object(fields) => {
+                for field in fields {
+                    f(&mut field.ty);
                 }
             }
-            Literal::Boolean(b) => b.to_string(),
+            TypeDef::Record { key, value } => {
+                f(key);
+                f(value);
+            }
+            TypeDef::Function { params, return_type } => {
+                for param in params {
+                    f(&mut param.ty);
+                }
+                f(return_type);
+            }
+            TypeDef::Generic { args, .. } => args.iter_mut().for_each(f),
+            TypeDef::TemplateLiteral { types, .. } => {
+                for ty in types {
+                    f(ty);
+                }
+            }
+            _ => {}
         }
     }
-}
 
-// ============================================================================
-// HELPER FUNCTIONS
-// ============================================================================
+    /// Renders this TypeDef to TypeScript syntax like [`render`](Self::render),
+    /// breaking it over several lines where it would not fit the format's
+    /// line width.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let format = Format::new().max_width(20);
+    /// assert_eq!(
+    ///     user.render_pretty(&format),
+    ///     "{\n    id: string;\n    name: string;\n}"
+    /// );
+    /// ```
+    pub fn render_pretty(&self, format: &Format) -> String {
+        self.pretty(format, 0, 0)
+    }
 
-/// Extracts fields from an Object TypeDef, unwrapping Named if necessary.
-///
-/// This is used by the derive macro to implement `#[ts(flatten)]`. When a field
-/// is marked with flatten, its type's fields are extracted and merged into the
-/// containing object.
-///
-/// # Panics
-///
-/// Panics if the TypeDef is not an Object (or Named wrapping an Object).
-pub fn extract_object_fields(typedef: &TypeDef) -> Vec<Field> {
-    match typedef {
-        TypeDef::Object(fields) => fields.clone(),
-        TypeDef::Named { def, .. } => extract_object_fields(def),
-        other => panic!(
-            "#[ts(flatten)] can only be used on fields with object types, got: {:?}",
-            other
-        ),
+    /// Renders compactly, or pretty-printed starting at `column` if a
+    /// format is given.
+    fn render_in(&self, format: Option<&Format>, column: usize) -> String {
+        match format {
+            Some(format) => self.pretty(format, 0, column),
+            None => self.render(),
+        }
     }
-}
 
-/// Extracts the inner type definition, unwrapping Named if necessary.
-///
-/// This is used by the derive macro to implement `#[ts(inline)]`. When a field
+    /// Renders the type after `=` or `:`, like [`pretty_value`](Self::pretty_value).
+    fn render_value_in(&self, format: Option<&Format>, column: usize) -> String {
+        match format {
+            Some(format) => self.pretty_value(format, 0, column),
+            None => format!(" {}", self.render()),
+        }
+    }
+
+    /// Pretty-prints this type at nesting depth `level`, starting at
+    /// `column` of the current line.
+    fn pretty(&self, format: &Format, level: usize, column: usize) -> String {
+        match self {
+            TypeDef::Array(inner) => {
+                // Wrap union types in parens for array syntax
+                if inner.is_union() {
+                    format!("({})[]", inner.pretty(format, level, column + 1))
+                } else {
+                    format!("{}[]", inner.pretty(format, level, column))
+                }
+            }
+            TypeDef::Tuple(items) => Self::pretty_list("[", items, ", ", "]", format, level, column),
+            TypeDef::Object(fields) => Self::pretty_object(fields, format, level, column),
+            TypeDef::Union(variants) => Self::pretty_list("", variants, " | ", "", format, level, column),
+            TypeDef::Intersection(types) => {
+                let mut result = String::new();
+                for (i, ty) in types.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(" & ");
+                    }
+                    if ty.is_union() {
+                        let column = end_column(column, &result) + 1;
+                        result.push_str(&format!("({})", ty.pretty(format, level, column)));
+                    } else {
+                        result.push_str(&ty.pretty(format, level, end_column(column, &result)));
+                    }
+                }
+                result
+            }
+            TypeDef::Record { key, value } => {
+                let (open, close) = if key.is_finite() { ("Partial<Record<", ">>") } else { ("Record<", ">") };
+                Self::pretty_list(open, [key.as_ref(), value.as_ref()], ", ", close, format, level, column)
+            }
+            TypeDef::Function { params, return_type } => {
+                let mut result = String::from("(");
+                for (i, p) in params.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    result.push_str(&p.name);
+                    result.push_str(": ");
+                    result.push_str(&p.ty.pretty(format, level, end_column(column, &result)));
+                }
+                result.push_str(") => ");
+                result.push_str(&return_type.pretty(format, level, end_column(column, &result)));
+                result
+            }
+            TypeDef::Generic { base, args, .. } => {
+                Self::pretty_list(&format!("{}<", base), args, ", ", ">", format, level, column)
+            }
+            _ => self.render(),
+        }
+    }
+
+    /// Pretty-prints `items` between `open` and `close`, separated by `joiner`.
+    fn pretty_list<'a>(
+        open: &str,
+        items: impl IntoIterator<Item = &'a TypeDef>,
+        joiner: &str,
+        close: &str,
+        format: &Format,
+        level: usize,
+        column: usize,
+    ) -> String {
+        let mut result = open.to_string();
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                result.push_str(joiner);
+            }
+            result.push_str(&item.pretty(format, level, end_column(column, &result)));
+        }
+        result.push_str(close);
+        result
+    }
+
+    /// Pretty-prints this type as it follows a `=` or `:` ending at `column`,
+    /// including the leading space.
+    ///
+    /// A union that doesn't fit, or a discriminated union when the format
+    /// splits those, is broken into one member per line:
+    ///
+    /// ```typescript
+    /// type Shape =
+    ///     | { kind: "circle"; radius: number }
+    ///     | { kind: "square"; side: number };
+    /// ```
+    fn pretty_value(&self, format: &Format, level: usize, column: usize) -> String {
+        let TypeDef::Union(variants) = self else {
+            return format!(" {}", self.pretty(format, level, column + 1));
+        };
+        let flat = self.pretty(format, level, column + 1);
+        let objects = variants.iter().filter(|v| matches!(v, TypeDef::Object(_))).count();
+        let discriminated = format.split_discriminated_unions && objects > 1;
+        if variants.len() < 2 || (!discriminated && format.fits(column + 1, &flat)) {
+            return format!(" {}", flat);
+        }
+
+        let indent = format.indent.render(level + 1);
+        let column = format.indent.width() * (level + 1) + 2;
+        let mut result = String::new();
+        for variant in variants {
+            // Continuation lines are aligned with the member after `| `
+            let member = variant
+                .pretty(format, level + 1, column)
+                .replace(&format!("\n{}", indent), &format!("\n{}  ", indent));
+            result.push('\n');
+            result.push_str(&indent);
+            result.push_str("| ");
+            result.push_str(&member);
+        }
+        result
+    }
+
+    /// Pretty-prints an object type, with one field per line unless it fits
+    /// on the current line.
+    fn pretty_object(fields: &[Field], format: &Format, level: usize, column: usize) -> String {
+        if fields.is_empty() {
+            return "{}".to_string();
+        }
+        let separator = format.separator.render();
+        let head = |f: &Field| {
+            let readonly = if f.readonly { "readonly " } else { "" };
+            let opt = if f.optional { "?" } else { "" };
+            format!("{}{}{}:", readonly, f.name, opt)
+        };
+
+        // Everything on one line, with docs inline as in `render`
+        let unbounded = Format {
+            max_width: usize::MAX,
+            ..*format
+        };
+        let fields_str: Vec<_> = fields
+            .iter()
+            .map(|f| {
+                let doc = match &f.docs {
+                    Some(docs) if !docs.is_empty() => format!("{} ", docs.render()),
+                    _ => String::new(),
+                };
+                let head = format!("{}{}", doc, head(f));
+                let value = f.ty.pretty_value(&unbounded, level, head.len());
+                format!("{}{}", head, value)
+            })
+            .collect();
+        let flat = format!("{{ {} }}", fields_str.join(&format!("{} ", separator)));
+        if format.fits(column, &flat) {
+            return flat;
+        }
+
+        let indent = format.indent.render(level + 1);
+        let mut result = String::from("{\n");
+        for f in fields {
+            if let Some(docs) = f.docs.as_ref().filter(|d| !d.is_empty()) {
+                for line in docs.render().lines() {
+                    result.push_str(&indent);
+                    result.push_str(line);
+                    result.push('\n');
+                }
+            }
+            let head = head(f);
+            let column = format.indent.width() * (level + 1) + head.chars().count();
+            result.push_str(&indent);
+            result.push_str(&head);
+            result.push_str(&f.ty.pretty_value(format, level + 1, column));
+            result.push_str(separator);
+            result.push('\n');
+        }
+        result.push_str(&format.indent.render(level));
+        result.push('}');
+        result
+    }
+
+    /// Renders a full type declaration for named types.
+    ///
+    /// For `Named` types, this returns `type Name = Definition;`
+    /// For `GenericDef` types, this returns `type Name<T, ...> = Definition;`
+    /// For `Named` types whose definition is an [`TypeDef::Enum`], this returns
+    /// the enum declaration in its [`EnumStyle`].
+    /// If the type has a namespace, it wraps in `namespace X { ... }`.
+    /// If the type has a wrapper, wraps in utility type: `type Name = Wrapper<Definition>;`
+    /// If the type has docs, they precede the declaration as a JSDoc block.
+    /// If the type's `declaration_style` is [`DeclarationStyle::Interface`],
+    /// object types are declared as `interface Name { ... }`.
+    /// For other types, this just returns the rendered type.
+    pub fn render_declaration(&self) -> String {
+        self.render_declaration_with(&DeclarationOptions::default())
+    }
+
+    /// Renders a full type declaration like [`render_declaration`](Self::render_declaration),
+    /// with `export` keywords.
+    ///
+    /// Namespaced types are wrapped in `export namespace X { ... }`.
+    pub fn render_exported_declaration(&self) -> String {
+        self.render_declaration_with(&DeclarationOptions {
+            export: true,
+            ..DeclarationOptions::default()
+        })
+    }
+
+    /// Renders a declaration for a `.d.ts` file, like
+    /// [`render_declaration`](Self::render_declaration).
+    ///
+    /// Declarations that would produce a value get the `declare` modifier:
+    /// `declare enum`, `declare const enum` and `declare namespace`. An
+    /// `as const` enum is declared by its type, without an initializer:
+    /// `declare const Name: { readonly A: "A" };`. Type aliases are unchanged.
+    pub fn render_ambient_declaration(&self) -> String {
+        self.render_declaration_with(&DeclarationOptions {
+            ambient: true,
+            ..DeclarationOptions::default()
+        })
+    }
+
+    /// Renders a declaration for a `.d.ts` file like
+    /// [`render_ambient_declaration`](Self::render_ambient_declaration),
+    /// with `export` keywords (`export declare enum`, `export declare namespace`).
+    pub fn render_exported_ambient_declaration(&self) -> String {
+        self.render_declaration_with(&DeclarationOptions {
+            export: true,
+            ambient: true,
+            ..DeclarationOptions::default()
+        })
+    }
+
+    /// Renders a full type declaration with the given options.
+    ///
+    /// The other `render_*declaration` methods are shorthands for this one.
+    /// A type's own `declaration_style` takes precedence over
+    /// [`DeclarationOptions::style`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = DeclarationOptions {
+    ///     export: true,
+    ///     style: DeclarationStyle::Interface,
+    ///     ..DeclarationOptions::default()
+    /// };
+    /// // export interface User { name: string }
+    /// user.render_declaration_with(&options);
+    /// ```
+    pub fn render_declaration_with(&self, options: &DeclarationOptions) -> String {
+        let export = if options.export { "export " } else { "" };
+        let ambient = options.ambient;
+        let format = options.format.as_ref();
+        match self {
+            TypeDef::Named { namespace, name, def, wrapper, docs, declaration_style, .. } => {
+                // Only the outermost declaration takes `declare`; the body of
+                // a declared namespace is already ambient
+                let declare = if ambient { "declare " } else { "" };
+                let (outer, inner) = if namespace.is_empty() { ("", declare) } else { (declare, "") };
+                // The declaration is indented by the namespaces around it
+                let format = format.map(|f| Format {
+                    max_width: f.max_width.saturating_sub(namespace.len() * f.indent.width()),
+                    ..*f
+                });
+                let format = format.as_ref();
+                let style = declaration_style.unwrap_or(options.style);
+                let interface = match (style, wrapper) {
+                    (DeclarationStyle::Interface, None) => Self::render_interface(export, name, "", def, format),
+                    _ => None,
+                };
+                let decl = match def.as_ref() {
+                    TypeDef::Enum { members, style } => Self::render_enum_declaration(
+                        export,
+                        inner,
+                        ambient,
+                        name,
+                        members,
+                        *style,
+                        format,
+                    ),
+                    _ => interface.unwrap_or_else(|| {
+                        let head = format!("{}type {} =", export, name);
+                        let def_rendered = match wrapper {
+                            Some(w) => {
+                                let column = head.len() + 1 + w.len() + 1;
+                                format!(" {}", Self::apply_wrapper(w, &def.render_in(format, column)))
+                            }
+                            None => def.render_value_in(format, head.len()),
+                        };
+                        format!("{}{};", head, def_rendered)
+                    }),
+                };
+                let decl = Docs::prefix(docs, decl);
+                let indent = format.map_or_else(|| "    ".to_string(), |f| f.indent.render(1));
+                Self::wrap_in_namespace(export, outer, namespace, &decl, &indent)
+            }
+            TypeDef::GenericDef {
+                name,
+                type_params,
+                def,
+                docs,
+                declaration_style,
+                ..
+            } => {
+                let params_str: Vec<_> = type_params.iter().map(|p| p.render()).collect();
+                let params = format!("<{}>", params_str.join(", "));
+                let interface = match declaration_style.unwrap_or(options.style) {
+                    DeclarationStyle::Interface => Self::render_interface(export, name, &params, def, format),
+                    DeclarationStyle::TypeAlias => None,
+                };
+                let decl = interface.unwrap_or_else(|| {
+                    let head = format!("{}type {}{} =", export, name, params);
+                    format!("{}{};", head, def.render_value_in(format, head.len()))
+                });
+                Docs::prefix(docs, decl)
+            }
+            _ => self.render_in(format, 0),
+        }
+    }
+
+    /// Renders an interface declaration, if `def` is object-shaped.
+    ///
+    /// An object becomes `interface Name { ... }`, and an intersection of an
+    /// object with named types (as from `#[ts(extends)]`) becomes
+    /// `interface Name extends Base { ... }`. Returns `None` for anything an
+    /// interface cannot express, which is then declared as a type alias.
+    fn render_interface(
+        export: &str,
+        name: &str,
+        params: &str,
+        def: &TypeDef,
+        format: Option<&Format>,
+    ) -> Option<String> {
+        let (bases, body) = match def {
+            TypeDef::Object(_) => (Vec::new(), def),
+            TypeDef::Intersection(types) => {
+                let mut bases = Vec::new();
+                let mut body = None;
+                for ty in types {
+                    match ty {
+                        TypeDef::Object(_) if body.is_none() => body = Some(ty),
+                        TypeDef::Ref(base) if is_qualified_name(base) => bases.push(ty.render()),
+                        TypeDef::Named { .. } | TypeDef::Generic { .. } => bases.push(ty.render()),
+                        _ => return None,
+                    }
+                }
+                (bases, body?)
+            }
+            _ => return None,
+        };
+        let extends = if bases.is_empty() {
+            String::new()
+        } else {
+            format!(" extends {}", bases.join(", "))
+        };
+        let head = format!("{}interface {}{}{} ", export, name, params, extends);
+        Some(format!("{}{}", head, body.render_in(format, head.len())))
+    }
+
+    /// Renders an enum declaration in the given style.
+    ///
+    /// `declare` is the modifier for the value declaration, and `ambient`
+    /// renders an `as const` object by its type instead of its initializer.
+    /// With a `format`, members go on lines of their own when they don't fit
+    /// on one.
+    fn render_enum_declaration(
+        export: &str,
+        declare: &str,
+        ambient: bool,
+        name: &str,
+        members: &[EnumMember],
+        style: EnumStyle,
+        format: Option<&Format>,
+    ) -> String {
+        let as_const_type = ambient && style == EnumStyle::AsConst;
+        let (prefix, separator, joiner) = match style {
+            EnumStyle::Enum | EnumStyle::ConstEnum => ("", " = ", ", "),
+            EnumStyle::AsConst if as_const_type => ("readonly ", ": ", "; "),
+            EnumStyle::AsConst => ("", ": ", ", "),
+        };
+        let member = |m: &EnumMember| {
+            format!("{}{}{}{}", prefix, property_name(&m.name), separator, m.value.render())
+        };
+        let members_str: Vec<_> = members
+            .iter()
+            .map(|m| {
+                // Multi-line docs keep their lines only when the body can break
+                let doc = match &m.docs {
+                    Some(docs) if !docs.is_empty() && format.is_some() => format!("{} ", docs.render()),
+                    Some(docs) if !docs.is_empty() => format!("{} ", docs.render_inline()),
+                    _ => String::new(),
+                };
+                format!("{}{}", doc, member(m))
+            })
+            .collect();
+        let mut body = if members_str.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{ {} }}", members_str.join(joiner))
+        };
+
+        // Break the body if the declaration would not fit on one line
+        if let Some(format) = format.filter(|_| !members.is_empty()) {
+            let (keyword, suffix) = match style {
+                EnumStyle::Enum => ("enum ", ""),
+                EnumStyle::ConstEnum => ("const enum ", ""),
+                EnumStyle::AsConst if as_const_type => ("const : ", ";"),
+                EnumStyle::AsConst => ("const  = ", " as const;"),
+            };
+            let column = export.len() + declare.len() + keyword.len() + name.len() + 1;
+            if !format.fits(column, &format!("{}{}", body, suffix)) {
+                let indent = format.indent.render(1);
+                let mut broken = String::from("{\n");
+                for m in members {
+                    if let Some(docs) = m.docs.as_ref().filter(|d| !d.is_empty()) {
+                        for line in docs.render().lines() {
+                            broken.push_str(&format!("{}{}\n", indent, line));
+                        }
+                    }
+                    broken.push_str(&format!("{}{}{}\n", indent, member(m), joiner.trim_end()));
+                }
+                broken.push('}');
+                body = broken;
+            }
+        }
+
+        match style {
+            EnumStyle::Enum => format!("{}{}enum {} {}", export, declare, name, body),
+            EnumStyle::ConstEnum => format!("{}{}const enum {} {}", export, declare, name, body),
+            EnumStyle::AsConst if as_const_type => format!(
+                "{export}{declare}const {name}: {body};\n\
+                 {export}type {name} = (typeof {name})[keyof typeof {name}];"
+            ),
+            EnumStyle::AsConst => format!(
+                "{export}const {name} = {body} as const;\n\
+                 {export}type {name} = (typeof {name})[keyof typeof {name}];"
+            ),
+        }
+    }
+
+    /// Applies a utility type wrapper to a definition.
+    ///
+    /// The wrapper can be:
+    /// - `"Prettify"` → `Prettify<Definition>`
+    /// - `"Prettify<Required<"` → `Prettify<Required<Definition>>` (closes unclosed `<`)
+    fn apply_wrapper(wrapper: &str, definition: &str) -> String {
+        // Count unclosed '<' characters to know how many '>' to add
+        let open_count = wrapper.chars().filter(|c| *c == '<').count();
+        let close_count = wrapper.chars().filter(|c| *c == '>').count();
+        let needed_closes = open_count.saturating_sub(close_count);
+
+        if open_count == 0 {
+            // Simple wrapper like "Prettify" → "Prettify<Definition>"
+            format!("{}<{}>", wrapper, definition)
+        } else {
+            // Wrapper with open angle brackets like "Prettify<Required<"
+            // Just append definition and closing brackets
+            format!("{}{}{}", wrapper, definition, ">".repeat(needed_closes))
+        }
+    }
+
+    /// Wraps a declaration in namespace blocks, prefixed with `export`
+    /// (e.g. `"export "`) when rendering exported declarations. `declare`
+    /// (e.g. `"declare "`) only applies to the outermost namespace, and
+    /// `indent` is the indentation of one level.
+    ///
+    /// For namespace `["VM", "Git"]` and inner `type State = "clean";`:
+    /// ```typescript
+    /// namespace VM {
+    ///     namespace Git {
+    ///         type State = "clean";
+    ///     }
+    /// }
+    /// ```
+    fn wrap_in_namespace(
+        export: &str,
+        declare: &str,
+        namespace: &[String],
+        inner: &str,
+        indent: &str,
+    ) -> String {
+        if namespace.is_empty() {
+            return inner.to_string();
+        }
+
+        let mut result = String::new();
+
+        // Opening namespace declarations
+        for (i, ns) in namespace.iter().enumerate() {
+            for _ in 0..i {
+                result.push_str(indent);
+            }
+            result.push_str(export);
+            if i == 0 {
+                result.push_str(declare);
+            }
+            result.push_str("namespace ");
+            result.push_str(ns);
+            result.push_str(" {\n");
+        }
+
+        // Inner content with proper indentation
+        let depth = namespace.len();
+        for line in inner.lines() {
+            for _ in 0..depth {
+                result.push_str(indent);
+            }
+            result.push_str(line);
+            result.push('\n');
+        }
+
+        // Closing braces
+        for i in (0..depth).rev() {
+            for _ in 0..i {
+                result.push_str(indent);
+            }
+            result.push('}');
+            if i > 0 {
+                result.push('\n');
+            }
+        }
+
+        result
+    }
+}
+
+impl Primitive {
+    /// Renders this primitive to its TypeScript keyword.
+    pub fn render(&self) -> &'static str {
+        match self {
+            Primitive::String => "string",
+            Primitive::Number => "number",
+            Primitive::Boolean => "boolean",
+            Primitive::Null => "null",
+            Primitive::Undefined => "undefined",
+            Primitive::Void => "void",
+            Primitive::Never => "never",
+            Primitive::Any => "any",
+            Primitive::Unknown => "unknown",
+            Primitive::BigInt => "bigint",
+        }
+    }
+}
+
+impl IntWidth {
+    /// The policy of integers of this width that have none: `number` for
+    /// 64 bits, which serde writes as JSON numbers, and `bigint` for 128.
+    pub fn default_policy(self) -> IntPolicy {
+        match self {
+            IntWidth::Bits64 => IntPolicy::Number,
+            IntWidth::Bits128 => IntPolicy::BigInt,
+        }
+    }
+
+    /// The TypeScript type of an integer of this width under `policy`, or
+    /// under the default policy if `None`.
+    pub fn repr(self, policy: Option<IntPolicy>) -> TypeDef {
+        policy.unwrap_or(self.default_policy()).typedef()
+    }
+}
+
+impl IntPolicy {
+    /// The TypeScript type of integers under this policy.
+    pub fn typedef(self) -> TypeDef {
+        match self {
+            IntPolicy::Number => TypeDef::Primitive(Primitive::Number),
+            IntPolicy::BigInt => TypeDef::Primitive(Primitive::BigInt),
+            IntPolicy::String => TypeDef::Primitive(Primitive::String),
+            IntPolicy::NumberOrString => TypeDef::Union(vec![
+                TypeDef::Primitive(Primitive::Number),
+                TypeDef::Primitive(Primitive::String),
+            ]),
+        }
+    }
+}
+
+/// A member of a [`TypeDef::Enum`]: `Name = value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumMember {
+    /// The member name.
+    pub name: String,
+    /// The member's value; a string or number literal.
+    pub value: Literal,
+    /// Documentation emitted as a JSDoc comment before the member.
+    pub docs: Option<Docs>,
+}
+
+impl EnumMember {
+    /// Creates a new enum member.
+    pub fn new(name: impl Into<String>, value: Literal) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            docs: None,
+        }
+    }
+
+    /// Attaches documentation to this member.
+    pub fn with_docs(mut self, docs: Docs) -> Self {
+        self.docs = Some(docs);
+        self
+    }
+}
+
+/// How a [`TypeDef::Enum`] is declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnumStyle {
+    /// `enum Name { ... }`
+    Enum,
+    /// `const enum Name { ... }`, inlined by the TypeScript compiler
+    ConstEnum,
+    /// `const Name = { ... } as const;` plus a union type of its values
+    AsConst,
+}
+
+/// How named object types are declared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DeclarationStyle {
+    /// `type Name = { ... };` (default)
+    #[default]
+    TypeAlias,
+    /// `interface Name { ... }`, or `interface Name extends Base { ... }` for
+    /// an intersection of named types with an object. Unions and other
+    /// shapes an interface cannot express are still declared as type aliases.
+    Interface,
+}
+
+/// Options for [`TypeDef::render_declaration_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeclarationOptions {
+    /// Whether declarations are prefixed with `export`
+    pub export: bool,
+    /// Whether declarations are ambient, for a `.d.ts` file
+    pub ambient: bool,
+    /// How object types are declared, unless the type sets its own style
+    pub style: DeclarationStyle,
+    /// Pretty-print declarations in this format, instead of on one line
+    pub format: Option<Format>,
+}
+
+/// Layout of pretty-printed TypeScript (see [`TypeDef::render_pretty`]).
+///
+/// Objects, unions and enums stay on one line when they fit within
+/// `max_width`, and are otherwise broken into one member per line:
+///
+/// ```typescript
+/// type User = {
+///     id: string;
+///     /** Display name. */
+///     name: string;
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Format {
+    /// Indentation of each nesting level
+    pub indent: Indent,
+    /// Line width that output should stay within
+    pub max_width: usize,
+    /// Separator between the fields of an object type
+    pub separator: Separator,
+    /// Whether a union of two or more object types is always broken into
+    /// one member per line, even when it would fit on one
+    pub split_discriminated_unions: bool,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            indent: Indent::Spaces(4),
+            max_width: 100,
+            separator: Separator::Semicolon,
+            split_discriminated_unions: true,
+        }
+    }
+}
+
+impl Format {
+    /// Creates the default format: four spaces, 100 columns, semicolons and
+    /// split discriminated unions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the indentation of each nesting level.
+    pub fn indent(mut self, indent: Indent) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the line width that output should stay within.
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Sets the separator between the fields of an object type.
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets whether discriminated unions always get one member per line.
+    pub fn split_discriminated_unions(mut self, split: bool) -> Self {
+        self.split_discriminated_unions = split;
+        self
+    }
+
+    /// Returns true if `text` fits on one line starting at `column`.
+    fn fits(&self, column: usize, text: &str) -> bool {
+        !text.contains('\n') && column.saturating_add(text.chars().count()) <= self.max_width
+    }
+}
+
+/// Indentation of one nesting level in a [`Format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// The given number of spaces
+    Spaces(usize),
+    /// A tab, counted as four columns
+    Tab,
+}
+
+impl Indent {
+    /// Returns the number of columns one level takes.
+    pub fn width(&self) -> usize {
+        match self {
+            Indent::Spaces(n) => *n,
+            Indent::Tab => 4,
+        }
+    }
+
+    /// Renders `level` levels of indentation.
+    pub fn render(&self, level: usize) -> String {
+        match self {
+            Indent::Spaces(n) => " ".repeat(n * level),
+            Indent::Tab => "\t".repeat(level),
+        }
+    }
+}
+
+/// Separator between the fields of a pretty-printed object type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Separator {
+    /// `{ a: string; b: number }` (default)
+    #[default]
+    Semicolon,
+    /// `{ a: string, b: number }`
+    Comma,
+}
+
+impl Separator {
+    /// Renders this separator.
+    pub fn render(&self) -> &'static str {
+        match self {
+            Separator::Semicolon => ";",
+            Separator::Comma => ",",
+        }
+    }
+}
+
+impl Literal {
+    /// Renders this literal to TypeScript syntax.
+    pub fn render(&self) -> String {
+        match self {
+            Literal::String(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+            Literal::Number(n) => {
+                if n.fract() == 0.0 {
+                    format!("{}", *n as i64)
+                } else {
+                    format!("{}", n)
+                }
+            }
+            Literal::Boolean(b) => b.to_string(),
+        }
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Renders a property or enum member name, quoting it unless it is a valid
+/// identifier.
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        Literal::String(name.to_string()).render()
+    }
+}
+
+/// Returns the column at which a line continues after `text`, which
+/// started at `column`.
+fn end_column(column: usize, text: &str) -> usize {
+    match text.rfind('\n') {
+        Some(i) => text[i + 1..].chars().count(),
+        None => column + text.chars().count(),
+    }
+}
+
+/// Returns true if `name` is a possibly namespace-qualified identifier, such
+/// as `User` or `VM.Git.State`.
+fn is_qualified_name(name: &str) -> bool {
+    name.split('.').all(|segment| property_name(segment) == segment)
+}
+
+/// Builds an object with `fields` and the contents of the `flattened`
+/// types, as `#[serde(flatten)]` writes them.
+///
+/// The fields of objects, including named ones, are merged in. Types that
+/// aren't plain objects, such as maps and enums, are intersected with the
+/// rest, and an `Option` adds its fields only when present. A map's entries
+/// sit beside the fields, so its value type is widened to their types too:
+///
+/// ```ignore
+/// let extra = flatten_object(vec![Field::new("id", u8::typescript())], vec![HashMap::<String, String>::typescript()]);
+/// assert_eq!(extra.render(), "{ id: number } & Record<string, string | number>");
+/// ```
+pub fn flatten_object(mut fields: Vec<Field>, flattened: Vec<TypeDef>) -> TypeDef {
+    let mut rest = Vec::new();
+    for typedef in flattened {
+        flatten_into(typedef, &mut fields, &mut rest);
+    }
+    for typedef in &mut rest {
+        widen_record(typedef, &fields);
+    }
+    match (fields.is_empty(), rest.len()) {
+        (_, 0) => TypeDef::Object(fields),
+        (true, 1) => rest.remove(0),
+        (true, _) => TypeDef::Intersection(rest),
+        (false, _) => {
+            rest.insert(0, TypeDef::Object(fields));
+            TypeDef::Intersection(rest)
+        }
+    }
+}
+
+/// Merges the fields of a flattened type into `fields`, or adds it to the
+/// types intersected with them.
+fn flatten_into(typedef: TypeDef, fields: &mut Vec<Field>, rest: &mut Vec<TypeDef>) {
+    match typedef {
+        TypeDef::Object(inner) => fields.extend(inner),
+        TypeDef::Named { def, .. } if matches!(*def, TypeDef::Object(_) | TypeDef::Intersection(_)) => {
+            flatten_into(*def, fields, rest);
+        }
+        TypeDef::Intersection(types) => {
+            for ty in types {
+                flatten_into(ty, fields, rest);
+            }
+        }
+        // `None` writes no fields at all, rather than `null`
+        TypeDef::Union(variants) if variants.contains(&TypeDef::Primitive(Primitive::Null)) => {
+            let mut present: Vec<_> =
+                variants.into_iter().filter(|ty| *ty != TypeDef::Primitive(Primitive::Null)).collect();
+            let present = if present.len() == 1 { present.remove(0) } else { TypeDef::Union(present) };
+            rest.push(TypeDef::Union(vec![present, TypeDef::Object(vec![])]));
+        }
+        other => rest.push(other),
+    }
+}
+
+/// Widens the value type of a flattened map to the types of the `fields`
+/// beside it, since every property of the object is one of its entries.
+fn widen_record(typedef: &mut TypeDef, fields: &[Field]) {
+    let members = |ty: &TypeDef| match ty {
+        TypeDef::Union(variants) => variants.clone(),
+        other => vec![other.clone()],
+    };
+    let covers = |wide: &TypeDef, ty: &TypeDef| {
+        wide == ty
+            || matches!(wide, TypeDef::Primitive(Primitive::Any | Primitive::Unknown))
+            || matches!(
+                (wide, ty),
+                (TypeDef::Primitive(Primitive::String), TypeDef::Literal(Literal::String(_)))
+                    | (TypeDef::Primitive(Primitive::Number), TypeDef::Literal(Literal::Number(_)))
+                    | (TypeDef::Primitive(Primitive::Boolean), TypeDef::Literal(Literal::Boolean(_)))
+            )
+    };
+    match typedef {
+        TypeDef::Record { value, .. } => {
+            let mut widened = members(value);
+            for ty in fields.iter().flat_map(|f| members(&f.ty)) {
+                if !widened.iter().any(|wide| covers(wide, &ty)) {
+                    widened.push(ty);
+                }
+            }
+            **value = if widened.len() == 1 { widened.remove(0) } else { TypeDef::Union(widened) };
+        }
+        // The map of a flattened `Option`
+        TypeDef::Union(variants) => {
+            for variant in variants {
+                widen_record(variant, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Extracts fields from an Object TypeDef, unwrapping Named if necessary.
+///
+/// See [`flatten_object`] for flattening types that may not be objects.
+///
+/// # Panics
+///
+/// Panics if the TypeDef is not an Object (or Named wrapping an Object).
+pub fn extract_object_fields(typedef: &TypeDef) -> Vec<Field> {
+    match typedef {
+        TypeDef::Object(fields) => fields.clone(),
+        TypeDef::Named { def, .. } => extract_object_fields(def),
+        other => panic!(
+            "#[ts(flatten)] can only be used on fields with object types, got: {:?}",
+            other
+        ),
+    }
+}
+
+thread_local! {
+    /// Types whose definitions are currently being built on this thread.
+    static IN_PROGRESS: std::cell::RefCell<HashSet<&'static str>> =
+        std::cell::RefCell::new(HashSet::new());
+}
+
+/// Removes a type from the in-progress set when dropped, even on panic.
+struct InProgressGuard(&'static str);
+
+impl Drop for InProgressGuard {
+    fn drop(&mut self) {
+        IN_PROGRESS.with(|set| {
+            set.borrow_mut().remove(self.0);
+        });
+    }
+}
+
+/// Builds a type definition for `T`, breaking cycles in recursive types.
+///
+/// This is used by the derive macro so that self-referential and mutually
+/// recursive types don't overflow the stack. The first call for `T` runs
+/// `build`; any nested call for the same `T` made while `build` is still
+/// running returns `reference()` instead (typically a [`TypeDef::Ref`] to
+/// the type's name).
+///
+/// # Example
+///
+/// ```ignore
+/// struct TreeNode { children: Vec<TreeNode> }
+///
+/// impl TS for TreeNode {
+///     fn typescript() -> TypeDef {
+///         ferro_type::recursion_guard::<Self>(
+///             || TypeDef::Ref("TreeNode".into()),
+///             || TypeDef::Named {
+///                 namespace: vec![],
+///                 name: "TreeNode".into(),
+///                 def: Box::new(TypeDef::Object(vec![
+///                     Field::new("children", <Vec<TreeNode> as TS>::typescript()),
+///                 ])),
+///                 module: None,
+///                 wrapper: None,
+///                 docs: None,
+///                 declaration_style: None,
+///             },
+///         )
+///     }
+/// }
+/// // Renders as: type TreeNode = { children: TreeNode[] };
+/// ```
+pub fn recursion_guard<T: ?Sized>(
+    reference: impl FnOnce() -> TypeDef,
+    build: impl FnOnce() -> TypeDef,
+) -> TypeDef {
+    let key = std::any::type_name::<T>();
+    let entered = IN_PROGRESS.with(|set| set.borrow_mut().insert(key));
+    if !entered {
+        return reference();
+    }
+    let _guard = InProgressGuard(key);
+    build()
+}
+
+/// Extracts the inner type definition, unwrapping Named if necessary.
+///
+/// This is used by the derive macro to implement `#[ts(inline)]`. When a field
 /// is marked with inline, the full type definition is inlined instead of using
 /// a type reference.
 pub fn inline_typedef(typedef: TypeDef) -> TypeDef {
@@ -706,7 +2031,7 @@ pub fn inline_typedef(typedef: TypeDef) -> TypeDef {
 // TYPE REGISTRY
 // ============================================================================
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
 /// A registry for collecting and managing TypeScript type definitions.
 ///
@@ -734,61 +2059,218 @@ pub struct TypeRegistry {
     types: HashMap<String, TypeDef>,
     /// Order in which types were registered (for stable output when no deps)
     registration_order: Vec<String>,
+    /// TypeScript to declare in place of a named type's definition
+    overrides: HashMap<String, String>,
+    /// How object types are declared, unless they set their own style
+    declaration_style: DeclarationStyle,
+    /// Layout of rendered declarations, or `None` for one line each
+    format: Option<Format>,
+    /// Policies for large integers without one of their own, by width
+    int_policies: HashMap<IntWidth, IntPolicy>,
+    /// The side of serialization objects are rendered for, or `None` for both
+    shape: Option<Shape>,
 }
 
-impl TypeRegistry {
-    /// Creates a new empty registry.
-    pub fn new() -> Self {
-        Self::default()
+impl TypeRegistry {
+    /// Creates a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry populated with all auto-registered types.
+    ///
+    /// This collects all types that were registered via the `#[derive(TypeScript)]`
+    /// macro using the distributed slice mechanism.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use ferrotype::{TypeRegistry, TypeScript};
+    ///
+    /// #[derive(TypeScript)]
+    /// struct User { name: String, age: u32 }
+    ///
+    /// #[derive(TypeScript)]
+    /// struct Post { title: String, author: User }
+    ///
+    /// // Collect all types automatically - no manual registration needed!
+    /// let registry = TypeRegistry::from_distributed();
+    /// println!("{}", registry.render());
+    /// ```
+    pub fn from_distributed() -> Self {
+        let mut registry = Self::new();
+        for type_fn in TYPESCRIPT_TYPES {
+            let typedef = type_fn();
+            registry.add_typedef(typedef);
+        }
+        registry
+    }
+
+    /// Collects all auto-registered types into this registry.
+    ///
+    /// This is useful when you want to add auto-registered types to an existing
+    /// registry that may already have some manually registered types.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = TypeRegistry::new();
+    /// // Add some manual types first
+    /// registry.register::<SomeManualType>();
+    /// // Then collect all auto-registered types
+    /// registry.collect_all();
+    /// ```
+    pub fn collect_all(&mut self) {
+        for type_fn in TYPESCRIPT_TYPES {
+            let typedef = type_fn();
+            self.add_typedef(typedef);
+        }
+    }
+
+    /// Declares the named type `name` as the TypeScript type `ts`.
+    ///
+    /// The declaration keeps its name, so references to it are unchanged, but
+    /// its derived definition is replaced by `ts` verbatim. Applies to types
+    /// registered before and after the call; `name` is the qualified name
+    /// (e.g. `"VM.State"` for a namespaced type).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.override_type("Timestamp", "string");
+    /// registry.register::<Event>();
+    /// // type Timestamp = string;
+    /// ```
+    pub fn override_type(&mut self, name: impl Into<String>, ts: impl Into<String>) {
+        let name = name.into();
+        let ts = ts.into();
+        if let Some(typedef) = self.types.get_mut(&name) {
+            Self::apply_override(typedef, &ts);
+        }
+        self.overrides.insert(name, ts);
+    }
+
+    /// Sets how object types are declared when rendering.
+    ///
+    /// Types that set their own style, such as with `#[ts(interface)]`, keep
+    /// it. The default, [`DeclarationStyle::TypeAlias`], declares every type
+    /// as `type Name = ...;`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.set_declaration_style(DeclarationStyle::Interface);
+    /// registry.register::<User>();
+    /// // interface User { name: string }
+    /// ```
+    pub fn set_declaration_style(&mut self, style: DeclarationStyle) {
+        self.declaration_style = style;
+    }
+
+    /// Returns how object types are declared when rendering.
+    pub fn declaration_style(&self) -> DeclarationStyle {
+        self.declaration_style
     }
 
-    /// Creates a registry populated with all auto-registered types.
+    /// Sets the layout of rendered declarations.
     ///
-    /// This collects all types that were registered via the `#[derive(TypeScript)]`
-    /// macro using the distributed slice mechanism.
+    /// With `Some(format)`, declarations are pretty-printed (see
+    /// [`TypeDef::render_pretty`]); with `None`, the default, each is
+    /// rendered on one line.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use ferrotype::{TypeRegistry, TypeScript};
+    /// registry.set_format(Some(Format::new().max_width(80)));
+    /// registry.register::<CreateOrderRequest>();
+    /// // type CreateOrderRequest = {
+    /// //     customer_id: string;
+    /// //     ...
+    /// // };
+    /// ```
+    pub fn set_format(&mut self, format: Option<Format>) {
+        self.format = format;
+    }
+
+    /// Returns the layout of rendered declarations.
+    pub fn format(&self) -> Option<&Format> {
+        self.format.as_ref()
+    }
+
+    /// Sets how integers of `width` are represented, unless they set their
+    /// own policy, such as with `#[ts(int64 = "...")]`.
     ///
-    /// #[derive(TypeScript)]
-    /// struct User { name: String, age: u32 }
+    /// Applies to types registered before and after the call, like
+    /// [`override_type`](Self::override_type).
     ///
-    /// #[derive(TypeScript)]
-    /// struct Post { title: String, author: User }
+    /// # Example
     ///
-    /// // Collect all types automatically - no manual registration needed!
-    /// let registry = TypeRegistry::from_distributed();
-    /// println!("{}", registry.render());
+    /// ```ignore
+    /// registry.set_int_policy(IntWidth::Bits64, IntPolicy::String);
+    /// registry.register::<Message>();
+    /// // type Message = { id: string; text: string };
     /// ```
-    pub fn from_distributed() -> Self {
-        let mut registry = Self::new();
-        for type_fn in TYPESCRIPT_TYPES {
-            let typedef = type_fn();
-            registry.add_typedef(typedef);
+    pub fn set_int_policy(&mut self, width: IntWidth, policy: IntPolicy) {
+        self.int_policies.insert(width, policy);
+        for typedef in self.types.values_mut() {
+            if let TypeDef::Named { def, .. } | TypeDef::GenericDef { def, .. } = typedef {
+                def.fill_int_policy(width, policy);
+            }
         }
-        registry
     }
 
-    /// Collects all auto-registered types into this registry.
+    /// Returns how integers of `width` are represented when they don't set
+    /// their own policy, or `None` if no policy was set.
+    pub fn int_policy(&self, width: IntWidth) -> Option<IntPolicy> {
+        self.int_policies.get(&width).copied()
+    }
+
+    /// Renders objects as they are on one side of serialization, exact for
+    /// each field's [`FieldShape`].
     ///
-    /// This is useful when you want to add auto-registered types to an existing
-    /// registry that may already have some manually registered types.
+    /// Applies to types registered before and after the call, like
+    /// [`set_int_policy`](Self::set_int_policy). Shaped fields are exact, so
+    /// only the first shape set changes them.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let mut registry = TypeRegistry::new();
-    /// // Add some manual types first
-    /// registry.register::<SomeManualType>();
-    /// // Then collect all auto-registered types
-    /// registry.collect_all();
+    /// registry.set_shape(Shape::Input);
+    /// registry.register::<NewPost>();
+    /// // type NewPost = { title: string; tags?: string[] };
     /// ```
-    pub fn collect_all(&mut self) {
-        for type_fn in TYPESCRIPT_TYPES {
-            let typedef = type_fn();
-            self.add_typedef(typedef);
+    pub fn set_shape(&mut self, shape: Shape) {
+        self.shape = Some(shape);
+        for typedef in self.types.values_mut() {
+            if let TypeDef::Named { def, .. } | TypeDef::GenericDef { def, .. } = typedef {
+                def.fill_shape(shape);
+            }
+        }
+    }
+
+    /// Returns the side of serialization objects are rendered for, or
+    /// `None` if they describe both.
+    pub fn shape(&self) -> Option<Shape> {
+        self.shape
+    }
+
+    /// Applies the registry's integer policies and shape to the definition
+    /// of a named type.
+    fn apply_policies(&self, typedef: &mut TypeDef) {
+        if let TypeDef::Named { def, .. } | TypeDef::GenericDef { def, .. } = typedef {
+            for (&width, &policy) in &self.int_policies {
+                def.fill_int_policy(width, policy);
+            }
+            if let Some(shape) = self.shape {
+                def.fill_shape(shape);
+            }
+        }
+    }
+
+    /// Replaces the definition of a named type with a raw TypeScript type.
+    fn apply_override(typedef: &mut TypeDef, ts: &str) {
+        if let TypeDef::Named { def, .. } | TypeDef::GenericDef { def, .. } = typedef {
+            **def = TypeDef::Ref(ts.to_string());
         }
     }
 
@@ -818,10 +2300,23 @@ impl TypeRegistry {
                 };
 
                 if !self.types.contains_key(&qualified_name) {
-                    self.types.insert(qualified_name.clone(), typedef.clone());
+                    let mut typedef = typedef.clone();
+                    let overridden = match self.overrides.get(&qualified_name) {
+                        Some(ts) => {
+                            Self::apply_override(&mut typedef, ts);
+                            true
+                        }
+                        None => {
+                            self.apply_policies(&mut typedef);
+                            false
+                        }
+                    };
+                    self.types.insert(qualified_name.clone(), typedef);
                     self.registration_order.push(qualified_name);
-                    // Also extract from the inner definition
-                    self.extract_named_types(def);
+                    // Also extract from the inner definition, unless replaced
+                    if !overridden {
+                        self.extract_named_types(def);
+                    }
                 }
             }
             TypeDef::Array(inner) => self.extract_named_types(inner),
@@ -850,7 +2345,10 @@ impl TypeRegistry {
                 }
                 self.extract_named_types(return_type);
             }
-            TypeDef::Generic { args, .. } => {
+            TypeDef::Generic { args, def, .. } => {
+                if let Some(def) = def {
+                    self.extract_named_types(def);
+                }
                 for arg in args {
                     self.extract_named_types(arg);
                 }
@@ -860,9 +2358,17 @@ impl TypeRegistry {
                     self.extract_named_types(ty);
                 }
             }
-            TypeDef::GenericDef { name, type_params, def } => {
+            TypeDef::GenericDef { name, type_params, def, .. } => {
                 if !self.types.contains_key(name) {
-                    self.types.insert(name.clone(), typedef.clone());
+                    let mut typedef = typedef.clone();
+                    if let Some(ts) = self.overrides.get(name) {
+                        Self::apply_override(&mut typedef, ts);
+                        self.types.insert(name.clone(), typedef);
+                        self.registration_order.push(name.clone());
+                        return;
+                    }
+                    self.apply_policies(&mut typedef);
+                    self.types.insert(name.clone(), typedef);
                     self.registration_order.push(name.clone());
                     // Extract from type parameter constraints and defaults
                     for param in type_params {
@@ -878,8 +2384,14 @@ impl TypeRegistry {
                 }
             }
             // IndexedAccess references a base type by name (similar to Ref)
-            // Primitives, Refs, Literals, TypeParamRefs, and IndexedAccess have no nested named types
-            TypeDef::Primitive(_) | TypeDef::Ref(_) | TypeDef::Literal(_) | TypeDef::IndexedAccess { .. } | TypeDef::TypeParamRef(_) => {}
+            // Primitives, Refs, Literals, TypeParamRefs, IndexedAccess, Enums and LargeInts have no nested named types
+            TypeDef::Primitive(_)
+            | TypeDef::Ref(_)
+            | TypeDef::Literal(_)
+            | TypeDef::IndexedAccess { .. }
+            | TypeDef::TypeParamRef(_)
+            | TypeDef::Enum { .. }
+            | TypeDef::LargeInt { .. } => {}
         }
     }
 
@@ -903,19 +2415,62 @@ impl TypeRegistry {
         self.types.get(name)
     }
 
+    /// Returns the registered types that the named type references, in
+    /// registration order.
+    ///
+    /// A recursive type includes its own name. Returns an empty list for
+    /// unknown names.
+    pub fn dependencies(&self, name: &str) -> Vec<&str> {
+        let Some(typedef) = self.types.get(name) else {
+            return Vec::new();
+        };
+        let mut deps: Vec<&str> = self
+            .get_dependencies(typedef)
+            .iter()
+            .filter_map(|dep| self.types.get_key_value(dep).map(|(k, _)| k.as_str()))
+            .collect();
+        deps.sort_by_key(|dep| (self.registration_index(dep), *dep));
+        deps
+    }
+
     /// Computes the dependencies for a type (what other named types it references).
+    ///
+    /// A type that references itself (directly or through nested definitions)
+    /// includes its own name in the result.
     fn get_dependencies(&self, typedef: &TypeDef) -> HashSet<String> {
         let mut deps = HashSet::new();
-        self.collect_dependencies(typedef, &mut deps);
+        match typedef {
+            // Don't add the declaration itself as a dependency, but check its body
+            TypeDef::Named { def, .. } => self.collect_dependencies(def, &mut deps),
+            TypeDef::GenericDef { type_params, def, .. } => {
+                for param in type_params {
+                    if let Some(ref constraint) = param.constraint {
+                        self.collect_dependencies(constraint, &mut deps);
+                    }
+                    if let Some(ref default) = param.default {
+                        self.collect_dependencies(default, &mut deps);
+                    }
+                }
+                self.collect_dependencies(def, &mut deps);
+            }
+            other => self.collect_dependencies(other, &mut deps),
+        }
         deps
     }
 
     /// Recursively collects dependencies from a TypeDef.
     fn collect_dependencies(&self, typedef: &TypeDef, deps: &mut HashSet<String>) {
         match typedef {
-            TypeDef::Named { def, .. } => {
-                // Don't add self as dependency, but check inner def
-                self.collect_dependencies(def, deps);
+            TypeDef::Named { namespace, name, .. } => {
+                // A nested named type is a reference to its own declaration
+                let qualified_name = if namespace.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", namespace.join("."), name)
+                };
+                if self.types.contains_key(&qualified_name) {
+                    deps.insert(qualified_name);
+                }
             }
             TypeDef::Ref(name) => {
                 if self.types.contains_key(name) {
@@ -953,7 +2508,7 @@ impl TypeRegistry {
                 }
                 self.collect_dependencies(return_type, deps);
             }
-            TypeDef::Generic { base, args } => {
+            TypeDef::Generic { base, args, .. } => {
                 // The base generic type itself is a dependency
                 if self.types.contains_key(base) {
                     deps.insert(base.clone());
@@ -973,90 +2528,193 @@ impl TypeRegistry {
                     deps.insert(base.clone());
                 }
             }
-            TypeDef::GenericDef { type_params, def, .. } => {
-                // Don't add self as dependency, but check constraints, defaults, and inner def
-                for param in type_params {
-                    if let Some(ref constraint) = param.constraint {
-                        self.collect_dependencies(constraint, deps);
-                    }
-                    if let Some(ref default) = param.default {
-                        self.collect_dependencies(default, deps);
-                    }
+            TypeDef::GenericDef { name, .. } => {
+                // A nested generic definition is a reference to its own declaration
+                if self.types.contains_key(name) {
+                    deps.insert(name.clone());
                 }
-                self.collect_dependencies(def, deps);
             }
             // TypeParamRef references a type parameter, not a named type, so no dependency
-            TypeDef::Primitive(_) | TypeDef::Literal(_) | TypeDef::TypeParamRef(_) => {}
+            TypeDef::Primitive(_)
+            | TypeDef::Literal(_)
+            | TypeDef::TypeParamRef(_)
+            | TypeDef::Enum { .. }
+            | TypeDef::LargeInt { .. } => {}
         }
     }
 
-    /// Returns types in dependency order (types with no dependencies first).
-    ///
-    /// Uses Kahn's algorithm for topological sort.
-    pub fn sorted_types(&self) -> Vec<&str> {
-        // Build dependency graph
-        let mut in_degree: HashMap<&str, usize> = HashMap::new();
-        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    /// Returns the position of a type in registration order.
+    fn registration_index(&self, name: &str) -> usize {
+        self.registration_order
+            .iter()
+            .position(|n| n == name)
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Builds the dependency graph: for each type (in registration order),
+    /// its dependencies sorted by registration order.
+    fn dependency_graph(&self) -> Vec<(&str, Vec<&str>)> {
+        let mut names: Vec<&str> = self.types.keys().map(|s| s.as_str()).collect();
+        names.sort_by_key(|name| (self.registration_index(name), *name));
+
+        names
+            .into_iter()
+            .map(|name| (name, self.dependencies(name)))
+            .collect()
+    }
 
-        // Initialize
-        for name in self.types.keys() {
-            in_degree.insert(name.as_str(), 0);
-            dependents.insert(name.as_str(), Vec::new());
+    /// Computes the strongly connected components of the dependency graph.
+    ///
+    /// Uses Tarjan's algorithm. Components are returned with their members in
+    /// registration order; types that are not part of a cycle form
+    /// single-member components.
+    fn strongly_connected_components<'a>(&self, graph: &[(&'a str, Vec<&'a str>)]) -> Vec<Vec<&'a str>> {
+        struct Tarjan<'a, 'g> {
+            graph: &'g [(&'a str, Vec<&'a str>)],
+            lookup: HashMap<&'a str, usize>,
+            index: Vec<Option<usize>>,
+            lowlink: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            next_index: usize,
+            components: Vec<Vec<&'a str>>,
         }
 
-        // Calculate in-degrees and build reverse graph
-        for (name, typedef) in &self.types {
-            let deps = self.get_dependencies(typedef);
-            for dep in deps {
-                if let Some(dep_name) = self.types.get_key_value(&dep) {
-                    *in_degree.get_mut(name.as_str()).unwrap() += 1;
-                    dependents.get_mut(dep_name.0.as_str()).unwrap().push(name.as_str());
+        impl<'a> Tarjan<'a, '_> {
+            fn visit(&mut self, v: usize) {
+                self.index[v] = Some(self.next_index);
+                self.lowlink[v] = self.next_index;
+                self.next_index += 1;
+                self.stack.push(v);
+                self.on_stack[v] = true;
+
+                for dep in &self.graph[v].1 {
+                    let w = self.lookup[dep];
+                    match self.index[w] {
+                        None => {
+                            self.visit(w);
+                            self.lowlink[v] = self.lowlink[v].min(self.lowlink[w]);
+                        }
+                        Some(w_index) if self.on_stack[w] => {
+                            self.lowlink[v] = self.lowlink[v].min(w_index);
+                        }
+                        Some(_) => {}
+                    }
+                }
+
+                if Some(self.lowlink[v]) == self.index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = self.stack.pop() {
+                        self.on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    self.components.push(component.into_iter().map(|i| self.graph[i].0).collect());
                 }
             }
         }
 
-        // Kahn's algorithm
-        let mut queue: VecDeque<&str> = VecDeque::new();
-        let mut result: Vec<&str> = Vec::new();
+        let mut tarjan = Tarjan {
+            graph,
+            lookup: graph.iter().enumerate().map(|(i, (name, _))| (*name, i)).collect(),
+            index: vec![None; graph.len()],
+            lowlink: vec![0; graph.len()],
+            on_stack: vec![false; graph.len()],
+            stack: Vec::new(),
+            next_index: 0,
+            components: Vec::new(),
+        };
 
-        // Start with types that have no dependencies
-        for (name, &degree) in &in_degree {
-            if degree == 0 {
-                queue.push_back(name);
+        for v in 0..graph.len() {
+            if tarjan.index[v].is_none() {
+                tarjan.visit(v);
             }
         }
 
-        // Sort the initial queue by registration order for stable output
-        let mut initial: Vec<_> = queue.drain(..).collect();
-        initial.sort_by_key(|name| {
-            self.registration_order.iter().position(|n| n == *name).unwrap_or(usize::MAX)
-        });
-        queue.extend(initial);
+        tarjan.components
+    }
 
-        while let Some(name) = queue.pop_front() {
-            result.push(name);
+    /// Returns groups of mutually recursive types.
+    ///
+    /// Each group is a cycle in the dependency graph: either a single type
+    /// that references itself, or several types that reference each other.
+    /// Groups and their members are ordered by registration order.
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let graph = self.dependency_graph();
+        let mut cycles: Vec<Vec<&str>> = self
+            .strongly_connected_components(&graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || graph
+                        .iter()
+                        .any(|(name, deps)| *name == component[0] && deps.contains(name))
+            })
+            .collect();
+        cycles.sort_by_key(|component| self.registration_index(component[0]));
+        cycles
+    }
 
-            // Get dependents sorted by registration order for stable output
-            let mut deps: Vec<_> = dependents.get(name).map(|v| v.as_slice()).unwrap_or(&[]).to_vec();
-            deps.sort_by_key(|n| {
-                self.registration_order.iter().position(|name| name == *n).unwrap_or(usize::MAX)
-            });
-
-            for dependent in deps {
-                let degree = in_degree.get_mut(dependent).unwrap();
-                *degree -= 1;
-                if *degree == 0 {
-                    queue.push_back(dependent);
+    /// Returns true if the named type is part of a dependency cycle.
+    pub fn is_recursive(&self, name: &str) -> bool {
+        self.cycles().iter().any(|cycle| cycle.contains(&name))
+    }
+
+    /// Returns types in dependency order (types with no dependencies first).
+    ///
+    /// Uses Kahn's algorithm over the strongly connected components of the
+    /// dependency graph, so recursive types are emitted as a group (in
+    /// registration order) once everything they depend on has been emitted.
+    /// Ties are broken by registration order, making the output deterministic.
+    pub fn sorted_types(&self) -> Vec<&str> {
+        let graph = self.dependency_graph();
+        let components = self.strongly_connected_components(&graph);
+
+        // Map each type to its component
+        let mut component_of: HashMap<&str, usize> = HashMap::new();
+        for (i, component) in components.iter().enumerate() {
+            for name in component {
+                component_of.insert(name, i);
+            }
+        }
+
+        // Build the condensed graph between components
+        let mut in_degree: Vec<usize> = vec![0; components.len()];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); components.len()];
+        for (name, deps) in &graph {
+            let from = component_of[name];
+            for dep in deps {
+                let to = component_of[dep];
+                if from != to && !dependents[to].contains(&from) {
+                    dependents[to].push(from);
+                    in_degree[from] += 1;
                 }
             }
         }
 
-        // If result doesn't contain all types, there's a cycle
-        // Fall back to registration order for remaining types
-        if result.len() < self.types.len() {
-            for name in &self.registration_order {
-                if !result.contains(&name.as_str()) {
-                    result.push(name.as_str());
+        // Components are prioritized by their earliest-registered member
+        let priority = |c: &usize| self.registration_index(components[*c][0]);
+
+        // Kahn's algorithm, starting with components that have no dependencies
+        let mut initial: Vec<usize> = (0..components.len()).filter(|c| in_degree[*c] == 0).collect();
+        initial.sort_by_key(priority);
+        let mut queue: VecDeque<usize> = initial.into();
+        let mut result: Vec<&str> = Vec::new();
+
+        while let Some(c) = queue.pop_front() {
+            result.extend(components[c].iter().copied());
+
+            // Get dependents sorted by registration order for stable output
+            let mut next = dependents[c].clone();
+            next.sort_by_key(priority);
+
+            for dependent in next {
+                in_degree[dependent] -= 1;
+                if in_degree[dependent] == 0 {
+                    queue.push_back(dependent);
                 }
             }
         }
@@ -1068,28 +2726,37 @@ impl TypeRegistry {
     ///
     /// Types are emitted in dependency order, with proper formatting.
     pub fn render(&self) -> String {
-        let sorted = self.sorted_types();
-        let mut output = String::new();
-
-        // Add header comment
-        output.push_str("// Generated by ferrotype\n");
-        output.push_str("// Do not edit manually\n\n");
-
-        for name in sorted {
-            if let Some(typedef) = self.types.get(name) {
-                output.push_str(&typedef.render_declaration());
-                output.push_str("\n\n");
-            }
-        }
-
-        // Remove trailing newline
-        output.trim_end().to_string() + "\n"
+        self.render_with(false, false)
     }
 
     /// Renders all registered types with `export` keywords.
     ///
     /// For namespaced types, exports the namespace declaration.
     pub fn render_exported(&self) -> String {
+        self.render_with(true, false)
+    }
+
+    /// Renders all registered types as ambient declarations for a `.d.ts`
+    /// file (see [`TypeDef::render_ambient_declaration`]).
+    pub fn render_ambient(&self) -> String {
+        self.render_with(false, true)
+    }
+
+    /// Renders all registered types as exported ambient declarations for a
+    /// `.d.ts` file (see [`TypeDef::render_exported_ambient_declaration`]).
+    pub fn render_exported_ambient(&self) -> String {
+        self.render_with(true, true)
+    }
+
+    /// Renders every type in dependency order in the registry's declaration
+    /// style. Exported output only includes named declarations.
+    fn render_with(&self, export: bool, ambient: bool) -> String {
+        let options = DeclarationOptions {
+            export,
+            ambient,
+            style: self.declaration_style,
+            format: self.format,
+        };
         let sorted = self.sorted_types();
         let mut output = String::new();
 
@@ -1098,211 +2765,285 @@ impl TypeRegistry {
         output.push_str("// Do not edit manually\n\n");
 
         for name in sorted {
-            if let Some(typedef) = self.types.get(name) {
-                match typedef {
-                    TypeDef::Named { namespace, name, def, wrapper, .. } => {
-                        let def_rendered = def.render();
-                        let wrapped = match wrapper {
-                            Some(w) => TypeDef::apply_wrapper(w, &def_rendered),
-                            None => def_rendered,
-                        };
-                        let inner = format!("export type {} = {};", name, wrapped);
-                        if namespace.is_empty() {
-                            output.push_str(&inner);
-                        } else {
-                            // For namespaced types, wrap in export namespace
-                            output.push_str(&Self::wrap_in_export_namespace(namespace, &inner));
-                        }
-                        output.push_str("\n\n");
-                    }
-                    TypeDef::GenericDef { name, type_params, def } => {
-                        let params_str: Vec<_> = type_params.iter().map(|p| p.render()).collect();
-                        output.push_str(&format!(
-                            "export type {}<{}> = {};\n\n",
-                            name,
-                            params_str.join(", "),
-                            def.render()
-                        ));
-                    }
-                    _ => {}
-                }
+            let Some(typedef) = self.types.get(name) else {
+                continue;
+            };
+            if export && !matches!(typedef, TypeDef::Named { .. } | TypeDef::GenericDef { .. }) {
+                continue;
             }
+            output.push_str(&typedef.render_declaration_with(&options));
+            output.push_str("\n\n");
         }
 
         // Remove trailing newline
         output.trim_end().to_string() + "\n"
     }
 
-    /// Wraps a declaration in export namespace blocks.
-    fn wrap_in_export_namespace(namespace: &[String], inner: &str) -> String {
-        if namespace.is_empty() {
-            return inner.to_string();
-        }
+    /// Clears all registered types.
+    pub fn clear(&mut self) {
+        self.types.clear();
+        self.registration_order.clear();
+    }
+}
 
-        let mut result = String::new();
-        let indent = "    ";
+// ============================================================================
+// TS TRAIT IMPLEMENTATIONS FOR PRIMITIVES
+// ============================================================================
 
-        // Opening export namespace declarations
-        for (i, ns) in namespace.iter().enumerate() {
-            for _ in 0..i {
-                result.push_str(indent);
-            }
-            result.push_str("export namespace ");
-            result.push_str(ns);
-            result.push_str(" {\n");
-        }
+impl TS for () {
+    fn typescript() -> TypeDef {
+        TypeDef::Primitive(Primitive::Void)
+    }
+}
 
-        // Inner content with proper indentation
-        let depth = namespace.len();
-        for _ in 0..depth {
-            result.push_str(indent);
-        }
-        result.push_str(inner);
-        result.push('\n');
+impl TS for bool {
+    fn typescript() -> TypeDef {
+        TypeDef::Primitive(Primitive::Boolean)
+    }
+}
 
-        // Closing braces
-        for i in (0..depth).rev() {
-            for _ in 0..i {
-                result.push_str(indent);
+impl TS for String {
+    fn typescript() -> TypeDef {
+        TypeDef::Primitive(Primitive::String)
+    }
+}
+
+impl TS for str {
+    fn typescript() -> TypeDef {
+        TypeDef::Primitive(Primitive::String)
+    }
+}
+
+impl TS for char {
+    fn typescript() -> TypeDef {
+        TypeDef::Primitive(Primitive::String)
+    }
+}
+
+macro_rules! impl_ts_number {
+    ($($t:ty),*) => {
+        $(
+            impl TS for $t {
+                fn typescript() -> TypeDef {
+                    TypeDef::Primitive(Primitive::Number)
+                }
             }
-            result.push('}');
-            if i > 0 {
-                result.push('\n');
+        )*
+    };
+}
+
+impl_ts_number!(i8, i16, i32, u8, u16, u32, f32, f64);
+
+impl_ts_number!(
+    std::num::NonZeroI8,
+    std::num::NonZeroI16,
+    std::num::NonZeroI32,
+    std::num::NonZeroU8,
+    std::num::NonZeroU16,
+    std::num::NonZeroU32
+);
+
+// 64-bit and 128-bit integers render according to their IntPolicy,
+// by default number and bigint respectively
+macro_rules! impl_ts_large_int {
+    ($width:ident: $($t:ty),*) => {
+        $(
+            impl TS for $t {
+                fn typescript() -> TypeDef {
+                    TypeDef::LargeInt { width: IntWidth::$width, policy: None }
+                }
             }
-        }
+        )*
+    };
+}
 
-        result
+impl_ts_large_int!(
+    Bits64: i64,
+    isize,
+    u64,
+    usize,
+    std::num::NonZeroI64,
+    std::num::NonZeroIsize,
+    std::num::NonZeroU64,
+    std::num::NonZeroUsize
+);
+
+impl_ts_large_int!(Bits128: i128, u128, std::num::NonZeroI128, std::num::NonZeroU128);
+
+// Paths and network addresses serialize as strings in human-readable formats
+macro_rules! impl_ts_string {
+    ($($t:ty),*) => {
+        $(
+            impl TS for $t {
+                fn typescript() -> TypeDef {
+                    TypeDef::Primitive(Primitive::String)
+                }
+            }
+        )*
+    };
+}
+
+impl_ts_string!(
+    std::path::Path,
+    std::path::PathBuf,
+    std::net::IpAddr,
+    std::net::Ipv4Addr,
+    std::net::Ipv6Addr,
+    std::net::SocketAddr,
+    std::net::SocketAddrV4,
+    std::net::SocketAddrV6
+);
+
+// serde serializes a `Duration` as `{ secs, nanos }`
+impl TS for std::time::Duration {
+    fn typescript() -> TypeDef {
+        TypeDef::Object(vec![
+            Field::new("secs", TypeDef::Primitive(Primitive::Number)),
+            Field::new("nanos", TypeDef::Primitive(Primitive::Number)),
+        ])
     }
+}
 
-    /// Clears all registered types.
-    pub fn clear(&mut self) {
-        self.types.clear();
-        self.registration_order.clear();
+// serde serializes a `SystemTime` as the duration since the Unix epoch
+impl TS for std::time::SystemTime {
+    fn typescript() -> TypeDef {
+        TypeDef::Object(vec![
+            Field::new("secs_since_epoch", TypeDef::Primitive(Primitive::Number)),
+            Field::new("nanos_since_epoch", TypeDef::Primitive(Primitive::Number)),
+        ])
     }
 }
 
 // ============================================================================
-// TS TRAIT IMPLEMENTATIONS FOR PRIMITIVES
+// TS TRAIT IMPLEMENTATIONS FOR GENERIC TYPES
 // ============================================================================
 
-impl TS for () {
+impl<T: TS> TS for Option<T> {
     fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::Void)
+        TypeDef::Union(vec![T::typescript(), TypeDef::Primitive(Primitive::Null)])
     }
 }
 
-impl TS for bool {
+impl<T: TS> TS for Vec<T> {
     fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::Boolean)
+        TypeDef::Array(Box::new(T::typescript()))
     }
 }
 
-impl TS for String {
-    fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::String)
-    }
-}
+/// Slices and fixed-size arrays over this many elements are `T[]` rather
+/// than a tuple
+const MAX_TUPLE_ARRAY_LEN: usize = 16;
 
-impl TS for &str {
+impl<T: TS> TS for [T] {
     fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::String)
+        TypeDef::Array(Box::new(T::typescript()))
     }
 }
 
-impl TS for char {
+// serde serializes arrays as tuples, so small ones are fixed-length tuples
+impl<T: TS, const N: usize> TS for [T; N] {
     fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::String)
+        if N <= MAX_TUPLE_ARRAY_LEN {
+            TypeDef::Tuple(vec![T::typescript(); N])
+        } else {
+            TypeDef::Array(Box::new(T::typescript()))
+        }
     }
 }
 
-macro_rules! impl_ts_number {
-    ($($t:ty),*) => {
-        $(
-            impl TS for $t {
-                fn typescript() -> TypeDef {
-                    TypeDef::Primitive(Primitive::Number)
-                }
+// Collections that serialize as sequences
+macro_rules! impl_ts_sequence {
+    ($($t:ident)::+) => {
+        impl<T: TS> TS for $($t)::+<T> {
+            fn typescript() -> TypeDef {
+                TypeDef::Array(Box::new(T::typescript()))
             }
-        )*
+        }
     };
 }
 
-impl_ts_number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+impl_ts_sequence!(std::collections::VecDeque);
+impl_ts_sequence!(std::collections::LinkedList);
+impl_ts_sequence!(std::collections::BinaryHeap);
+impl_ts_sequence!(std::collections::BTreeSet);
 
-// i128/u128 map to bigint in TypeScript
-impl TS for i128 {
+impl<T: TS, S> TS for std::collections::HashSet<T, S> {
     fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::BigInt)
+        TypeDef::Array(Box::new(T::typescript()))
     }
 }
 
-impl TS for u128 {
-    fn typescript() -> TypeDef {
-        TypeDef::Primitive(Primitive::BigInt)
-    }
+// Wrappers that serialize as the value they contain
+macro_rules! impl_ts_transparent {
+    ($($t:ident)::+) => {
+        impl<T: TS + ?Sized> TS for $($t)::+<T> {
+            fn typescript() -> TypeDef {
+                T::typescript()
+            }
+        }
+    };
 }
 
-// ============================================================================
-// TS TRAIT IMPLEMENTATIONS FOR GENERIC TYPES
-// ============================================================================
+impl_ts_transparent!(Box);
+impl_ts_transparent!(std::rc::Rc);
+impl_ts_transparent!(std::sync::Arc);
+impl_ts_transparent!(std::cell::RefCell);
+impl_ts_transparent!(std::sync::Mutex);
+impl_ts_transparent!(std::sync::RwLock);
 
-impl<T: TS> TS for Option<T> {
+impl<T: TS> TS for std::cell::Cell<T> {
     fn typescript() -> TypeDef {
-        TypeDef::Union(vec![T::typescript(), TypeDef::Primitive(Primitive::Null)])
+        T::typescript()
     }
 }
 
-impl<T: TS> TS for Vec<T> {
+impl<T: TS> TS for std::num::Wrapping<T> {
     fn typescript() -> TypeDef {
-        TypeDef::Array(Box::new(T::typescript()))
+        T::typescript()
     }
 }
 
-impl<T: TS> TS for Box<T> {
+impl<T: TS> TS for std::cmp::Reverse<T> {
     fn typescript() -> TypeDef {
         T::typescript()
     }
 }
 
-impl<T: TS> TS for std::rc::Rc<T> {
+impl<T: TS + ?Sized> TS for &T {
     fn typescript() -> TypeDef {
         T::typescript()
     }
 }
 
-impl<T: TS> TS for std::sync::Arc<T> {
+impl<T: TS + ?Sized> TS for &mut T {
     fn typescript() -> TypeDef {
         T::typescript()
     }
 }
 
-impl<T: TS> TS for std::cell::RefCell<T> {
+impl<T: TS + ToOwned + ?Sized> TS for std::borrow::Cow<'_, T> {
     fn typescript() -> TypeDef {
         T::typescript()
     }
 }
 
-impl<T: TS> TS for std::cell::Cell<T> {
+// serde serializes `PhantomData` as `null`; `#[derive(TS)]` omits
+// `PhantomData` fields of structs entirely
+impl<T: ?Sized> TS for std::marker::PhantomData<T> {
     fn typescript() -> TypeDef {
-        T::typescript()
+        TypeDef::Primitive(Primitive::Null)
     }
 }
 
-impl<K: TS, V: TS> TS for HashMap<K, V> {
+impl<K: TS, V: TS, S> TS for HashMap<K, V, S> {
     fn typescript() -> TypeDef {
-        TypeDef::Record {
-            key: Box::new(K::typescript()),
-            value: Box::new(V::typescript()),
-        }
+        TypeDef::map(K::typescript(), V::typescript())
     }
 }
 
 impl<K: TS, V: TS> TS for std::collections::BTreeMap<K, V> {
     fn typescript() -> TypeDef {
-        TypeDef::Record {
-            key: Box::new(K::typescript()),
-            value: Box::new(V::typescript()),
-        }
+        TypeDef::map(K::typescript(), V::typescript())
     }
 }
 
@@ -1325,63 +3066,38 @@ impl<T: TS, E: TS> TS for Result<T, E> {
 // TS TRAIT IMPLEMENTATIONS FOR TUPLES
 // ============================================================================
 
-impl<A: TS> TS for (A,) {
-    fn typescript() -> TypeDef {
-        TypeDef::Tuple(vec![A::typescript()])
-    }
-}
-
-impl<A: TS, B: TS> TS for (A, B) {
-    fn typescript() -> TypeDef {
-        TypeDef::Tuple(vec![A::typescript(), B::typescript()])
-    }
-}
-
-impl<A: TS, B: TS, C: TS> TS for (A, B, C) {
-    fn typescript() -> TypeDef {
-        TypeDef::Tuple(vec![A::typescript(), B::typescript(), C::typescript()])
-    }
+macro_rules! impl_ts_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: TS),+> TS for ($($name,)+) {
+            fn typescript() -> TypeDef {
+                TypeDef::Tuple(vec![$($name::typescript()),+])
+            }
+        }
+    };
 }
 
-impl<A: TS, B: TS, C: TS, D: TS> TS for (A, B, C, D) {
-    fn typescript() -> TypeDef {
-        TypeDef::Tuple(vec![
-            A::typescript(),
-            B::typescript(),
-            C::typescript(),
-            D::typescript(),
-        ])
-    }
-}
+impl_ts_tuple!(A);
+impl_ts_tuple!(A, B);
+impl_ts_tuple!(A, B, C);
+impl_ts_tuple!(A, B, C, D);
+impl_ts_tuple!(A, B, C, D, E);
+impl_ts_tuple!(A, B, C, D, E, F);
+impl_ts_tuple!(A, B, C, D, E, F, G);
+impl_ts_tuple!(A, B, C, D, E, F, G, H);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_ts_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
 
-impl<A: TS, B: TS, C: TS, D: TS, E: TS> TS
-    for (A, B, C, D, E)
-{
-    fn typescript() -> TypeDef {
-        TypeDef::Tuple(vec![
-            A::typescript(),
-            B::typescript(),
-            C::typescript(),
-            D::typescript(),
-            E::typescript(),
-        ])
-    }
-}
+// ============================================================================
+// TS TRAIT IMPLEMENTATIONS FOR ECOSYSTEM CRATES
+// ============================================================================
 
-impl<A: TS, B: TS, C: TS, D: TS, E: TS, F: TS>
-    TS for (A, B, C, D, E, F)
-{
-    fn typescript() -> TypeDef {
-        TypeDef::Tuple(vec![
-            A::typescript(),
-            B::typescript(),
-            C::typescript(),
-            D::typescript(),
-            E::typescript(),
-            F::typescript(),
-        ])
-    }
-}
+mod ecosystem;
 
 // ============================================================================
 // TESTS
@@ -1489,6 +3205,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
         // Named types render as just their name (for inline use)
         assert_eq!(named.render(), "UserId");
@@ -1509,6 +3227,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
         // Inline reference includes namespace path
         assert_eq!(namespaced.render(), "VM.Git.State");
@@ -1527,6 +3247,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
         assert_eq!(namespaced.render(), "API.Response");
         let decl = namespaced.render_declaration();
@@ -1535,51 +3257,463 @@ mod tests {
     }
 
     #[test]
-    fn test_typedef_wrapper_simple() {
-        // Simple wrapper like "Prettify"
-        let wrapped = TypeDef::Named {
+    fn test_typedef_wrapper_simple() {
+        // Simple wrapper like "Prettify"
+        let wrapped = TypeDef::Named {
+            namespace: vec![],
+            name: "User".into(),
+            def: Box::new(TypeDef::Object(vec![
+                Field::new("id", TypeDef::Primitive(Primitive::String)),
+                Field::new("name", TypeDef::Primitive(Primitive::String)),
+            ])),
+            module: None,
+            wrapper: Some("Prettify".to_string()),
+            docs: None,
+            declaration_style: None,
+        };
+        let decl = wrapped.render_declaration();
+        assert_eq!(decl, "type User = Prettify<{ id: string; name: string }>;");
+    }
+
+    #[test]
+    fn test_typedef_wrapper_chained() {
+        // Chained wrapper like "Prettify<Required<"
+        let wrapped = TypeDef::Named {
+            namespace: vec![],
+            name: "Config".into(),
+            def: Box::new(TypeDef::Object(vec![
+                Field::new("theme", TypeDef::Primitive(Primitive::String)),
+            ])),
+            module: None,
+            wrapper: Some("Prettify<Required<".to_string()),
+            docs: None,
+            declaration_style: None,
+        };
+        let decl = wrapped.render_declaration();
+        assert_eq!(decl, "type Config = Prettify<Required<{ theme: string }>>;");
+    }
+
+    #[test]
+    fn test_typedef_wrapper_with_namespace() {
+        // Wrapper with namespace
+        let wrapped = TypeDef::Named {
+            namespace: vec!["API".into()],
+            name: "Response".into(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: None,
+            wrapper: Some("Prettify".to_string()),
+            docs: None,
+            declaration_style: None,
+        };
+        let decl = wrapped.render_declaration();
+        assert!(decl.contains("namespace API {"));
+        assert!(decl.contains("type Response = Prettify<string>;"));
+    }
+
+    #[test]
+    fn test_docs_render() {
+        assert_eq!(Docs::new("A user.").render(), "/** A user. */");
+        assert_eq!(
+            Docs::new("A user.\n\nSee `Account`.").render(),
+            "/**\n * A user.\n *\n * See `Account`.\n */"
+        );
+        assert_eq!(
+            Docs::default().with_deprecated("").render(),
+            "/** @deprecated */"
+        );
+        assert_eq!(
+            Docs::new("Old id.").with_deprecated("use `uuid`").render(),
+            "/**\n * Old id.\n * @deprecated use `uuid`\n */"
+        );
+        assert_eq!(
+            Docs::new("Old id.\n\nSee `Account`.").with_deprecated("use `uuid`").render_inline(),
+            "/** Old id. See `Account`. @deprecated use `uuid` */"
+        );
+        assert_eq!(Docs::default().render_inline(), "");
+        // A stray `*/` must not close the comment early
+        assert_eq!(Docs::new("a */ b").render(), "/** a *\\/ b */");
+        assert!(Docs::new("  ").is_empty());
+    }
+
+    #[test]
+    fn test_typedef_docs_render() {
+        let user = TypeDef::Named {
+            namespace: vec![],
+            name: "User".into(),
+            def: Box::new(TypeDef::Object(vec![
+                Field::new("id", TypeDef::Primitive(Primitive::String))
+                    .with_docs(Docs::new("Unique id.")),
+                Field::optional("nick", TypeDef::Primitive(Primitive::String))
+                    .with_docs(Docs::default().with_deprecated("")),
+            ])),
+            module: None,
+            wrapper: None,
+            docs: Some(Docs::new("A registered user.")),
+            declaration_style: None,
+        };
+        assert_eq!(
+            user.render_declaration(),
+            "/** A registered user. */\n\
+             type User = { /** Unique id. */ id: string; /** @deprecated */ nick?: string };"
+        );
+
+        let generic = TypeDef::GenericDef {
+            name: "Page".into(),
+            type_params: vec![TypeParam::new("T")],
+            def: Box::new(TypeDef::Array(Box::new(TypeDef::TypeParamRef("T".into())))),
+            module: None,
+            docs: Some(Docs::new("One page of results.")),
+            declaration_style: None,
+        };
+        assert_eq!(
+            generic.render_declaration(),
+            "/** One page of results. */\ntype Page<T> = T[];"
+        );
+    }
+
+    #[test]
+    fn test_typedef_docs_in_namespace() {
+        let state = TypeDef::Named {
+            namespace: vec!["VM".into()],
+            name: "State".into(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: None,
+            wrapper: None,
+            docs: Some(Docs::new("VM state.\nSee the VM docs.")),
+            declaration_style: None,
+        };
+        assert_eq!(
+            state.render_declaration(),
+            "namespace VM {\n    /**\n     * VM state.\n     * See the VM docs.\n     */\n    type State = string;\n}"
+        );
+    }
+
+    #[test]
+    fn test_typedef_enum_render() {
+        let members = vec![
+            EnumMember::new("Low", Literal::Number(1.0)),
+            EnumMember::new("very-high", Literal::Number(2.0)),
+        ];
+        let named = |style| TypeDef::Named {
+            namespace: vec!["Jobs".into()],
+            name: "Priority".into(),
+            def: Box::new(TypeDef::Enum { members: members.clone(), style }),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+
+        assert_eq!(
+            TypeDef::Enum { members: members.clone(), style: EnumStyle::Enum }.render(),
+            "1 | 2"
+        );
+        assert_eq!(named(EnumStyle::Enum).render(), "Jobs.Priority");
+        assert_eq!(
+            named(EnumStyle::Enum).render_declaration(),
+            "namespace Jobs {\n    enum Priority { Low = 1, \"very-high\" = 2 }\n}"
+        );
+        assert_eq!(
+            named(EnumStyle::ConstEnum).render_exported_declaration(),
+            "export namespace Jobs {\n    export const enum Priority { Low = 1, \"very-high\" = 2 }\n}"
+        );
+        assert_eq!(
+            named(EnumStyle::AsConst).render_declaration(),
+            "namespace Jobs {\n    \
+             const Priority = { Low: 1, \"very-high\": 2 } as const;\n    \
+             type Priority = (typeof Priority)[keyof typeof Priority];\n}"
+        );
+    }
+
+    #[test]
+    fn test_typedef_ambient_declaration() {
+        let members = vec![
+            EnumMember::new("Low", Literal::Number(1.0)),
+            EnumMember::new("very-high", Literal::Number(2.0)),
+        ];
+        let named = |namespace: &[&str], style| TypeDef::Named {
+            namespace: namespace.iter().map(|ns| ns.to_string()).collect(),
+            name: "Priority".into(),
+            def: Box::new(TypeDef::Enum { members: members.clone(), style }),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+
+        assert_eq!(
+            named(&[], EnumStyle::Enum).render_ambient_declaration(),
+            "declare enum Priority { Low = 1, \"very-high\" = 2 }"
+        );
+        assert_eq!(
+            named(&[], EnumStyle::ConstEnum).render_exported_ambient_declaration(),
+            "export declare const enum Priority { Low = 1, \"very-high\" = 2 }"
+        );
+        assert_eq!(
+            named(&[], EnumStyle::AsConst).render_exported_ambient_declaration(),
+            "export declare const Priority: { readonly Low: 1; readonly \"very-high\": 2 };\n\
+             export type Priority = (typeof Priority)[keyof typeof Priority];"
+        );
+
+        // Only the outermost namespace is declared
+        assert_eq!(
+            named(&["Jobs", "Queue"], EnumStyle::AsConst).render_ambient_declaration(),
+            "declare namespace Jobs {\n    namespace Queue {\n        \
+             const Priority: { readonly Low: 1; readonly \"very-high\": 2 };\n        \
+             type Priority = (typeof Priority)[keyof typeof Priority];\n    }\n}"
+        );
+        assert_eq!(
+            named(&["Jobs"], EnumStyle::Enum).render_exported_ambient_declaration(),
+            "export declare namespace Jobs {\n    export enum Priority { Low = 1, \"very-high\" = 2 }\n}"
+        );
+
+        // Type aliases need no modifier
+        let alias = TypeDef::Named {
+            namespace: vec![],
+            name: "UserId".into(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        assert_eq!(alias.render_ambient_declaration(), "type UserId = string;");
+        assert_eq!(
+            alias.render_exported_ambient_declaration(),
+            "export type UserId = string;"
+        );
+    }
+
+    #[test]
+    fn test_render_pretty() {
+        let string = || TypeDef::Primitive(Primitive::String);
+        let user = TypeDef::Object(vec![
+            Field::new("id", string()),
+            Field::optional("name", string()).with_docs(Docs::new("Display name.")),
+            Field::new("tags", TypeDef::Array(Box::new(string()))).readonly(),
+        ]);
+
+        // Fits on one line
+        assert_eq!(user.render_pretty(&Format::new()), user.render());
+        assert_eq!(
+            user.render_pretty(&Format::new().separator(Separator::Comma)),
+            "{ id: string, /** Display name. */ name?: string, readonly tags: string[] }"
+        );
+
+        // Broken over several lines, with docs above their fields
+        let narrow = Format::new().max_width(40);
+        assert_eq!(
+            user.render_pretty(&narrow),
+            "{\n    id: string;\n    /** Display name. */\n    name?: string;\n    readonly tags: string[];\n}"
+        );
+        assert_eq!(
+            user.render_pretty(&narrow.indent(Indent::Tab).separator(Separator::Comma)),
+            "{\n\tid: string,\n\t/** Display name. */\n\tname?: string,\n\treadonly tags: string[],\n}"
+        );
+
+        // Nested objects only break where needed
+        let nested = TypeDef::Object(vec![
+            Field::new("user", user.clone()),
+            Field::new("count", TypeDef::Primitive(Primitive::Number)),
+        ]);
+        assert_eq!(
+            nested.render_pretty(&Format::new().max_width(90)),
+            "{\n    user: { id: string; /** Display name. */ name?: string; readonly tags: string[] };\n    \
+             count: number;\n}"
+        );
+        assert_eq!(
+            nested.render_pretty(&narrow),
+            "{\n    user: {\n        id: string;\n        /** Display name. */\n        name?: string;\n        \
+             readonly tags: string[];\n    };\n    count: number;\n}"
+        );
+
+        // Multi-line docs always break the object
+        let documented = TypeDef::Object(vec![
+            Field::new("id", string()).with_docs(Docs::new("Unique id.\nNever reused."))
+        ]);
+        assert_eq!(
+            documented.render_pretty(&Format::new()),
+            "{\n    /**\n     * Unique id.\n     * Never reused.\n     */\n    id: string;\n}"
+        );
+        assert_eq!(TypeDef::Object(vec![]).render_pretty(&narrow), "{}");
+    }
+
+    #[test]
+    fn test_pretty_declaration() {
+        let object = |kind: &str, field: &str| {
+            TypeDef::Object(vec![
+                Field::new("kind", TypeDef::Literal(Literal::String(kind.into()))),
+                Field::new(field, TypeDef::Primitive(Primitive::Number)),
+            ])
+        };
+        let named = |def: TypeDef| TypeDef::Named {
+            namespace: vec![],
+            name: "Shape".into(),
+            def: Box::new(def),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        let options = |format: Format| DeclarationOptions {
+            export: true,
+            format: Some(format),
+            ..DeclarationOptions::default()
+        };
+        let shape = named(TypeDef::Union(vec![object("circle", "radius"), object("square", "side")]));
+
+        // Discriminated unions get one member per line
+        assert_eq!(
+            shape.render_declaration_with(&options(Format::new())),
+            "export type Shape =\n    | { kind: \"circle\"; radius: number }\n    | { kind: \"square\"; side: number };"
+        );
+        assert_eq!(
+            shape.render_declaration_with(&options(Format::new().split_discriminated_unions(false))),
+            "export type Shape = { kind: \"circle\"; radius: number } | { kind: \"square\"; side: number };"
+        );
+        // Broken members are aligned after `| `
+        assert_eq!(
+            shape.render_declaration_with(&options(Format::new().max_width(36))),
+            "export type Shape =\n    | {\n          kind: \"circle\";\n          radius: number;\n      }\n    | {\n          \
+             kind: \"square\";\n          side: number;\n      };"
+        );
+
+        // Other unions are only split when they don't fit
+        let literals = named(TypeDef::Union(
+            ["small", "medium", "large"].iter().map(|s| TypeDef::Literal(Literal::String(s.to_string()))).collect(),
+        ));
+        assert_eq!(
+            literals.render_declaration_with(&options(Format::new())),
+            "export type Shape = \"small\" | \"medium\" | \"large\";"
+        );
+        assert_eq!(
+            literals.render_declaration_with(&options(Format::new().max_width(30))),
+            "export type Shape =\n    | \"small\"\n    | \"medium\"\n    | \"large\";"
+        );
+
+        // Interfaces and namespaces
+        let mut interface = named(object("circle", "radius"));
+        if let TypeDef::Named { namespace, declaration_style, .. } = &mut interface {
+            *namespace = vec!["Geometry".into()];
+            *declaration_style = Some(DeclarationStyle::Interface);
+        }
+        assert_eq!(
+            interface.render_declaration_with(&options(Format::new().max_width(40).indent(Indent::Spaces(2)))),
+            "export namespace Geometry {\n  export interface Shape {\n    kind: \"circle\";\n    radius: number;\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn test_pretty_enum_declaration() {
+        let named = |style| TypeDef::Named {
             namespace: vec![],
-            name: "User".into(),
-            def: Box::new(TypeDef::Object(vec![
-                Field::new("id", TypeDef::Primitive(Primitive::String)),
-                Field::new("name", TypeDef::Primitive(Primitive::String)),
-            ])),
+            name: "Priority".into(),
+            def: Box::new(TypeDef::Enum {
+                members: vec![
+                    EnumMember::new("Low", Literal::Number(1.0)).with_docs(Docs::new("Whenever.")),
+                    EnumMember::new("High", Literal::Number(2.0)),
+                ],
+                style,
+            }),
             module: None,
-            wrapper: Some("Prettify".to_string()),
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
-        let decl = wrapped.render_declaration();
-        assert_eq!(decl, "type User = Prettify<{ id: string; name: string }>;");
+        let options = |ambient, max_width| DeclarationOptions {
+            ambient,
+            format: Some(Format::new().max_width(max_width)),
+            ..DeclarationOptions::default()
+        };
+
+        assert_eq!(
+            named(EnumStyle::Enum).render_declaration_with(&options(false, 100)),
+            "enum Priority { /** Whenever. */ Low = 1, High = 2 }"
+        );
+        assert_eq!(
+            named(EnumStyle::Enum).render_declaration_with(&options(false, 40)),
+            "enum Priority {\n    /** Whenever. */\n    Low = 1,\n    High = 2,\n}"
+        );
+        assert_eq!(
+            named(EnumStyle::AsConst).render_declaration_with(&options(true, 40)),
+            "declare const Priority: {\n    /** Whenever. */\n    readonly Low: 1;\n    readonly High: 2;\n};\n\
+             type Priority = (typeof Priority)[keyof typeof Priority];"
+        );
     }
 
     #[test]
-    fn test_typedef_wrapper_chained() {
-        // Chained wrapper like "Prettify<Required<"
-        let wrapped = TypeDef::Named {
+    fn test_typedef_interface_declaration() {
+        let fields = TypeDef::Object(vec![Field::new("name", TypeDef::Primitive(Primitive::String))]);
+        let named = |def: TypeDef, wrapper: Option<&str>| TypeDef::Named {
             namespace: vec![],
-            name: "Config".into(),
-            def: Box::new(TypeDef::Object(vec![
-                Field::new("theme", TypeDef::Primitive(Primitive::String)),
-            ])),
+            name: "User".into(),
+            def: Box::new(def),
             module: None,
-            wrapper: Some("Prettify<Required<".to_string()),
+            wrapper: wrapper.map(String::from),
+            docs: None,
+            declaration_style: Some(DeclarationStyle::Interface),
         };
-        let decl = wrapped.render_declaration();
-        assert_eq!(decl, "type Config = Prettify<Required<{ theme: string }>>;");
-    }
 
-    #[test]
-    fn test_typedef_wrapper_with_namespace() {
-        // Wrapper with namespace
-        let wrapped = TypeDef::Named {
-            namespace: vec!["API".into()],
-            name: "Response".into(),
-            def: Box::new(TypeDef::Primitive(Primitive::String)),
+        assert_eq!(
+            named(fields.clone(), None).render_exported_declaration(),
+            "export interface User { name: string }"
+        );
+        assert_eq!(named(TypeDef::Object(vec![]), None).render_declaration(), "interface User {}");
+
+        // Intersections with named types become `extends` clauses
+        let extended = TypeDef::Intersection(vec![
+            TypeDef::Ref("BaseEntity".into()),
+            TypeDef::Ref("Claude.Todo".into()),
+            fields.clone(),
+        ]);
+        assert_eq!(
+            named(extended, None).render_declaration(),
+            "interface User extends BaseEntity, Claude.Todo { name: string }"
+        );
+
+        // Anything else falls back to a type alias
+        let union = TypeDef::Union(vec![fields.clone(), TypeDef::Primitive(Primitive::Null)]);
+        assert_eq!(named(union, None).render_declaration(), "type User = { name: string } | null;");
+        let raw = TypeDef::Intersection(vec![TypeDef::Ref("string | number".into()), fields.clone()]);
+        assert_eq!(
+            named(raw, None).render_declaration(),
+            "type User = string | number & { name: string };"
+        );
+        assert_eq!(
+            named(fields.clone(), Some("Prettify")).render_declaration(),
+            "type User = Prettify<{ name: string }>;"
+        );
+
+        // The type's own style takes precedence over the options
+        let options = DeclarationOptions {
+            style: DeclarationStyle::Interface,
+            ..DeclarationOptions::default()
+        };
+        let mut alias = named_with_refs("Alias", &[]);
+        if let TypeDef::Named { declaration_style, .. } = &mut alias {
+            *declaration_style = Some(DeclarationStyle::TypeAlias);
+        }
+        assert_eq!(alias.render_declaration_with(&options), "type Alias = {};");
+        assert_eq!(
+            named_with_refs("Post", &["User"]).render_declaration_with(&options),
+            "interface Post { user: User }"
+        );
+
+        let generic = TypeDef::GenericDef {
+            name: "Page".into(),
+            type_params: vec![TypeParam::new("T")],
+            def: Box::new(TypeDef::Object(vec![Field::new(
+                "items",
+                TypeDef::Array(Box::new(TypeDef::TypeParamRef("T".into()))),
+            )])),
             module: None,
-            wrapper: Some("Prettify".to_string()),
+            docs: Some(Docs::new("A page of results.")),
+            declaration_style: None,
         };
-        let decl = wrapped.render_declaration();
-        assert!(decl.contains("namespace API {"));
-        assert!(decl.contains("type Response = Prettify<string>;"));
+        assert_eq!(
+            generic.render_declaration_with(&options),
+            "/** A page of results. */\ninterface Page<T> { items: T[] }"
+        );
     }
 
     #[test]
@@ -1610,7 +3744,7 @@ mod tests {
     fn test_typedef_literal_render() {
         assert_eq!(TypeDef::Literal(Literal::String("foo".into())).render(), "\"foo\"");
         assert_eq!(TypeDef::Literal(Literal::Number(42.0)).render(), "42");
-        assert_eq!(TypeDef::Literal(Literal::Number(3.14)).render(), "3.14");
+        assert_eq!(TypeDef::Literal(Literal::Number(2.5)).render(), "2.5");
         assert_eq!(TypeDef::Literal(Literal::Boolean(true)).render(), "true");
         assert_eq!(TypeDef::Literal(Literal::Boolean(false)).render(), "false");
     }
@@ -1638,6 +3772,7 @@ mod tests {
         let generic = TypeDef::Generic {
             base: "Promise".into(),
             args: vec![TypeDef::Primitive(Primitive::String)],
+            def: None,
         };
         assert_eq!(generic.render(), "Promise<string>");
 
@@ -1647,6 +3782,7 @@ mod tests {
                 TypeDef::Primitive(Primitive::String),
                 TypeDef::Primitive(Primitive::Number),
             ],
+            def: None,
         };
         assert_eq!(multi_generic.render(), "Map<string, number>");
     }
@@ -1761,10 +3897,54 @@ mod tests {
         assert_eq!(String::typescript().render(), "string");
         assert_eq!(i32::typescript().render(), "number");
         assert_eq!(f64::typescript().render(), "number");
+        assert_eq!(u64::typescript().render(), "number");
         assert_eq!(i128::typescript().render(), "bigint");
         assert_eq!(u128::typescript().render(), "bigint");
     }
 
+    #[test]
+    fn test_typedef_large_int_render() {
+        let id = |policy| TypeDef::LargeInt { width: IntWidth::Bits64, policy };
+        assert_eq!(id(None).render(), "number");
+        assert_eq!(id(Some(IntPolicy::BigInt)).render(), "bigint");
+        assert_eq!(id(Some(IntPolicy::String)).render(), "string");
+        assert_eq!(id(Some(IntPolicy::NumberOrString)).render(), "number | string");
+        assert_eq!(
+            TypeDef::Array(Box::new(id(Some(IntPolicy::NumberOrString)))).render(),
+            "(number | string)[]"
+        );
+        assert_eq!(u64::typescript(), id(None));
+        assert_eq!(
+            <std::num::NonZeroU128>::typescript(),
+            TypeDef::LargeInt { width: IntWidth::Bits128, policy: None }
+        );
+    }
+
+    #[test]
+    fn test_typedef_with_int_policy() {
+        let user = TypeDef::Named {
+            namespace: vec![],
+            name: "User".into(),
+            def: Box::new(TypeDef::Object(vec![Field::new("id", u64::typescript())])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        let field = TypeDef::Tuple(vec![
+            <Option<u64>>::typescript(),
+            <Vec<i128>>::typescript(),
+            TypeDef::LargeInt { width: IntWidth::Bits64, policy: Some(IntPolicy::BigInt) },
+            user,
+        ])
+        .with_int_policy(IntWidth::Bits64, IntPolicy::String);
+        // Integers with a policy and those inside named types keep theirs
+        assert_eq!(field.render(), "[string | null, bigint[], bigint, User]");
+        let TypeDef::Tuple(items) = &field else { unreachable!() };
+        let TypeDef::Named { def, .. } = &items[3] else { unreachable!() };
+        assert_eq!(def.render(), "{ id: number }");
+    }
+
     #[test]
     fn test_typescript_trait_option() {
         let opt = <Option<String>>::typescript();
@@ -1783,6 +3963,56 @@ mod tests {
         assert_eq!(map.render(), "Record<string, number>");
     }
 
+    #[test]
+    fn test_typescript_trait_map_keys() {
+        // JSON object keys are strings, whatever the Rust key type
+        assert_eq!(<HashMap<u32, bool>>::typescript().render(), "Record<`${number}`, boolean>");
+        assert_eq!(
+            <std::collections::BTreeMap<u64, String>>::typescript().render(),
+            "Record<`${number}`, string>"
+        );
+        assert_eq!(<HashMap<char, u8>>::typescript().render(), "Record<string, number>");
+        assert_eq!(<HashMap<bool, u8>>::typescript().render(), "Partial<Record<`${boolean}`, number>>");
+
+        let status = || TypeDef::Named {
+            namespace: vec![],
+            name: "Status".into(),
+            def: Box::new(TypeDef::Union(vec![
+                TypeDef::Literal(Literal::String("A".into())),
+                TypeDef::Literal(Literal::String("B".into())),
+            ])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        let level = TypeDef::Named {
+            namespace: vec![],
+            name: "Level".into(),
+            def: Box::new(TypeDef::Union(vec![
+                TypeDef::Literal(Literal::Number(10.0)),
+                TypeDef::Literal(Literal::Number(20.0)),
+            ])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        let value = || TypeDef::Primitive(Primitive::Number);
+        // A map needn't have every key of an enum
+        assert_eq!(TypeDef::map(status(), value()).render(), "Partial<Record<Status, number>>");
+        assert_eq!(TypeDef::map(level, value()).render(), "Partial<Record<`${Level}`, number>>");
+        assert_eq!(
+            TypeDef::Literal(Literal::Number(10.0)).into_json_key(),
+            TypeDef::Literal(Literal::String("10".into()))
+        );
+
+        assert!(status().is_json_key());
+        assert!(TypeDef::map(u64::typescript(), value()).render().starts_with("Record<`${number}`"));
+        assert!(!<(u8, u8)>::typescript().is_json_key());
+        assert!(!<Option<String>>::typescript().is_json_key());
+    }
+
     #[test]
     fn test_typescript_trait_result() {
         let result = <Result<String, String>>::typescript();
@@ -1854,6 +4084,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(user_type);
@@ -1872,6 +4104,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(user_type.clone());
@@ -1892,6 +4126,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // User type depends on UserId via Ref
@@ -1904,6 +4140,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // Post type that references User type
@@ -1916,6 +4154,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(post_type);
@@ -1941,6 +4181,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(user_type);
@@ -1960,6 +4202,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(user_type);
@@ -1968,6 +4212,25 @@ mod tests {
         assert!(output.contains("export type User = string;"));
     }
 
+    #[test]
+    fn test_registry_render_exported_docs() {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(TypeDef::Named {
+            namespace: vec![],
+            name: "UserId".to_string(),
+            def: Box::new(TypeDef::Primitive(Primitive::String)),
+            module: None,
+            wrapper: None,
+            docs: Some(Docs::new("Opaque user id.").with_deprecated("use `AccountId`")),
+            declaration_style: None,
+        });
+
+        let output = registry.render_exported();
+        assert!(output.contains(
+            "/**\n * Opaque user id.\n * @deprecated use `AccountId`\n */\nexport type UserId = string;"
+        ));
+    }
+
     #[test]
     fn test_registry_dependency_order() {
         let mut registry = TypeRegistry::new();
@@ -1979,6 +4242,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // User type depends on UserId via Ref
@@ -1991,6 +4256,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // Add in reverse order (User before UserId)
@@ -2015,6 +4282,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(user_type);
@@ -2034,6 +4303,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
         registry.add_typedef(TypeDef::Named {
             namespace: vec![],
@@ -2041,6 +4312,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::Number)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         });
 
         let names: Vec<_> = registry.type_names().collect();
@@ -2060,6 +4333,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         let b = TypeDef::Named {
@@ -2070,6 +4345,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         let a = TypeDef::Named {
@@ -2080,6 +4357,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // Add in wrong order
@@ -2111,6 +4390,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // UserLogin type depends on Profile via IndexedAccess
@@ -2123,6 +4404,8 @@ mod tests {
             }),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // Add in wrong order
@@ -2137,12 +4420,320 @@ mod tests {
         assert!(profile_pos < user_login_pos, "Profile should come before UserLogin");
     }
 
+    #[test]
+    fn test_registry_nested_named_dependency_order() {
+        let mut registry = TypeRegistry::new();
+
+        let user = TypeDef::Named {
+            namespace: vec![],
+            name: "User".to_string(),
+            def: Box::new(TypeDef::Object(vec![
+                Field::new("name", TypeDef::Primitive(Primitive::String)),
+            ])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+
+        // Post embeds the full User definition (as derived types do)
+        let post = TypeDef::Named {
+            namespace: vec![],
+            name: "Post".to_string(),
+            def: Box::new(TypeDef::Object(vec![
+                Field::new("author", user),
+            ])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+
+        registry.add_typedef(post);
+
+        assert_eq!(registry.sorted_types(), vec!["User", "Post"]);
+    }
+
+    /// Builds a named object type with one field per referenced type.
+    fn named_with_refs(name: &str, refs: &[&str]) -> TypeDef {
+        TypeDef::Named {
+            namespace: vec![],
+            name: name.to_string(),
+            def: Box::new(TypeDef::Object(
+                refs.iter()
+                    .map(|r| Field::new(r.to_lowercase(), TypeDef::Ref(r.to_string())))
+                    .collect(),
+            )),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        }
+    }
+
+    #[test]
+    fn test_registry_self_recursive_type() {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named_with_refs("Leaf", &[]));
+        registry.add_typedef(named_with_refs("Node", &["Node", "Leaf"]));
+
+        assert_eq!(registry.sorted_types(), vec!["Leaf", "Node"]);
+        assert_eq!(registry.cycles(), vec![vec!["Node"]]);
+        assert!(registry.is_recursive("Node"));
+        assert!(!registry.is_recursive("Leaf"));
+    }
+
+    #[test]
+    fn test_registry_dependencies() {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named_with_refs("Leaf", &[]));
+        registry.add_typedef(named_with_refs("Node", &["Node", "Leaf", "External"]));
+
+        // Only registered types count, in registration order
+        assert_eq!(registry.dependencies("Node"), vec!["Leaf", "Node"]);
+        assert!(registry.dependencies("Leaf").is_empty());
+        assert!(registry.dependencies("Missing").is_empty());
+    }
+
+    #[test]
+    fn test_registry_override_type() {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named_with_refs("Before", &[]));
+        registry.override_type("Before", "string");
+        registry.override_type("After", "Date");
+        registry.add_typedef(TypeDef::Named {
+            namespace: vec![],
+            name: "After".into(),
+            def: Box::new(TypeDef::Object(vec![Field::new("hidden", named_with_refs("Hidden", &[]))])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        });
+        registry.add_typedef(named_with_refs("User", &["Before", "After"]));
+
+        assert_eq!(registry.get("Before").unwrap().render_declaration(), "type Before = string;");
+        assert_eq!(registry.get("After").unwrap().render_declaration(), "type After = Date;");
+        // Types only reachable through a replaced definition are not collected
+        assert!(registry.get("Hidden").is_none());
+        assert!(registry.dependencies("After").is_empty());
+        assert_eq!(registry.dependencies("User"), vec!["Before", "After"]);
+    }
+
+    #[test]
+    fn test_registry_declaration_style() {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named_with_refs("User", &[]));
+        assert_eq!(registry.declaration_style(), DeclarationStyle::TypeAlias);
+        assert_eq!(registry.render(), "// Generated by ferrotype\n// Do not edit manually\n\ntype User = {};\n");
+
+        registry.set_declaration_style(DeclarationStyle::Interface);
+        registry.add_typedef(named_with_refs("Post", &["User"]));
+        assert_eq!(
+            registry.render_exported(),
+            "// Generated by ferrotype\n// Do not edit manually\n\n\
+             export interface User {}\n\nexport interface Post { user: User }\n"
+        );
+    }
+
+    #[test]
+    fn test_registry_format() {
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named_with_refs("Order", &["Customer", "Product"]));
+        assert_eq!(registry.format(), None);
+
+        registry.set_format(Some(Format::new().max_width(30)));
+        assert_eq!(
+            registry.render_exported(),
+            "// Generated by ferrotype\n// Do not edit manually\n\n\
+             export type Order = {\n    customer: Customer;\n    product: Product;\n};\n"
+        );
+    }
+
+    #[test]
+    fn test_registry_int_policy() {
+        let named = |name: &str, fields: Vec<Field>| TypeDef::Named {
+            namespace: vec![],
+            name: name.into(),
+            def: Box::new(TypeDef::Object(fields)),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named("Before", vec![Field::new("id", u64::typescript())]));
+        assert_eq!(registry.int_policy(IntWidth::Bits64), None);
+
+        registry.set_int_policy(IntWidth::Bits64, IntPolicy::String);
+        registry.add_typedef(named(
+            "After",
+            vec![
+                Field::new("id", u64::typescript()),
+                Field::new("count", u64::typescript().with_int_policy(IntWidth::Bits64, IntPolicy::Number)),
+                Field::new("total", u128::typescript()),
+            ],
+        ));
+        assert_eq!(registry.int_policy(IntWidth::Bits64), Some(IntPolicy::String));
+        assert_eq!(registry.get("Before").unwrap().render_declaration(), "type Before = { id: string };");
+        assert_eq!(
+            registry.get("After").unwrap().render_declaration(),
+            "type After = { id: string; count: number; total: bigint };"
+        );
+    }
+
+    #[test]
+    fn test_flatten_object() {
+        let id = || Field::new("id", TypeDef::Primitive(Primitive::Number));
+        let name = Field::new("name", TypeDef::Primitive(Primitive::String));
+        let map = HashMap::<String, bool>::typescript();
+        assert_eq!(
+            flatten_object(vec![id()], vec![TypeDef::Object(vec![name])]).render(),
+            "{ id: number; name: string }"
+        );
+        assert_eq!(
+            flatten_object(vec![id()], vec![map.clone(), Option::<bool>::typescript()]).render(),
+            "{ id: number } & Record<string, boolean | number> & (boolean | {})"
+        );
+        // Flattening into a flattened object merges the fields again
+        let nested = flatten_object(vec![id()], vec![map.clone()]);
+        assert_eq!(
+            flatten_object(vec![Field::new("tag", TypeDef::Literal(Literal::String("a".into())))], vec![nested])
+                .render(),
+            "{ tag: \"a\"; id: number } & Record<string, boolean | number | \"a\">"
+        );
+        // Values the map's own type covers are not repeated
+        let label = Field::new("label", TypeDef::Literal(Literal::String("x".into())));
+        let optional = Field::new("note", Option::<String>::typescript());
+        assert_eq!(
+            flatten_object(vec![label, optional], vec![HashMap::<String, String>::typescript()]).render(),
+            "{ label: \"x\"; note: string | null } & Record<string, string | null>"
+        );
+        assert_eq!(flatten_object(vec![], vec![map]).render(), "Record<string, boolean>");
+    }
+
+    #[test]
+    fn test_typedef_with_shape() {
+        let string = || TypeDef::Primitive(Primitive::String);
+        let nullable = || TypeDef::Union(vec![string(), TypeDef::Primitive(Primitive::Null)]);
+        let post = TypeDef::Array(Box::new(TypeDef::Object(vec![
+            Field::new("title", string()),
+            Field::optional("tags", nullable()).with_shape(FieldShape::Defaulted),
+            Field::optional("draft", string()).with_shape(FieldShape::Skipped),
+            Field::optional("note", string()).with_shape(FieldShape::SkippedNone),
+        ])));
+        assert_eq!(
+            post.render(),
+            "{ title: string; tags?: string | null; draft?: string; note?: string }[]"
+        );
+        assert_eq!(
+            post.clone().with_shape(Shape::Output).render(),
+            "{ title: string; tags: string | null; draft?: string; note?: string }[]"
+        );
+        let input = post.with_shape(Shape::Input);
+        assert_eq!(
+            input.render(),
+            "{ title: string; tags?: string | null; draft: string; note?: string | null }[]"
+        );
+        // Shaped fields are exact, so shaping again changes nothing
+        assert_eq!(input.clone().with_shape(Shape::Output), input);
+    }
+
+    #[test]
+    fn test_registry_shape() {
+        let named = |name: &str| TypeDef::Named {
+            namespace: vec![],
+            name: name.into(),
+            def: Box::new(TypeDef::Object(vec![
+                Field::optional("tags", Vec::<String>::typescript()).with_shape(FieldShape::Defaulted),
+            ])),
+            module: None,
+            wrapper: None,
+            docs: None,
+            declaration_style: None,
+        };
+        let mut registry = TypeRegistry::new();
+        registry.add_typedef(named("Before"));
+        assert_eq!(registry.shape(), None);
+        assert_eq!(registry.get("Before").unwrap().render_declaration(), "type Before = { tags?: string[] };");
+
+        registry.set_shape(Shape::Output);
+        registry.add_typedef(named("After"));
+        assert_eq!(registry.shape(), Some(Shape::Output));
+        assert_eq!(registry.get("Before").unwrap().render_declaration(), "type Before = { tags: string[] };");
+        assert_eq!(registry.get("After").unwrap().render_declaration(), "type After = { tags: string[] };");
+    }
+
+    #[test]
+    fn test_registry_cycle_ordering() {
+        let mut registry = TypeRegistry::new();
+
+        // Root -> A <-> B -> Base, registered out of dependency order
+        registry.add_typedef(named_with_refs("Root", &["A"]));
+        registry.add_typedef(named_with_refs("B", &["A", "Base"]));
+        registry.add_typedef(named_with_refs("A", &["B"]));
+        registry.add_typedef(named_with_refs("Base", &[]));
+
+        // Base first, then the A/B cycle in registration order, then Root
+        assert_eq!(registry.sorted_types(), vec!["Base", "B", "A", "Root"]);
+        assert_eq!(registry.cycles(), vec![vec!["B", "A"]]);
+
+        // Repeated calls produce identical output
+        for _ in 0..10 {
+            assert_eq!(registry.sorted_types(), vec!["Base", "B", "A", "Root"]);
+        }
+    }
+
+    #[test]
+    fn test_registry_multiple_cycles() {
+        let mut registry = TypeRegistry::new();
+
+        registry.add_typedef(named_with_refs("X", &["Y"]));
+        registry.add_typedef(named_with_refs("Y", &["X", "P"]));
+        registry.add_typedef(named_with_refs("P", &["Q"]));
+        registry.add_typedef(named_with_refs("Q", &["P"]));
+
+        assert_eq!(registry.sorted_types(), vec!["P", "Q", "X", "Y"]);
+        assert_eq!(registry.cycles(), vec![vec!["X", "Y"], vec!["P", "Q"]]);
+    }
+
+    #[test]
+    fn test_recursion_guard_breaks_cycles() {
+        struct Node;
+
+        impl TS for Node {
+            fn typescript() -> TypeDef {
+                recursion_guard::<Self>(
+                    || TypeDef::Ref("Node".into()),
+                    || TypeDef::Named {
+                        namespace: vec![],
+                        name: "Node".into(),
+                        def: Box::new(TypeDef::Object(vec![
+                            Field::new("next", <Option<Box<Node>>>::typescript()),
+                        ])),
+                        module: None,
+                        wrapper: None,
+                        docs: None,
+                        declaration_style: None,
+                    },
+                )
+            }
+        }
+
+        let td = Node::typescript();
+        assert_eq!(td.render_declaration(), "type Node = { next: Node | null };");
+
+        // The guard is released afterwards, so the full definition is built again
+        assert!(matches!(Node::typescript(), TypeDef::Named { .. }));
+    }
+
     // ========================================================================
     // AUTO-REGISTRATION TESTS
     // ========================================================================
 
     // Test types for auto-registration
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct AutoRegTestUser {
         name: String,
         age: u32,
@@ -2159,6 +4750,8 @@ mod tests {
                 ])),
                 module: None,
                 wrapper: None,
+                docs: None,
+                declaration_style: None,
             }
         }
     }
@@ -2187,6 +4780,8 @@ mod tests {
             def: Box::new(TypeDef::Primitive(Primitive::String)),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
         registry.add_typedef(manual_type);
 
@@ -2259,6 +4854,9 @@ mod tests {
             name: "Identity".into(),
             type_params: vec![TypeParam::new("T")],
             def: Box::new(TypeDef::TypeParamRef("T".into())),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
         assert_eq!(generic_def.render(), "Identity");
         assert_eq!(generic_def.render_declaration(), "type Identity<T> = T;");
@@ -2275,6 +4873,9 @@ mod tests {
             def: Box::new(TypeDef::Object(vec![
                 Field::new("data", TypeDef::TypeParamRef("T".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
         assert_eq!(
             generic_def.render_declaration(),
@@ -2296,6 +4897,9 @@ mod tests {
                 Field::new("timestamp", TypeDef::Primitive(Primitive::Number)),
                 Field::new("data", TypeDef::TypeParamRef("T".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
 
         assert_eq!(
@@ -2314,6 +4918,9 @@ mod tests {
                 Field::new("key", TypeDef::TypeParamRef("K".into())),
                 Field::new("value", TypeDef::TypeParamRef("V".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
         assert_eq!(
             pair_def.render_declaration(),
@@ -2340,6 +4947,9 @@ mod tests {
                     Field::new("error", TypeDef::TypeParamRef("E".into())),
                 ]),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
         assert_eq!(
             result_def.render_declaration(),
@@ -2356,6 +4966,7 @@ mod tests {
                 Field::new("type", TypeDef::Literal(Literal::String("text".into()))),
                 Field::new("content", TypeDef::Primitive(Primitive::String)),
             ])],
+            def: None,
         };
         assert_eq!(
             application.render(),
@@ -2375,6 +4986,9 @@ mod tests {
                 Field::new("id", TypeDef::Primitive(Primitive::String)),
                 Field::new("data", TypeDef::TypeParamRef("T".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
         registry.add_typedef(core_def);
 
@@ -2397,6 +5011,9 @@ mod tests {
             def: Box::new(TypeDef::Object(vec![
                 Field::new("data", TypeDef::TypeParamRef("T".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
         registry.add_typedef(core_def);
 
@@ -2418,6 +5035,8 @@ mod tests {
             )])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // Then define a generic using that type as a constraint
@@ -2427,6 +5046,9 @@ mod tests {
             def: Box::new(TypeDef::Object(vec![
                 Field::new("data", TypeDef::TypeParamRef("T".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(core_def);
@@ -2456,6 +5078,9 @@ mod tests {
                 Field::new("timestamp", TypeDef::Primitive(Primitive::Number)),
                 Field::new("data", TypeDef::TypeParamRef("T".into())),
             ])),
+            module: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // 2. Define variant types
@@ -2468,6 +5093,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         let image_data = TypeDef::Named {
@@ -2479,6 +5106,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // 3. Define wrapped message types
@@ -2488,9 +5117,12 @@ mod tests {
             def: Box::new(TypeDef::Generic {
                 base: "Core".into(),
                 args: vec![TypeDef::Ref("TextData".into())],
+                def: None,
             }),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         let image_message = TypeDef::Named {
@@ -2499,9 +5131,12 @@ mod tests {
             def: Box::new(TypeDef::Generic {
                 base: "Core".into(),
                 args: vec![TypeDef::Ref("ImageData".into())],
+                def: None,
             }),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         // 4. Define the union type
@@ -2514,6 +5149,8 @@ mod tests {
             ])),
             module: None,
             wrapper: None,
+            docs: None,
+            declaration_style: None,
         };
 
         registry.add_typedef(core_def);
@@ -0,0 +1,11 @@
+//! Test that TS derive rejects namespaces on generic types
+
+use ferro_type::TS;
+
+#[derive(TS)]
+#[ts(namespace = "Api")]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ferro_type::TS;
+
+#[derive(TS)]
+#[ts(tag = "kind")]
+enum Event {
+    Message(String),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test that TS derive rejects tuple variants in internally tagged enums,
+//! which serde cannot serialize either
+
+use ferro_type::TS;
+
+#[derive(TS)]
+#[ts(tag = "type")]
+enum Shape {
+    Point(f64, f64),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+//! Test that TS derive rejects `repr = "number"` discriminants it cannot
+//! evaluate
+
+use ferro_type::TS;
+
+const BASE: isize = 100;
+
+#[derive(TS)]
+#[ts(repr = "number")]
+enum Code {
+    Ok = BASE,
+    Failed,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+//! Test that TS derive rejects `repr = "number"` on enums with data,
+//! since only unit variants have a discriminant to emit
+
+use ferro_type::TS;
+
+#[derive(TS)]
+#[ts(repr = "number")]
+enum Code {
+    Ok,
+    Failed(String),
+}
+
+fn main() {}
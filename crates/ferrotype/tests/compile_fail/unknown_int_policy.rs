@@ -0,0 +1,9 @@
+use ferro_type::TS;
+
+#[derive(TS)]
+struct Message {
+    #[ts(int64 = "text")]
+    id: u64,
+}
+
+fn main() {}
@@ -0,0 +1,187 @@
+//! Tests for doc comments and #[deprecated] emitted as JSDoc by #[derive(TS)]
+
+#![allow(dead_code, deprecated)]
+
+use ferro_type::{Docs, TypeDef, TypeRegistry, TS};
+
+/// Helper to get the docs attached to a Named TypeDef
+fn type_docs(td: &TypeDef) -> Option<Docs> {
+    match td {
+        TypeDef::Named { docs, .. } => docs.clone(),
+        other => panic!("expected a named type, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// STRUCT DOCS
+// ============================================================================
+
+/// A registered user.
+///
+/// Users own projects.
+#[derive(TS)]
+struct DocUser {
+    /// Unique identifier.
+    id: String,
+    /// Display name,
+    /// shown in the sidebar.
+    name: String,
+    #[deprecated(since = "0.3.0", note = "use `name`")]
+    nick: Option<String>,
+    age: u32,
+}
+
+#[test]
+fn test_struct_docs() {
+    let td = DocUser::typescript();
+    assert_eq!(
+        type_docs(&td),
+        Some(Docs::new("A registered user.\n\nUsers own projects."))
+    );
+    assert_eq!(
+        td.render_declaration(),
+        "/**\n * A registered user.\n *\n * Users own projects.\n */\n\
+         type DocUser = { \
+         /** Unique identifier. */ id: string; \
+         /** Display name, shown in the sidebar. */ name: string; \
+         /** @deprecated use `name` */ nick: string | null; \
+         age: number };"
+    );
+}
+
+#[derive(TS)]
+#[ts(interface)]
+struct DocAccount {
+    /// Account owner.
+    ///
+    /// Set on creation.
+    owner: String,
+    /// Legacy plan name.
+    #[deprecated(note = "use `tier`")]
+    plan: String,
+}
+
+#[test]
+fn test_multiline_field_docs() {
+    // Compact output keeps each field on one line
+    assert_eq!(
+        DocAccount::typescript().render_declaration(),
+        "interface DocAccount { \
+         /** Account owner. Set on creation. */ owner: string; \
+         /** Legacy plan name. @deprecated use `tier` */ plan: string }"
+    );
+
+    // Pretty output keeps the lines of the docs
+    let mut registry = TypeRegistry::new();
+    registry.register::<DocAccount>();
+    registry.set_format(Some(ferro_type::Format::new()));
+    assert!(registry.render().contains(
+        "interface DocAccount {\n    \
+         /**\n     * Account owner.\n     *\n     * Set on creation.\n     */\n    owner: string;\n    \
+         /**\n     * Legacy plan name.\n     * @deprecated use `tier`\n     */\n    plan: string;\n}"
+    ));
+}
+
+#[deprecated]
+#[derive(TS)]
+struct OldConfig {
+    verbose: bool,
+}
+
+#[deprecated = "use `DocUser`"]
+#[derive(TS)]
+struct LegacyUser {
+    id: String,
+}
+
+#[test]
+fn test_deprecated_types() {
+    assert_eq!(
+        OldConfig::typescript().render_declaration(),
+        "/** @deprecated */\ntype OldConfig = { verbose: boolean };"
+    );
+    assert_eq!(
+        LegacyUser::typescript().render_declaration(),
+        "/** @deprecated use `DocUser` */\ntype LegacyUser = { id: string };"
+    );
+}
+
+/// One page of results.
+#[derive(TS)]
+struct DocPage<T> {
+    /// Items on this page.
+    items: Vec<T>,
+}
+
+#[test]
+fn test_generic_docs() {
+    let mut registry = TypeRegistry::new();
+    registry.register::<DocPage<String>>();
+    assert!(registry.render().contains(
+        "/** One page of results. */\ntype DocPage<T> = { /** Items on this page. */ items: T[] };"
+    ));
+}
+
+// ============================================================================
+// ENUM DOCS
+// ============================================================================
+
+/// Something that happened.
+#[derive(TS)]
+enum DocEvent {
+    /// The user logged in.
+    Login { user: String },
+    /// A plain ping.
+    Ping,
+    #[deprecated(note = "use `Login`")]
+    SignIn(String),
+}
+
+#[test]
+fn test_external_variant_docs() {
+    let td = DocEvent::typescript();
+    assert_eq!(type_docs(&td), Some(Docs::new("Something that happened.")));
+    // Unit variants are bare string literals, so their docs have nowhere to go
+    assert_eq!(
+        td.render_declaration(),
+        "/** Something that happened. */\n\
+         type DocEvent = { /** The user logged in. */ Login: { user: string } } | \"Ping\" | \
+         { /** @deprecated use `Login` */ SignIn: string };"
+    );
+}
+
+#[derive(TS)]
+#[ts(tag = "type")]
+enum DocShape {
+    /// A circle around the origin.
+    Circle {
+        /// Radius in pixels.
+        radius: f64,
+    },
+    /// Nothing to draw.
+    Empty,
+}
+
+#[test]
+fn test_tagged_variant_docs() {
+    assert_eq!(
+        DocShape::typescript().render_declaration(),
+        "type DocShape = \
+         { /** A circle around the origin. */ type: \"Circle\"; /** Radius in pixels. */ radius: number } | \
+         { /** Nothing to draw. */ type: \"Empty\" };"
+    );
+}
+
+#[test]
+fn test_undocumented_type_has_no_docs() {
+    #[derive(TS)]
+    struct Plain {
+        id: String,
+    }
+
+    assert_eq!(type_docs(&Plain::typescript()), None);
+    assert_eq!(
+        Plain::typescript().render_declaration(),
+        "type Plain = { id: string };"
+    );
+}
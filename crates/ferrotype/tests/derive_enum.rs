@@ -3,7 +3,9 @@
 //! These tests verify that the derive macro generates correct TypeScript
 //! discriminated union types for various enum patterns.
 
-use ferro_type::{TS, TypeDef, Primitive};
+#![allow(dead_code, clippy::enum_variant_names)]
+
+use ferro_type::{TS, TypeDef};
 
 /// Helper to get the inner definition from a Named TypeDef
 fn inner_def(td: TypeDef) -> TypeDef {
@@ -13,6 +15,14 @@ fn inner_def(td: TypeDef) -> TypeDef {
     }
 }
 
+/// Helper to get the GenericDef declaration carried by a generic application
+fn generic_def(td: TypeDef) -> TypeDef {
+    match td {
+        TypeDef::Generic { def: Some(def), .. } => *def,
+        other => panic!("expected a generic application, got {:?}", other),
+    }
+}
+
 // ============================================================================
 // UNIT VARIANT TESTS
 // ============================================================================
@@ -60,7 +70,7 @@ fn test_tuple_variant_enum() {
     let rendered = inner_def(td.clone()).render();
     assert_eq!(
         rendered,
-        r#"{ type: "D2"; value: [number, number] } | { type: "D3"; value: [number, number, number] }"#
+        r#"{ D2: [number, number] } | { D3: [number, number, number] }"#
     );
     assert_eq!(td.render(), "Coordinate");
 }
@@ -76,7 +86,7 @@ fn test_newtype_variant_enum() {
     let td = NewtypeWrapper::typescript();
     assert_eq!(
         inner_def(td).render(),
-        r#"{ type: "Text"; value: string } | { type: "Number"; value: number }"#
+        r#"{ Text: string } | { Number: number }"#
     );
 }
 
@@ -107,7 +117,7 @@ fn test_struct_variant_enum() {
     let rendered = inner_def(td.clone()).render();
     assert_eq!(
         rendered,
-        r#"{ type: "Circle"; center: Point; radius: number } | { type: "Rectangle"; width: number; height: number }"#
+        r#"{ Circle: { center: Point; radius: number } } | { Rectangle: { width: number; height: number } }"#
     );
     assert_eq!(td.render(), "Shape");
 }
@@ -130,7 +140,7 @@ fn test_mixed_variant_enum() {
     let rendered = inner_def(td.clone()).render();
     assert_eq!(
         rendered,
-        r#"{ type: "Ping" } | { type: "Text"; value: string } | { type: "Binary"; value: number[] } | { type: "Error"; code: number; message: string }"#
+        r#""Ping" | { Text: string } | { Binary: number[] } | { Error: { code: number; message: string } }"#
     );
     assert_eq!(td.render(), "Message");
 }
@@ -148,17 +158,16 @@ enum OptionalValue<T> {
 #[test]
 fn test_generic_enum() {
     let td_string = <OptionalValue<String>>::typescript();
+    assert_eq!(td_string.render(), "OptionalValue<string>");
     assert_eq!(
-        inner_def(td_string.clone()).render(),
-        r#"{ type: "None" } | { type: "Some"; value: string }"#
+        generic_def(td_string.clone()).render_declaration(),
+        r#"type OptionalValue<T> = "None" | { Some: T };"#
     );
-    assert_eq!(td_string.render(), "OptionalValue");
 
+    // Every instantiation carries the same declaration
     let td_i32 = <OptionalValue<i32>>::typescript();
-    assert_eq!(
-        inner_def(td_i32).render(),
-        r#"{ type: "None" } | { type: "Some"; value: number }"#
-    );
+    assert_eq!(td_i32.render(), "OptionalValue<number>");
+    assert_eq!(generic_def(td_i32), generic_def(td_string));
 }
 
 #[derive(TS)]
@@ -170,9 +179,10 @@ enum ResultLike<T, E> {
 #[test]
 fn test_multi_generic_enum() {
     let td = <ResultLike<String, i32>>::typescript();
+    assert_eq!(td.render(), "ResultLike<string, number>");
     assert_eq!(
-        inner_def(td).render(),
-        r#"{ type: "Ok"; value: string } | { type: "Err"; value: number }"#
+        generic_def(td).render_declaration(),
+        r#"type ResultLike<T, E> = { Ok: T } | { Err: E };"#
     );
 }
 
@@ -192,11 +202,11 @@ enum ComplexVariants {
 fn test_complex_nested_types() {
     let td = ComplexVariants::typescript();
     let rendered = inner_def(td).render();
-    assert!(rendered.contains(r#"{ type: "Empty" }"#));
-    assert!(rendered.contains(r#"{ type: "Simple"; value: string }"#));
+    assert!(rendered.contains(r#""Empty""#));
+    assert!(rendered.contains(r#"{ Simple: string }"#));
     // Vec<Option<String>> becomes (string | null)[] with parens for union in array
-    assert!(rendered.contains(r#"{ type: "Nested"; value: (string | null)[] }"#));
-    assert!(rendered.contains(r#"{ type: "Struct"; items: number[]; count: number }"#));
+    assert!(rendered.contains(r#"{ Nested: (string | null)[] }"#));
+    assert!(rendered.contains(r#"{ Struct: { items: number[]; count: number } }"#));
 }
 
 // ============================================================================
@@ -209,8 +219,9 @@ fn test_derive_enum_snapshots() {
     insta::assert_snapshot!("derive_tuple_enum", inner_def(Coordinate::typescript()).render());
     insta::assert_snapshot!("derive_struct_enum", inner_def(Shape::typescript()).render());
     insta::assert_snapshot!("derive_mixed_enum", inner_def(Message::typescript()).render());
-    insta::assert_snapshot!("derive_generic_enum_string", inner_def(<OptionalValue<String>>::typescript()).render());
-    insta::assert_snapshot!("derive_generic_enum_i32", inner_def(<OptionalValue<i32>>::typescript()).render());
+    insta::assert_snapshot!("derive_generic_enum_string", <OptionalValue<String>>::typescript().render());
+    insta::assert_snapshot!("derive_generic_enum_i32", <OptionalValue<i32>>::typescript().render());
+    insta::assert_snapshot!("derive_generic_enum_declaration", generic_def(<OptionalValue<i32>>::typescript()).render_declaration());
 }
 
 // ============================================================================
@@ -321,8 +332,8 @@ enum DataVariantRenameEnum {
 fn test_data_variant_rename() {
     let td = DataVariantRenameEnum::typescript();
     let rendered = inner_def(td).render();
-    assert!(rendered.contains(r#"type: "textMessage""#));
-    assert!(rendered.contains(r#"type: "errorInfo""#));
+    assert!(rendered.contains(r#"{ textMessage: string }"#));
+    assert!(rendered.contains(r#"{ errorInfo: { code: number; message: string } }"#));
 }
 
 // ============================================================================
@@ -354,11 +365,16 @@ fn test_enum_variant_field_skip() {
 // TAG ATTRIBUTE TESTS - #[ts(tag = "...")]
 // ============================================================================
 
+#[derive(TS)]
+struct TextBody {
+    body: String,
+}
+
 #[derive(TS)]
 #[ts(tag = "kind")]
 enum CustomTagEnum {
     Ping,
-    Text(String),
+    Text(TextBody),
     Error { code: i32, message: String },
 }
 
@@ -378,7 +394,7 @@ fn test_custom_tag_field_name() {
 #[ts(tag = "t")]
 enum ShortTagEnum {
     A,
-    B(i32),
+    B { value: i32 },
 }
 
 #[test]
@@ -483,8 +499,9 @@ enum UntaggedMixed {
 fn test_untagged_mixed_variants() {
     let td = UntaggedMixed::typescript();
     let rendered = inner_def(td).render();
-    // Unit variant becomes string literal
-    assert!(rendered.contains(r#""Null""#));
+    // Unit variant becomes null, as serde writes it
+    assert!(rendered.contains("null"));
+    assert!(!rendered.contains(r#""Null""#));
     // Newtype unwraps to inner type
     assert!(rendered.contains("string"));
     // Tuple becomes tuple type
@@ -505,8 +522,8 @@ enum UntaggedUnitEnum {
 fn test_untagged_unit_variants() {
     let td = UntaggedUnitEnum::typescript();
     let rendered = inner_def(td).render();
-    // Unit variants become string literals
-    assert_eq!(rendered, r#""A" | "B" | "C""#);
+    // serde writes untagged unit variants as null
+    assert_eq!(rendered, "null");
 }
 
 #[derive(TS)]
@@ -520,12 +537,218 @@ enum UntaggedWithRename {
 fn test_untagged_with_rename_all() {
     let td = UntaggedWithRename::typescript();
     let rendered = inner_def(td).render();
-    // rename_all applies to unit variant name
-    assert!(rendered.contains(r#""firstItem""#));
+    // Unit variant carries no name on the wire
+    assert_eq!(rendered, "null | string");
     // Newtype unwraps, rename doesn't affect type
     assert!(rendered.contains("string"));
 }
 
+// ============================================================================
+// VARIANT FIELD ATTRIBUTE TESTS
+// ============================================================================
+
+#[derive(TS)]
+struct Audit {
+    created_by: String,
+    revision: u32,
+}
+
+#[derive(TS)]
+struct Account {
+    login: String,
+}
+
+#[derive(TS)]
+enum VariantFieldAttrs {
+    Created {
+        #[ts(type = "Date")]
+        at: String,
+        #[ts(optional)]
+        note: Option<String>,
+        #[ts(default)]
+        retries: u32,
+        #[ts(pattern = "evt-${string}")]
+        id: String,
+        #[ts(index = Account, key = login)]
+        owner: String,
+        #[ts(flatten)]
+        audit: Audit,
+    },
+}
+
+#[test]
+fn test_struct_variant_field_attributes() {
+    let rendered = inner_def(VariantFieldAttrs::typescript()).render();
+    assert_eq!(
+        rendered,
+        r#"{ Created: { at: Date; note?: string; retries?: number; id: `evt-${string}`; owner: Account["login"]; created_by: string; revision: number } }"#
+    );
+}
+
+#[derive(TS)]
+#[ts(tag = "type")]
+enum TaggedVariantFlatten {
+    Edited {
+        #[ts(flatten)]
+        audit: Audit,
+        #[ts(rename = "diff")]
+        changes: Vec<String>,
+    },
+}
+
+#[test]
+fn test_internally_tagged_variant_flatten() {
+    let rendered = inner_def(TaggedVariantFlatten::typescript()).render();
+    assert_eq!(
+        rendered,
+        r#"{ type: "Edited"; diff: string[]; created_by: string; revision: number }"#
+    );
+}
+
+#[derive(TS)]
+#[ts(untagged)]
+enum UntaggedVariantFields {
+    Partial {
+        #[ts(optional)]
+        name: Option<String>,
+    },
+}
+
+#[test]
+fn test_untagged_variant_field_attributes() {
+    let rendered = inner_def(UntaggedVariantFields::typescript()).render();
+    assert_eq!(rendered, "{ name?: string }");
+}
+
+#[derive(TS)]
+#[ts(rename_all = "snake_case", rename_all_fields = "camelCase")]
+enum RenameAllFieldsEnum {
+    UserCreated { user_id: u32, display_name: String },
+    #[ts(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserDeleted { user_id: u32 },
+}
+
+#[test]
+fn test_rename_all_fields() {
+    let rendered = inner_def(RenameAllFieldsEnum::typescript()).render();
+    // rename_all applies to variant names, rename_all_fields to their fields,
+    // and a variant-level rename_all wins over rename_all_fields
+    assert_eq!(
+        rendered,
+        r#"{ user_created: { userId: number; displayName: string } } | { user_deleted: { USER_ID: number } }"#
+    );
+}
+
+// ============================================================================
+// NUMERIC AND DECLARED ENUM TESTS
+// ============================================================================
+
+#[derive(TS)]
+#[ts(repr = "number")]
+#[repr(u8)]
+enum StatusCode {
+    Ok = 1,
+    Moved = 3,
+    NotFound,
+    #[ts(skip)]
+    Internal,
+    Teapot = 18,
+}
+
+#[test]
+fn test_numeric_enum() {
+    // Implicit discriminants count on from the previous variant, skipped ones included
+    assert_eq!(inner_def(StatusCode::typescript()).render(), "1 | 3 | 4 | 18");
+}
+
+#[derive(TS)]
+#[ts(repr = "number")]
+enum Offset {
+    Back = -1,
+    Stay,
+    Forward,
+}
+
+#[test]
+fn test_numeric_enum_negative_and_implicit() {
+    assert_eq!(inner_def(Offset::typescript()).render(), "-1 | 0 | 1");
+}
+
+#[derive(TS)]
+#[ts(enum_style = "enum", rename_all = "lowercase")]
+enum Direction {
+    /// Towards the top.
+    Up,
+    Down,
+}
+
+#[derive(TS)]
+#[ts(enum_style = "const_enum", repr = "number")]
+enum Priority {
+    Low = 10,
+    High = 20,
+}
+
+#[derive(TS)]
+#[ts(enum_style = "as_const")]
+enum Theme {
+    Light,
+    Dark,
+}
+
+#[test]
+fn test_enum_declarations() {
+    assert_eq!(
+        Direction::typescript().render_declaration(),
+        r#"enum Direction { /** Towards the top. */ Up = "up", Down = "down" }"#
+    );
+    assert_eq!(
+        Priority::typescript().render_exported_declaration(),
+        "export const enum Priority { Low = 10, High = 20 }"
+    );
+    assert_eq!(
+        Theme::typescript().render_declaration(),
+        "const Theme = { Light: \"Light\", Dark: \"Dark\" } as const;\n\
+         type Theme = (typeof Theme)[keyof typeof Theme];"
+    );
+    // Inline, the enum is the union of its values
+    assert_eq!(inner_def(Priority::typescript()).render(), "10 | 20");
+}
+
+#[derive(TS)]
+struct Palette {
+    theme: Theme,
+    direction: Option<Direction>,
+}
+
+#[test]
+fn test_enum_declarations_are_referenced_by_name() {
+    let mut registry = ferro_type::TypeRegistry::new();
+    registry.register::<Palette>();
+    assert_eq!(
+        registry.render_exported(),
+        "// Generated by ferrotype\n// Do not edit manually\n\n\
+         export const Theme = { Light: \"Light\", Dark: \"Dark\" } as const;\n\
+         export type Theme = (typeof Theme)[keyof typeof Theme];\n\n\
+         export enum Direction { /** Towards the top. */ Up = \"up\", Down = \"down\" }\n\n\
+         export type Palette = { theme: Theme; direction: Direction | null };\n"
+    );
+}
+
+#[derive(TS)]
+#[ts(interface, tag = "kind")]
+enum InterfaceShape {
+    Circle { radius: f64 },
+}
+
+#[test]
+fn test_interface_falls_back_to_type_alias() {
+    assert_eq!(
+        InterfaceShape::typescript().render_declaration(),
+        "type InterfaceShape = { kind: \"Circle\"; radius: number };"
+    );
+}
+
 // ============================================================================
 // SNAPSHOT TESTS FOR NEW ATTRIBUTES
 // ============================================================================
@@ -0,0 +1,539 @@
+//! Tests for #[derive(TS)] reading #[serde(...)] attributes
+//!
+//! These tests verify that types which already derive serde's traits get a
+//! matching TypeScript shape without duplicating attributes in #[ts(...)].
+
+#![allow(dead_code)]
+
+use ferro_type::{Shape, TypeDef, TS};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Helper to get the inner definition from a Named TypeDef
+fn inner_def(td: TypeDef) -> TypeDef {
+    match td {
+        TypeDef::Named { def, .. } => *def,
+        other => other,
+    }
+}
+
+// ============================================================================
+// CONTAINER ATTRIBUTE TESTS
+// ============================================================================
+
+#[derive(Serialize, TS)]
+#[serde(rename = "Account", rename_all = "camelCase")]
+struct SerdeAccount {
+    user_id: u32,
+    display_name: String,
+}
+
+#[test]
+fn test_serde_rename_and_rename_all() {
+    let td = SerdeAccount::typescript();
+    assert_eq!(td.render(), "Account");
+    assert_eq!(inner_def(td).render(), "{ userId: number; displayName: string }");
+
+    let json = serde_json::to_string(&SerdeAccount { user_id: 1, display_name: "a".into() }).unwrap();
+    assert_eq!(json, r#"{"userId":1,"displayName":"a"}"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename(serialize = "Outgoing", deserialize = "Incoming"))]
+#[serde(rename_all(serialize = "SCREAMING_SNAKE_CASE"))]
+struct DirectionalRename {
+    field_one: bool,
+}
+
+#[test]
+fn test_serde_directional_rename_uses_serialize_side() {
+    let td = DirectionalRename::typescript();
+    assert_eq!(td.render(), "Outgoing");
+    assert_eq!(inner_def(td).render(), "{ FIELD_ONE: boolean }");
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+enum LowercaseEnum {
+    FirstVariant,
+    SecondVariant,
+}
+
+#[test]
+fn test_serde_rename_all_lowercase() {
+    let td = LowercaseEnum::typescript();
+    assert_eq!(inner_def(td).render(), r#""firstvariant" | "secondvariant""#);
+    assert_eq!(serde_json::to_string(&LowercaseEnum::FirstVariant).unwrap(), r#""firstvariant""#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "kind", content = "data")]
+enum SerdeAdjacent {
+    Text(String),
+    Count(i32),
+}
+
+#[test]
+fn test_serde_tag_and_content() {
+    let td = SerdeAdjacent::typescript();
+    assert_eq!(
+        inner_def(td).render(),
+        r#"{ kind: "Text"; data: string } | { kind: "Count"; data: number }"#
+    );
+    let json = serde_json::to_string(&SerdeAdjacent::Count(3)).unwrap();
+    assert_eq!(json, r#"{"kind":"Count","data":3}"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(untagged)]
+enum SerdeUntagged {
+    Num(i32),
+    Str(String),
+}
+
+#[test]
+fn test_serde_untagged() {
+    let td = SerdeUntagged::typescript();
+    assert_eq!(inner_def(td).render(), "number | string");
+}
+
+#[derive(Serialize, TS)]
+#[serde(transparent)]
+struct SerdeTransparent(String);
+
+#[derive(Serialize, TS)]
+#[serde(transparent)]
+struct SerdeTransparentNamed {
+    inner: Vec<u8>,
+    #[serde(skip)]
+    cache: Option<String>,
+}
+
+#[test]
+fn test_serde_transparent() {
+    assert_eq!(SerdeTransparent::typescript().render(), "string");
+    assert_eq!(SerdeTransparentNamed::typescript().render(), "number[]");
+
+    let value = SerdeTransparentNamed { inner: vec![1, 2], cache: None };
+    assert_eq!(serde_json::to_string(&value).unwrap(), "[1,2]");
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "type")]
+struct TaggedStruct {
+    id: u32,
+}
+
+#[test]
+fn test_serde_tagged_struct() {
+    let td = TaggedStruct::typescript();
+    assert_eq!(inner_def(td).render(), r#"{ type: "TaggedStruct"; id: number }"#);
+    assert_eq!(serde_json::to_string(&TaggedStruct { id: 1 }).unwrap(), r#"{"type":"TaggedStruct","id":1}"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(deny_unknown_fields, bound = "", crate = "serde")]
+struct IgnoredContainerOptions {
+    value: String,
+}
+
+#[test]
+fn test_serde_unrelated_options_are_ignored() {
+    let td = IgnoredContainerOptions::typescript();
+    assert_eq!(inner_def(td).render(), "{ value: string }");
+}
+
+// ============================================================================
+// FIELD ATTRIBUTE TESTS
+// ============================================================================
+
+fn default_port() -> u16 {
+    8080
+}
+
+#[derive(Serialize, TS)]
+struct Inner {
+    inner_field: String,
+}
+
+#[derive(Serialize, TS)]
+struct SerdeFields {
+    #[serde(rename = "ID")]
+    id: u32,
+    #[serde(rename(serialize = "outName"))]
+    name: String,
+    #[serde(alias = "mail", alias = "e_mail")]
+    email: String,
+    #[serde(skip)]
+    secret: String,
+    #[serde(skip_serializing)]
+    write_only: String,
+    #[serde(skip_deserializing)]
+    read_only: String,
+    #[serde(default)]
+    retries: u8,
+    #[serde(default = "default_port")]
+    port: u16,
+    #[serde(flatten)]
+    inner: Inner,
+    #[serde(with = "serde_bytes_like", skip_serializing_if = "Vec::is_empty")]
+    payload: Vec<u8>,
+}
+
+mod serde_bytes_like {
+    pub fn serialize<S: serde::Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(bytes)
+    }
+}
+
+#[test]
+fn test_serde_field_attributes() {
+    let td = SerdeFields::typescript();
+    let rendered = inner_def(td).render();
+    assert!(rendered.contains("ID: number"));
+    assert!(rendered.contains("outName: string"));
+    // alias only affects deserialization
+    assert!(rendered.contains("email: string"));
+    // skip and skip_serializing never appear in the output
+    assert!(!rendered.contains("secret"));
+    assert!(!rendered.contains("write_only"));
+    // skip_deserializing fields are still serialized
+    assert!(rendered.contains("read_only: string"));
+    assert!(rendered.contains("retries?: number"));
+    assert!(rendered.contains("port?: number"));
+    assert!(rendered.contains("payload?: number[]"));
+    assert!(rendered.contains("inner_field: string"));
+    assert!(!rendered.contains("inner:"));
+}
+
+// ============================================================================
+// FLATTEN TESTS
+// ============================================================================
+
+#[derive(Serialize, TS)]
+struct Extra {
+    id: u8,
+    #[serde(flatten)]
+    rest: HashMap<String, String>,
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "kind")]
+enum Payment {
+    Card { last4: String },
+    Cash,
+}
+
+#[derive(Serialize, TS)]
+struct Order {
+    id: u8,
+    #[serde(flatten)]
+    payment: Payment,
+}
+
+#[derive(Serialize, TS)]
+struct Discount {
+    percent: u8,
+}
+
+#[derive(Serialize, TS)]
+struct Line {
+    sku: String,
+    #[serde(flatten)]
+    discount: Option<Discount>,
+}
+
+#[test]
+fn test_serde_flatten_map() {
+    assert_eq!(
+        inner_def(Extra::typescript()).render(),
+        "{ id: number } & Record<string, string | number>"
+    );
+    let extra = Extra { id: 1, rest: HashMap::from([("note".to_string(), "hi".to_string())]) };
+    assert_eq!(serde_json::to_string(&extra).unwrap(), r#"{"id":1,"note":"hi"}"#);
+}
+
+#[test]
+fn test_serde_flatten_enum() {
+    assert_eq!(inner_def(Order::typescript()).render(), "{ id: number } & Payment");
+    let order = Order { id: 1, payment: Payment::Card { last4: "4242".into() } };
+    assert_eq!(serde_json::to_string(&order).unwrap(), r#"{"id":1,"kind":"Card","last4":"4242"}"#);
+    let order = Order { id: 2, payment: Payment::Cash };
+    assert_eq!(serde_json::to_string(&order).unwrap(), r#"{"id":2,"kind":"Cash"}"#);
+}
+
+#[test]
+fn test_serde_flatten_option() {
+    assert_eq!(inner_def(Line::typescript()).render(), "{ sku: string } & (Discount | {})");
+    let line = Line { sku: "a".into(), discount: None };
+    assert_eq!(serde_json::to_string(&line).unwrap(), r#"{"sku":"a"}"#);
+    let line = Line { sku: "a".into(), discount: Some(Discount { percent: 5 }) };
+    assert_eq!(serde_json::to_string(&line).unwrap(), r#"{"sku":"a","percent":5}"#);
+}
+
+// ============================================================================
+// FIELD SHAPE TESTS
+// ============================================================================
+
+#[derive(Serialize, Deserialize, TS)]
+struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(default)]
+    bio: Option<String>,
+    avatar: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<String>,
+}
+
+#[test]
+fn test_serde_field_shapes() {
+    let td = inner_def(Profile::typescript());
+    assert_eq!(
+        td.render(),
+        "{ nickname?: string; bio?: string | null; avatar: string | null; links?: string[] }"
+    );
+    assert_eq!(
+        td.clone().with_shape(Shape::Output).render(),
+        "{ nickname?: string; bio: string | null; avatar: string | null; links?: string[] }"
+    );
+    assert_eq!(
+        td.with_shape(Shape::Input).render(),
+        "{ nickname?: string | null; bio?: string | null; avatar: string | null; links: string[] }"
+    );
+
+    // Output: `None` is left out or written as null, empty links left out
+    let profile = Profile { nickname: None, bio: None, avatar: None, links: vec![] };
+    assert_eq!(serde_json::to_string(&profile).unwrap(), r#"{"bio":null,"avatar":null}"#);
+
+    // Input: nickname also reads null, bio may be left out, links may not
+    let json = r#"{"nickname":null,"avatar":null,"links":[]}"#;
+    assert!(serde_json::from_str::<Profile>(json).is_ok());
+    assert!(serde_json::from_str::<Profile>(r#"{"avatar":null}"#).is_err());
+}
+
+#[derive(Serialize, Deserialize, Default, TS)]
+#[serde(default)]
+struct Settings {
+    retries: u8,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proxy: Option<String>,
+}
+
+#[test]
+fn test_serde_container_default() {
+    let td = inner_def(Settings::typescript());
+    assert_eq!(td.render(), "{ retries?: number; name?: string; proxy?: string }");
+    assert_eq!(
+        td.clone().with_shape(Shape::Output).render(),
+        "{ retries: number; name: string; proxy?: string }"
+    );
+    assert_eq!(
+        td.with_shape(Shape::Input).render(),
+        "{ retries?: number; name?: string; proxy?: string | null }"
+    );
+    assert_eq!(serde_json::to_string(&Settings::default()).unwrap(), r#"{"retries":0,"name":""}"#);
+    assert!(serde_json::from_str::<Settings>("{}").is_ok());
+}
+
+// ============================================================================
+// PRECEDENCE TESTS
+// ============================================================================
+
+#[derive(Serialize, TS)]
+#[serde(rename = "SerdeName", rename_all = "camelCase")]
+#[ts(rename = "TsName")]
+struct TsOverridesSerde {
+    #[serde(rename = "serdeField")]
+    #[ts(rename = "tsField")]
+    some_field: String,
+    other_field: String,
+}
+
+#[test]
+fn test_ts_attributes_take_precedence() {
+    let td = TsOverridesSerde::typescript();
+    assert_eq!(td.render(), "TsName");
+    let rendered = inner_def(td).render();
+    assert!(rendered.contains("tsField: string"));
+    assert!(!rendered.contains("serdeField"));
+    // serde's rename_all still applies where #[ts] doesn't override it
+    assert!(rendered.contains("otherField: string"));
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "snake_case")]
+enum RenameAllPrecedence {
+    FirstVariant,
+}
+
+#[test]
+fn test_ts_rename_all_takes_precedence() {
+    let td = RenameAllPrecedence::typescript();
+    assert_eq!(inner_def(td).render(), r#""first_variant""#);
+}
+
+// ============================================================================
+// VARIANT ATTRIBUTE TESTS
+// ============================================================================
+
+#[derive(Serialize, TS)]
+enum SerdeVariants {
+    #[serde(rename = "on")]
+    On,
+    #[serde(alias = "disabled")]
+    Off,
+    #[serde(skip)]
+    Internal,
+}
+
+#[test]
+fn test_serde_variant_attributes() {
+    let td = SerdeVariants::typescript();
+    assert_eq!(inner_def(td).render(), r#""on" | "Off""#);
+}
+
+// ============================================================================
+// ENUM REPRESENTATION TESTS
+// ============================================================================
+
+#[derive(Serialize, TS)]
+struct SerdeLocation {
+    lat: f64,
+    lng: f64,
+}
+
+#[derive(Serialize, TS)]
+enum SerdeExternal {
+    Ping,
+    Text(String),
+    Pair(i32, i32),
+    Moved { x: i32 },
+}
+
+#[test]
+fn test_serde_externally_tagged_by_default() {
+    let td = SerdeExternal::typescript();
+    assert_eq!(
+        inner_def(td).render(),
+        r#""Ping" | { Text: string } | { Pair: [number, number] } | { Moved: { x: number } }"#
+    );
+
+    let json = |v: &SerdeExternal| serde_json::to_string(v).unwrap();
+    assert_eq!(json(&SerdeExternal::Ping), r#""Ping""#);
+    assert_eq!(json(&SerdeExternal::Text("hi".into())), r#"{"Text":"hi"}"#);
+    assert_eq!(json(&SerdeExternal::Pair(1, 2)), r#"{"Pair":[1,2]}"#);
+    assert_eq!(json(&SerdeExternal::Moved { x: 3 }), r#"{"Moved":{"x":3}}"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "type")]
+enum SerdeInternal {
+    Ping,
+    At(SerdeLocation),
+    Moved { x: i32 },
+}
+
+#[test]
+fn test_serde_internally_tagged_newtype_merges_fields() {
+    let td = SerdeInternal::typescript();
+    assert_eq!(
+        inner_def(td).render(),
+        r#"{ type: "Ping" } | { type: "At" } & SerdeLocation | { type: "Moved"; x: number }"#
+    );
+
+    let json = serde_json::to_string(&SerdeInternal::At(SerdeLocation { lat: 1.5, lng: 2.5 })).unwrap();
+    assert_eq!(json, r#"{"type":"At","lat":1.5,"lng":2.5}"#);
+    let json = serde_json::to_string(&SerdeInternal::Ping).unwrap();
+    assert_eq!(json, r#"{"type":"Ping"}"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "type")]
+enum SerdeInternalMap {
+    Counts(#[ts(int64 = "string")] HashMap<String, u64>),
+}
+
+#[test]
+fn test_serde_internally_tagged_newtype_field_attributes() {
+    let td = SerdeInternalMap::typescript();
+    assert_eq!(inner_def(td).render(), r#"{ type: "Counts" } & Record<string, string>"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "t", content = "c")]
+enum SerdeAdjacentUnit {
+    Ping,
+    Pair(i32, i32),
+}
+
+#[test]
+fn test_serde_adjacently_tagged_unit_variant() {
+    let td = SerdeAdjacentUnit::typescript();
+    assert_eq!(
+        inner_def(td).render(),
+        r#"{ t: "Ping" } | { t: "Pair"; c: [number, number] }"#
+    );
+    let json = serde_json::to_string(&SerdeAdjacentUnit::Ping).unwrap();
+    assert_eq!(json, r#"{"t":"Ping"}"#);
+}
+
+#[derive(Serialize, TS)]
+#[serde(untagged)]
+enum SerdeUntaggedUnit {
+    Missing,
+    Value(i32),
+}
+
+#[test]
+fn test_serde_untagged_unit_variant_is_null() {
+    let td = SerdeUntaggedUnit::typescript();
+    assert_eq!(inner_def(td).render(), "null | number");
+    let json = serde_json::to_string(&SerdeUntaggedUnit::Missing).unwrap();
+    assert_eq!(json, "null");
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "event", rename_all = "snake_case", rename_all_fields = "camelCase")]
+enum SerdeRenameAllFields {
+    SignedUp {
+        user_id: u32,
+        #[serde(skip_serializing)]
+        password_hash: String,
+    },
+}
+
+#[test]
+fn test_serde_rename_all_fields() {
+    let td = SerdeRenameAllFields::typescript();
+    assert_eq!(
+        inner_def(td).render(),
+        r#"{ event: "signed_up"; userId: number }"#
+    );
+    let json = serde_json::to_string(&SerdeRenameAllFields::SignedUp {
+        user_id: 7,
+        password_hash: String::new(),
+    })
+    .unwrap();
+    assert_eq!(json, r#"{"event":"signed_up","userId":7}"#);
+}
+
+#[derive(serde_repr::Serialize_repr, TS)]
+#[ts(repr = "number")]
+#[repr(u8)]
+enum SerdeReprLevel {
+    Debug = 10,
+    Info = 20,
+    Warn,
+}
+
+#[test]
+fn test_serde_repr_numeric_enum() {
+    assert_eq!(inner_def(SerdeReprLevel::typescript()).render(), "10 | 20 | 21");
+
+    let json = |v: &SerdeReprLevel| serde_json::to_string(v).unwrap();
+    assert_eq!(json(&SerdeReprLevel::Debug), "10");
+    assert_eq!(json(&SerdeReprLevel::Warn), "21");
+}
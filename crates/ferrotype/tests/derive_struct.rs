@@ -1,5 +1,7 @@
 //! Tests for #[derive(TS)] on structs
 
+#![allow(dead_code)]
+
 use ferro_type::{TS, TypeDef, TypeRegistry};
 
 /// Helper to get the inner definition from a Named TypeDef
@@ -10,6 +12,14 @@ fn inner_def(td: TypeDef) -> TypeDef {
     }
 }
 
+/// Helper to get the GenericDef declaration carried by a generic application
+fn generic_def(td: TypeDef) -> TypeDef {
+    match td {
+        TypeDef::Generic { def: Some(def), .. } => *def,
+        other => panic!("expected a generic application, got {:?}", other),
+    }
+}
+
 // ============================================================================
 // NAMED STRUCT TESTS
 // ============================================================================
@@ -118,10 +128,14 @@ struct Container<T> {
 #[test]
 fn test_generic_struct() {
     let td_string = Container::<String>::typescript();
-    assert!(inner_def(td_string).render().contains("value: string"));
+    assert_eq!(td_string.render(), "Container<string>");
+    assert_eq!(
+        generic_def(td_string).render_declaration(),
+        "type Container<T> = { value: T };"
+    );
 
     let td_i32 = Container::<i32>::typescript();
-    assert!(inner_def(td_i32).render().contains("value: number"));
+    assert_eq!(td_i32.render(), "Container<number>");
 }
 
 #[derive(TS)]
@@ -133,9 +147,149 @@ struct Pair<A, B> {
 #[test]
 fn test_multi_generic_struct() {
     let td = Pair::<String, i32>::typescript();
-    let rendered = inner_def(td).render();
-    assert!(rendered.contains("first: string"));
-    assert!(rendered.contains("second: number"));
+    assert_eq!(td.render(), "Pair<string, number>");
+    let rendered = generic_def(td).render_declaration();
+    assert_eq!(rendered, "type Pair<A, B> = { first: A; second: B };");
+}
+
+#[derive(TS)]
+struct Page<T> {
+    items: Vec<T>,
+    next: Option<String>,
+}
+
+#[derive(TS)]
+struct Author {
+    name: String,
+}
+
+#[derive(TS)]
+struct Post {
+    title: String,
+}
+
+#[derive(TS)]
+struct Feed {
+    authors: Page<Author>,
+    posts: Page<Post>,
+}
+
+#[test]
+fn test_generic_instantiations_share_declaration() {
+    let mut registry = TypeRegistry::new();
+    registry.register::<Feed>();
+
+    // Both instantiations reference the single generic declaration
+    assert_eq!(
+        registry.render(),
+        "// Generated by ferrotype\n// Do not edit manually\n\n\
+         type Page<T> = { items: T[]; next: string | null };\n\n\
+         type Author = { name: string };\n\n\
+         type Post = { title: string };\n\n\
+         type Feed = { authors: Page<Author>; posts: Page<Post> };\n"
+    );
+    // The declaration is placed in the module the type is defined in
+    assert!(matches!(
+        registry.get("Page"),
+        Some(TypeDef::GenericDef { module: Some(module), .. }) if module == module_path!()
+    ));
+}
+
+#[derive(TS)]
+struct GenericTree<T> {
+    value: T,
+    children: Vec<GenericTree<T>>,
+}
+
+#[test]
+fn test_recursive_generic_declaration() {
+    let td = GenericTree::<i32>::typescript();
+    assert_eq!(td.render(), "GenericTree<number>");
+    assert_eq!(
+        generic_def(td).render_declaration(),
+        "type GenericTree<T> = { value: T; children: GenericTree<T>[] };"
+    );
+}
+
+trait Identified {}
+trait Labeled {}
+
+impl Identified for Author {}
+impl Labeled for Author {}
+
+#[derive(TS)]
+#[ts(bounds(Identified = "{ id: string }", Labeled = "{ name: string }", AsRef = "string"))]
+struct Keyed<K: AsRef<str>, V = Author>
+where
+    V: Identified + Labeled,
+{
+    key: K,
+    value: V,
+}
+
+#[test]
+fn test_generic_constraints_and_defaults() {
+    let td = Keyed::<String>::typescript();
+    assert_eq!(td.render(), "Keyed<string, Author>");
+    assert_eq!(
+        generic_def(td).render_declaration(),
+        "type Keyed<K extends string, V extends { id: string } & { name: string } = Author> = { key: K; value: V };"
+    );
+}
+
+trait Entity {
+    type Id: TS;
+}
+
+impl Entity for Author {
+    type Id = u32;
+}
+
+#[derive(TS)]
+#[ts(bounds(Entity = "{ id: unknown }"))]
+struct EntityRef<T: Entity> {
+    item: T,
+}
+
+#[derive(TS)]
+struct EntityPage<T: Entity> {
+    items: Vec<EntityRef<T>>,
+    ids: Vec<T::Id>,
+}
+
+#[test]
+fn test_generic_with_bounds_the_markers_lack() {
+    // `T` is only used bare, so the declaration stays generic
+    let td = EntityRef::<Author>::typescript();
+    assert_eq!(td.render(), "EntityRef<Author>");
+    assert_eq!(
+        generic_def(td).render_declaration(),
+        "type EntityRef<T extends { id: unknown }> = { item: T };"
+    );
+
+    // `EntityRef<T>` and `T::Id` need `T: Entity`, so the type is declared
+    // from its arguments instead
+    let td = EntityPage::<Author>::typescript();
+    assert_eq!(td.render(), "EntityPage");
+    assert_eq!(
+        td.render_declaration(),
+        "type EntityPage = { items: EntityRef<Author>[]; ids: number[] };"
+    );
+}
+
+#[derive(TS)]
+#[ts(wrapper = "Prettify<Readonly<")]
+struct Wrapped<T> {
+    inner: Option<T>,
+}
+
+#[test]
+fn test_generic_wrapper() {
+    let td = Wrapped::<bool>::typescript();
+    assert_eq!(
+        generic_def(td).render_declaration(),
+        "type Wrapped<T> = Prettify<Readonly<{ inner: T | null }>>;"
+    );
 }
 
 // ============================================================================
@@ -536,6 +690,53 @@ fn test_transparent_complex() {
     assert_eq!(td.render(), "string[]");
 }
 
+// ============================================================================
+// INT POLICY ATTRIBUTE TESTS
+// ============================================================================
+
+#[derive(TS)]
+struct Snowflakes {
+    #[ts(int64 = "string")]
+    id: u64,
+    #[ts(int64 = "bigint")]
+    parent: Option<i64>,
+    #[ts(int64 = "number_or_string", int128 = "string")]
+    history: Vec<(usize, u128)>,
+    #[ts(int64 = "number")]
+    count: u64,
+    total: i128,
+    raw: u64,
+}
+
+#[derive(TS)]
+#[ts(transparent)]
+struct SnowflakeId(#[ts(int64 = "string")] u64);
+
+#[derive(TS)]
+struct Ticket(#[ts(int64 = "string")] u64, u32);
+
+#[test]
+fn test_int_policy_attribute() {
+    assert_eq!(
+        inner_def(Snowflakes::typescript()).render(),
+        "{ id: string; parent: bigint | null; history: [number | string, string][]; count: number; \
+         total: bigint; raw: number }"
+    );
+    assert_eq!(SnowflakeId::typescript().render(), "string");
+    assert_eq!(inner_def(Ticket::typescript()).render(), "[string, number]");
+}
+
+#[test]
+fn test_int_policy_attribute_wins_over_registry() {
+    let mut registry = TypeRegistry::new();
+    registry.set_int_policy(ferro_type::IntWidth::Bits64, ferro_type::IntPolicy::BigInt);
+    registry.register::<Snowflakes>();
+    assert!(registry.render().contains(
+        "type Snowflakes = { id: string; parent: bigint | null; history: [number | string, string][]; \
+         count: number; total: bigint; raw: bigint };"
+    ));
+}
+
 // ============================================================================
 // DEFAULT ATTRIBUTE TESTS
 // ============================================================================
@@ -1134,6 +1335,31 @@ fn test_extends_with_type_override() {
     assert!(rendered.contains("item_type: \"tree\" | \"blob\""));
 }
 
+// Struct declared as an interface
+#[derive(TS)]
+#[ts(interface, extends = "BaseTodo")]
+struct InterfaceSubtask {
+    created_at: i64,
+}
+
+#[derive(TS)]
+#[ts(interface)]
+struct InterfacePage<T> {
+    items: Vec<T>,
+}
+
+#[test]
+fn test_interface_declaration() {
+    assert_eq!(
+        InterfaceSubtask::typescript().render_declaration(),
+        "interface InterfaceSubtask extends BaseTodo { created_at: number }"
+    );
+    assert_eq!(
+        generic_def(InterfacePage::<String>::typescript()).render_declaration(),
+        "interface InterfacePage<T> { items: T[] }"
+    );
+}
+
 // =============================================================================
 // Indexed Access with Compile-Time Validation (New Syntax)
 // =============================================================================
@@ -1175,3 +1401,128 @@ fn test_indexed_access_mixed() {
     assert!(rendered.contains("profile_avatar: Profile[\"avatar_url\"]"));
     assert!(rendered.contains("external_ref: ExternalTsType[\"someKey\"]"));
 }
+
+// =============================================================================
+// RECURSIVE TYPE TESTS
+// =============================================================================
+
+// Self-referential struct
+#[derive(TS)]
+struct TreeNode {
+    value: String,
+    children: Vec<TreeNode>,
+}
+
+#[test]
+fn test_self_recursive_struct() {
+    let td = TreeNode::typescript();
+    assert_eq!(
+        td.render_declaration(),
+        "type TreeNode = { value: string; children: TreeNode[] };"
+    );
+}
+
+// Self-reference through Option<Box<T>>
+#[derive(TS)]
+struct LinkedNode {
+    value: i32,
+    next: Option<Box<LinkedNode>>,
+}
+
+#[test]
+fn test_self_recursive_through_box() {
+    let td = LinkedNode::typescript();
+    assert_eq!(
+        td.render_declaration(),
+        "type LinkedNode = { value: number; next: LinkedNode | null };"
+    );
+}
+
+// Mutually recursive structs: Thread -> Comment -> Thread
+#[derive(TS)]
+struct Thread {
+    title: String,
+    comments: Vec<Comment>,
+}
+
+#[derive(TS)]
+struct Comment {
+    body: String,
+    replies: Option<Box<Thread>>,
+}
+
+#[test]
+fn test_mutually_recursive_structs() {
+    let mut registry = TypeRegistry::new();
+    registry.register::<Thread>();
+
+    assert_eq!(registry.len(), 2);
+    assert_eq!(
+        registry.get("Thread").unwrap().render_declaration(),
+        "type Thread = { title: string; comments: Comment[] };"
+    );
+    assert_eq!(
+        registry.get("Comment").unwrap().render_declaration(),
+        "type Comment = { body: string; replies: Thread | null };"
+    );
+    assert_eq!(registry.cycles(), vec![vec!["Thread", "Comment"]]);
+}
+
+#[test]
+fn test_mutually_recursive_render_is_deterministic() {
+    let render = |register_comment_first: bool| {
+        let mut registry = TypeRegistry::new();
+        if register_comment_first {
+            registry.register::<Comment>();
+        }
+        registry.register::<Thread>();
+        registry.render()
+    };
+
+    let output = render(false);
+    assert_eq!(output, render(false));
+    assert!(output.contains("type Thread = { title: string; comments: Comment[] };"));
+    assert!(output.contains("type Comment = { body: string; replies: Thread | null };"));
+
+    // The cycle is emitted in registration order
+    let thread_pos = output.find("type Thread").unwrap();
+    let comment_pos = output.find("type Comment").unwrap();
+    assert!(thread_pos < comment_pos);
+
+    let output = render(true);
+    let thread_pos = output.find("type Thread").unwrap();
+    let comment_pos = output.find("type Comment").unwrap();
+    assert!(comment_pos < thread_pos);
+}
+
+/// Recursive enum modeling an expression AST
+#[derive(TS)]
+enum Expr {
+    Number(f64),
+    Neg(Box<Expr>),
+    Add { left: Box<Expr>, right: Box<Expr> },
+}
+
+#[test]
+fn test_recursive_enum() {
+    let td = Expr::typescript();
+    let rendered = inner_def(td).render();
+    assert!(rendered.contains("{ Neg: Expr }"));
+    assert!(rendered.contains("{ Add: { left: Expr; right: Expr } }"));
+}
+
+/// A namespaced recursive type refers back to its qualified name
+#[derive(TS)]
+#[ts(namespace = "Ast")]
+struct Block {
+    statements: Vec<Block>,
+}
+
+#[test]
+fn test_recursive_namespaced_type() {
+    let mut registry = TypeRegistry::new();
+    registry.register::<Block>();
+    assert_eq!(registry.len(), 1);
+    assert!(registry.render().contains("type Block = { statements: Ast.Block[] };"));
+    assert!(registry.is_recursive("Ast.Block"));
+}
@@ -0,0 +1,140 @@
+//! Tests for the TS impls of standard library types
+
+#![allow(dead_code)]
+
+use ferro_type::{TS, TypeDef};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::num::{NonZeroU32, NonZeroU128, Wrapping};
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+/// Helper to get the inner definition from a Named TypeDef
+fn inner_def(td: TypeDef) -> TypeDef {
+    match td {
+        TypeDef::Named { def, .. } => *def,
+        other => other,
+    }
+}
+
+#[derive(TS, Serialize)]
+struct Collections {
+    hash_set: HashSet<String>,
+    btree_set: BTreeSet<u8>,
+    deque: VecDeque<bool>,
+    list: LinkedList<char>,
+    heap: BinaryHeap<u32>,
+    array: [u8; 3],
+    large_array: [u8; 32],
+    empty_array: [String; 0],
+}
+
+#[derive(TS, Serialize)]
+struct Wrappers<'a> {
+    slice: &'a [u16],
+    text: &'a str,
+    cow: Cow<'a, str>,
+    cow_slice: Cow<'a, [u8]>,
+    boxed: Box<str>,
+    mutex: Mutex<i32>,
+    rw_lock: RwLock<Vec<String>>,
+    wrapping: Wrapping<u8>,
+    reverse: Reverse<f64>,
+    non_zero: NonZeroU32,
+    large_non_zero: NonZeroU128,
+}
+
+#[derive(TS, Serialize)]
+struct Marked<T> {
+    id: u32,
+    marker: PhantomData<T>,
+}
+
+#[derive(TS, Serialize)]
+struct Platform {
+    timeout: Duration,
+    modified: SystemTime,
+    path: PathBuf,
+    ip: IpAddr,
+    addr: SocketAddr,
+}
+
+#[test]
+fn test_std_impl_snapshots() {
+    insta::assert_snapshot!("std_collections", inner_def(Collections::typescript()).render());
+    insta::assert_snapshot!("std_wrappers", inner_def(Wrappers::typescript()).render());
+    insta::assert_snapshot!("std_platform", inner_def(Platform::typescript()).render());
+    insta::assert_snapshot!(
+        "std_tuple_16",
+        <(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, String, bool)>::typescript().render()
+    );
+}
+
+#[test]
+fn test_phantom_data_is_omitted() {
+    let TypeDef::Generic { def: Some(def), .. } = <Marked<String>>::typescript() else {
+        panic!("expected a generic application");
+    };
+    assert_eq!(def.render_declaration(), "type Marked<T> = { id: number };");
+    // On its own, PhantomData serializes as unit
+    assert_eq!(<PhantomData<String>>::typescript().render(), "null");
+    assert_eq!(serde_json::to_string(&PhantomData::<String>).unwrap(), "null");
+}
+
+#[test]
+fn test_std_impls_match_serde() {
+    let platform = Platform {
+        timeout: Duration::new(5, 30),
+        modified: SystemTime::UNIX_EPOCH + Duration::from_secs(10),
+        path: PathBuf::from("/tmp"),
+        ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 80),
+    };
+    assert_eq!(
+        serde_json::to_string(&platform).unwrap(),
+        r#"{"timeout":{"secs":5,"nanos":30},"modified":{"secs_since_epoch":10,"nanos_since_epoch":0},"path":"/tmp","ip":"127.0.0.1","addr":"127.0.0.1:80"}"#
+    );
+
+    assert_eq!(serde_json::to_string(&[1u8, 2, 3]).unwrap(), "[1,2,3]");
+    assert_eq!(serde_json::to_string(&Reverse(Wrapping(2u8))).unwrap(), "2");
+    assert_eq!(serde_json::to_string(&Mutex::new(NonZeroU32::new(4))).unwrap(), "4");
+}
+
+#[derive(TS, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    Open,
+    Closed,
+}
+
+#[derive(TS, Serialize)]
+struct Keyed {
+    by_id: BTreeMap<u32, String>,
+    by_status: BTreeMap<Status, u8>,
+    by_flag: BTreeMap<bool, u8>,
+}
+
+#[test]
+fn test_map_keys_match_serde() {
+    assert_eq!(
+        inner_def(Keyed::typescript()).render(),
+        "{ by_id: Record<`${number}`, string>; by_status: Partial<Record<Status, number>>; \
+         by_flag: Partial<Record<`${boolean}`, number>> }"
+    );
+    let keyed = Keyed {
+        by_id: BTreeMap::from([(7, "seven".to_string())]),
+        by_status: BTreeMap::from([(Status::Closed, 1)]),
+        by_flag: BTreeMap::from([(true, 2)]),
+    };
+    assert_eq!(
+        serde_json::to_string(&keyed).unwrap(),
+        r#"{"by_id":{"7":"seven"},"by_status":{"Closed":1},"by_flag":{"true":2}}"#
+    );
+    // serde_json can't write tuples as keys, and neither is the key valid
+    assert!(!<(u8, u8)>::typescript().is_json_key());
+    assert!(serde_json::to_string(&BTreeMap::from([((1u8, 2u8), 3u8)])).is_err());
+}
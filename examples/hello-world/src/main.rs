@@ -92,10 +92,10 @@ mod tests {
         let td = HelloResponse::typescript();
         // Named types render as their name
         assert_eq!(td.render(), "HelloResponse");
-        // The declaration shows the full type
+        // The declaration shows the full type, with the doc comment as JSDoc
         assert_eq!(
             td.render_declaration(),
-            "type HelloResponse = { message: string };"
+            "/** Response from the hello RPC method. */\ntype HelloResponse = { message: string };"
         );
     }
 }